@@ -0,0 +1,99 @@
+//! Patient representatives are appointed in the Ubuntu Health core program. Programs that cannot take a
+//! crate dependency on the core program read the appointment PDA through this mirror of its account layout.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+pub const UBUNTU_HEALTH_CORE_PROGRAM_ID: Pubkey = pubkey!("UbuntuHea1thPassNFT111111111111111111111111");
+
+/// Mirror of the core program's `PatientRepresentative` appointment, in field order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PatientRepresentative {
+    pub patient: Pubkey,
+    pub representative: Pubkey,
+    pub role: RepresentativeRole,
+    pub appointed_at: i64,
+    pub scopes: u8,
+    pub expires_at: Option<i64>,
+}
+
+/// Mirror of the core program's `RepresentativeRole`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum RepresentativeRole {
+    FamilyMember,
+    Caseworker,
+    Caregiver,
+}
+
+/// Mirror of the core program's `RepresentativeScope`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RepresentativeScope {
+    ManageTreatment,
+    ManageMilestones,
+    LogRecovery,
+    ReceiveFunds,
+}
+
+impl PatientRepresentative {
+    /// Loads an appointment, checking it is the core program's PDA for this patient and representative;
+    /// any failed check is reported as the caller's `invalid` error
+    pub fn load<E>(
+        appointment_info: &AccountInfo,
+        patient: &Pubkey,
+        representative: &Pubkey,
+        invalid: E,
+    ) -> Result<Self>
+    where
+        E: Into<Error> + Copy,
+    {
+        let (expected_appointment, _) = Pubkey::find_program_address(
+            &[b"patient_representative", patient.as_ref(), representative.as_ref()],
+            &UBUNTU_HEALTH_CORE_PROGRAM_ID,
+        );
+        if *appointment_info.owner != UBUNTU_HEALTH_CORE_PROGRAM_ID || appointment_info.key() != expected_appointment {
+            return Err(invalid.into());
+        }
+        
+        let data = appointment_info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != Self::discriminator() {
+            return Err(invalid.into());
+        }
+        Self::deserialize(&mut &data[8..]).map_err(|_| invalid.into())
+    }
+    
+    /// Whether the appointment covers `scope` at `now`
+    pub fn permits(&self, scope: RepresentativeScope, now: i64) -> bool {
+        self.scopes & (1 << scope as u8) != 0 &&
+            self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+    
+    /// Anchor account discriminator used by the core program
+    fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"account:PatientRepresentativeAccount").to_bytes()[..8]);
+        discriminator
+    }
+}
+
+/// Whether `signer` is the patient or holds a current appointment from them covering `scope`
+pub fn acts_for_patient<E>(
+    signer: &Pubkey,
+    patient: &Pubkey,
+    representative: Option<&AccountInfo>,
+    scope: RepresentativeScope,
+    invalid: E,
+) -> Result<bool>
+where
+    E: Into<Error> + Copy,
+{
+    if signer == patient {
+        return Ok(true);
+    }
+    
+    Ok(match representative {
+        Some(representative) => PatientRepresentative::load(representative, patient, signer, invalid)?
+            .permits(scope, Clock::get()?.unix_timestamp),
+        None => false,
+    })
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use patient_representatives::RepresentativeScope;
 
 declare_id!("11111111111111111111111111111114");
 
@@ -17,6 +18,16 @@ pub mod recovery_logs {
         community_witnesses: Vec<Pubkey>,
         privacy_level: PrivacyLevel,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.patient.key(),
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::LogRecovery,
+            )?,
+            RecoveryLogError::UnauthorizedPatientAction
+        );
+        
         let recovery_log = &mut ctx.accounts.recovery_log;
         
         recovery_log.patient = ctx.accounts.patient.key();
@@ -110,6 +121,16 @@ pub mod recovery_logs {
         data_fields_contributed: Vec<String>,
         zk_proof_ipfs: String,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.recovery_log.patient,
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::LogRecovery,
+            )?,
+            RecoveryLogError::UnauthorizedPatientAction
+        );
+        
         let recovery_log = &mut ctx.accounts.recovery_log;
         
         require!(
@@ -149,6 +170,16 @@ pub mod recovery_logs {
         traditional_healing_effectiveness: Option<u8>,
         ubuntu_community_feedback: String,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.patient.key(),
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::LogRecovery,
+            )?,
+            RecoveryLogError::UnauthorizedPatientAction
+        );
+        
         let milestone_summary = &mut ctx.accounts.milestone_summary;
         
         milestone_summary.patient = ctx.accounts.patient.key();
@@ -183,6 +214,16 @@ pub mod recovery_logs {
         ubuntu_lessons_learned: String,
         community_impact_notes: String,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.patient.key(),
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::LogRecovery,
+            )?,
+            RecoveryLogError::UnauthorizedPatientAction
+        );
+        
         let healing_story = &mut ctx.accounts.healing_story;
         
         healing_story.story_chapters.push(StoryChapter {
@@ -206,14 +247,39 @@ pub mod recovery_logs {
     }
 }
 
+/// Whether `signer` is the patient or holds a current core-program appointment from them covering `scope`
+fn acts_for_patient(
+    signer: &Pubkey,
+    patient: &Pubkey,
+    representative: Option<&AccountInfo>,
+    scope: RepresentativeScope,
+) -> Result<bool> {
+    patient_representatives::acts_for_patient(
+        signer,
+        patient,
+        representative,
+        scope,
+        RecoveryLogError::InvalidPatientRepresentative,
+    )
+}
+
 #[derive(Accounts)]
 pub struct LogRecoveryEntry<'info> {
+    /// Patient the entry is about
+    /// CHECK: Only used as a key; the authority must be the patient or hold their LogRecovery delegation
+    pub patient: UncheckedAccount<'info>,
+    
+    /// Patient or their representative
     #[account(mut)]
-    pub patient: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    /// CHECK: Owner, address and layout are validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
     
     #[account(
         init,
-        payer = patient,
+        payer = authority,
         space = 8 + RecoveryLog::INIT_SPACE,
     )]
     pub recovery_log: Account<'info, RecoveryLog>,
@@ -239,7 +305,12 @@ pub struct ElderBlessLog<'info> {
 
 #[derive(Accounts)]
 pub struct ContributeToResearch<'info> {
-    pub patient: Signer<'info>,
+    /// Patient or their representative
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    /// CHECK: Owner, address and layout are validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
     
     #[account(mut)]
     pub recovery_log: Account<'info, RecoveryLog>,
@@ -247,12 +318,21 @@ pub struct ContributeToResearch<'info> {
 
 #[derive(Accounts)]
 pub struct CreateMilestoneSummary<'info> {
+    /// Patient the summary is about
+    /// CHECK: Only used as a key; the authority must be the patient or hold their LogRecovery delegation
+    pub patient: UncheckedAccount<'info>,
+    
+    /// Patient or their representative
     #[account(mut)]
-    pub patient: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    /// CHECK: Owner, address and layout are validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
     
     #[account(
         init,
-        payer = patient,
+        payer = authority,
         space = 8 + MilestoneSummary::INIT_SPACE,
     )]
     pub milestone_summary: Account<'info, MilestoneSummary>,
@@ -262,12 +342,21 @@ pub struct CreateMilestoneSummary<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateHealingStory<'info> {
+    /// Patient whose story this is
+    /// CHECK: Only used as a key; the authority must be the patient or hold their LogRecovery delegation
+    pub patient: UncheckedAccount<'info>,
+    
+    /// Patient or their representative
     #[account(mut)]
-    pub patient: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    /// CHECK: Owner, address and layout are validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
     
     #[account(
         init_if_needed,
-        payer = patient,
+        payer = authority,
         space = 8 + HealingStory::INIT_SPACE,
         seeds = [b"healing_story", patient.key().as_ref()],
         bump
@@ -496,4 +585,8 @@ pub enum RecoveryLogError {
     TraditionalHealingNotesRequired,
     #[msg("Elder blessing required for sacred healing data")]
     ElderBlessingRequired,
+    #[msg("Only the patient or a representative holding the required scope can do this")]
+    UnauthorizedPatientAction,
+    #[msg("Account is not a patient representative appointment from the Ubuntu Health core program")]
+    InvalidPatientRepresentative,
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use patient_representatives::RepresentativeScope;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{self, Mint, Token, TokenAccount, MintTo},
@@ -162,6 +163,16 @@ pub mod treatment_passes {
         cultural_practices_used: Vec<String>,
         community_witnesses: Vec<Pubkey>,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.patient_or_caregiver.key(),
+                &ctx.accounts.treatment_pass.patient,
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::ManageMilestones,
+            )?,
+            TreatmentPassError::UnauthorizedPatientAction
+        );
+        
         let treatment_pass = &mut ctx.accounts.treatment_pass;
         
         require!(
@@ -206,6 +217,12 @@ pub mod treatment_passes {
         ubuntu_community_approval: bool,
     ) -> Result<()> {
         require!(ubuntu_community_approval, TreatmentPassError::NoUbuntuConsent);
+        // Handing the pass to someone else is never delegated, so a representative cannot take it over
+        require_keys_eq!(
+            ctx.accounts.current_patient.key(),
+            ctx.accounts.treatment_pass.patient,
+            TreatmentPassError::UnauthorizedPatientAction
+        );
         
         let treatment_pass = &mut ctx.accounts.treatment_pass;
         let old_patient = treatment_pass.patient;
@@ -230,6 +247,22 @@ pub mod treatment_passes {
     }
 }
 
+/// Whether `signer` is the patient or holds a current core-program appointment from them covering `scope`
+fn acts_for_patient(
+    signer: &Pubkey,
+    patient: &Pubkey,
+    representative: Option<&AccountInfo>,
+    scope: RepresentativeScope,
+) -> Result<bool> {
+    patient_representatives::acts_for_patient(
+        signer,
+        patient,
+        representative,
+        scope,
+        TreatmentPassError::InvalidPatientRepresentative,
+    )
+}
+
 #[derive(Accounts)]
 #[instruction(patient: Pubkey)]
 pub struct MintTreatmentPass<'info> {
@@ -295,10 +328,15 @@ pub struct CompleteMilestone<'info> {
     
     #[account(mut)]
     pub treatment_pass: Account<'info, TreatmentPass>,
+    
+    /// Caregiver's appointment as the patient's representative; omitted when the patient acts
+    /// CHECK: Owner, address and layout are validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
 pub struct TransferPass<'info> {
+    /// Current patient; transfers require their own signature
     pub current_patient: Signer<'info>,
     
     #[account(mut)]
@@ -408,4 +446,8 @@ pub enum TreatmentPassError {
     TreatmentCompleted,
     #[msg("Elder approval required")]
     ElderApprovalRequired,
+    #[msg("Only the patient or a representative holding the required scope can do this")]
+    UnauthorizedPatientAction,
+    #[msg("Account is not a patient representative appointment from the Ubuntu Health core program")]
+    InvalidPatientRepresentative,
 }
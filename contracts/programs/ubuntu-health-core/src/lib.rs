@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Mint};
 
-declare_id!("11111111111111111111111111111112");
+declare_id!("UbuntuMemberRegistry11111111111111111111111");
 
 #[program]
 pub mod ubuntu_health_core {
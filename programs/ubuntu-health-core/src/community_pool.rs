@@ -0,0 +1,339 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::treatment_pass_nft::{
+    SponsorContribution, SponsorContributionAccount, TreatmentPassAccount, TreatmentPassStatus,
+};
+use crate::verifier_registry::VerifierRegistryAccount;
+
+/// Community overflow pool
+/// Collects surplus sponsorship from overfunded passes and grants it to passes still seeking funding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CommunityOverflowPool {
+    /// Authority allowed to grant pooled funds to treatment passes
+    pub authority: Pubkey,
+    /// Token account holding pooled funds, owned by the pool PDA
+    pub vault: Pubkey,
+    /// Total surplus received from overfunded passes
+    pub total_received: u64,
+    /// Total granted to other treatment passes
+    pub total_granted: u64,
+    /// Pool creation timestamp
+    pub created_at: i64,
+}
+
+#[account]
+pub struct CommunityOverflowPoolAccount {
+    pub pool: CommunityOverflowPool,
+    pub bump: u8,
+}
+
+impl CommunityOverflowPoolAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // vault
+        8 + // total_received
+        8 + // total_granted
+        8 + // created_at
+        1; // bump
+}
+
+/// Instructions for community overflow pool operations
+#[derive(Accounts)]
+pub struct InitializeCommunityOverflowPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CommunityOverflowPoolAccount::LEN,
+        seeds = [b"community_overflow_pool"],
+        bump
+    )]
+    pub community_pool: Account<'info, CommunityOverflowPoolAccount>,
+    
+    /// Vault for pooled funds; must already be owned by the pool PDA
+    #[account(
+        constraint = community_pool_vault.owner == community_pool.key() @ ErrorCode::InvalidCommunityPoolVault
+    )]
+    pub community_pool_vault: Account<'info, TokenAccount>,
+    
+    /// Only the platform authority may establish the pool
+    #[account(
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump,
+        constraint = registry_account.registry.platform_authority == authority.key() @ ErrorCode::UnauthorizedPlatformAuthority
+    )]
+    pub registry_account: Account<'info, VerifierRegistryAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrantOverflowFunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"community_overflow_pool"],
+        bump = community_pool.bump,
+        constraint = community_pool.pool.authority == authority.key() @ ErrorCode::UnauthorizedPoolAuthority
+    )]
+    pub community_pool: Account<'info, CommunityOverflowPoolAccount>,
+    
+    #[account(
+        mut,
+        address = community_pool.pool.vault @ ErrorCode::InvalidCommunityPoolVault
+    )]
+    pub community_pool_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// The pool's contribution record on the receiving pass, so unreleased grants can be reclaimed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SponsorContributionAccount::LEN,
+        seeds = [b"sponsor_contribution", treatment_pass_account.key().as_ref(), community_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_contribution: Account<'info, SponsorContributionAccount>,
+    
+    /// Escrow account of the receiving pass, owned by the treatment pass PDA
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount,
+        constraint = escrow_account.mint == community_pool_vault.mint @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimOverflowGrant<'info> {
+    #[account(
+        mut,
+        seeds = [b"community_overflow_pool"],
+        bump = community_pool.bump
+    )]
+    pub community_pool: Account<'info, CommunityOverflowPoolAccount>,
+    
+    #[account(
+        mut,
+        address = community_pool.pool.vault @ ErrorCode::InvalidCommunityPoolVault
+    )]
+    pub community_pool_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"sponsor_contribution", treatment_pass_account.key().as_ref(), community_pool.key().as_ref()],
+        bump = pool_contribution.bump
+    )]
+    pub pool_contribution: Account<'info, SponsorContributionAccount>,
+    
+    /// Escrow account of the refundable pass
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Community overflow pool instruction implementations
+pub fn initialize_community_overflow_pool(ctx: Context<InitializeCommunityOverflowPool>) -> Result<()> {
+    let community_pool = &mut ctx.accounts.community_pool;
+    community_pool.bump = ctx.bumps.community_pool;
+    
+    community_pool.pool = CommunityOverflowPool {
+        authority: ctx.accounts.authority.key(),
+        vault: ctx.accounts.community_pool_vault.key(),
+        total_received: 0,
+        total_granted: 0,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+    
+    emit!(CommunityOverflowPoolInitialized {
+        authority: ctx.accounts.authority.key(),
+        vault: ctx.accounts.community_pool_vault.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn grant_overflow_funds(ctx: Context<GrantOverflowFunds>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let community_pool_key = ctx.accounts.community_pool.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    require!(
+        treatment_pass.is_accepting_funding(clock.unix_timestamp),
+        ErrorCode::TreatmentPassNotFundable
+    );
+    
+    // Grants never push a pass past its target
+    let granted_amount = amount.min(treatment_pass.remaining_funding());
+    require!(granted_amount > 0, ErrorCode::InvalidGrantAmount);
+    
+    // Transfer pooled funds to the pass escrow
+    let seeds = &[b"community_overflow_pool".as_ref(), &[ctx.accounts.community_pool.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.community_pool_vault.to_account_info(),
+        to: ctx.accounts.escrow_account.to_account_info(),
+        authority: ctx.accounts.community_pool.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, granted_amount)?;
+    
+    // Granted funds are not earmarked to any milestone
+    treatment_pass.unearmarked_funding = treatment_pass.unearmarked_funding.checked_add(granted_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    treatment_pass.record_funding(granted_amount)?;
+    
+    // Record the grant as the pool's contribution to the pass
+    let pool_contribution = &mut ctx.accounts.pool_contribution;
+    if pool_contribution.contribution.wallet != community_pool_key {
+        treatment_pass.sponsor_count = treatment_pass.sponsor_count.checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+    
+        pool_contribution.bump = ctx.bumps.pool_contribution;
+        pool_contribution.contribution = SponsorContribution::new(
+            treatment_pass_key,
+            community_pool_key,
+            clock.unix_timestamp,
+        );
+    }
+    pool_contribution.contribution.amount_contributed = pool_contribution.contribution.amount_contributed
+        .checked_add(granted_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    pool_contribution.contribution.last_sponsored_at = clock.unix_timestamp;
+    
+    let community_pool = &mut ctx.accounts.community_pool;
+    community_pool.pool.total_granted = community_pool.pool.total_granted.checked_add(granted_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    emit!(OverflowFundsGranted {
+        pass_id: treatment_pass.pass_id,
+        amount: granted_amount,
+        total_funded: treatment_pass.current_funding,
+        granted_by: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
+
+/// Permissionless crank returning the pool's unreleased share of a cancelled or expired pass,
+/// or of a completed pass whose failed milestones left part of the general pool unspent
+pub fn reclaim_overflow_grant(ctx: Context<ReclaimOverflowGrant>) -> Result<()> {
+    let treatment_pass = &ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    // Grants are never earmarked, so on a completed pass the refund is only the unspent pool share
+    require!(
+        treatment_pass.status.allows_sponsor_refunds()
+            || treatment_pass.status == TreatmentPassStatus::TreatmentCompleted,
+        ErrorCode::GrantNotReclaimable
+    );
+    
+    let pool_contribution = &mut ctx.accounts.pool_contribution.contribution;
+    require!(!pool_contribution.refund_claimed, ErrorCode::GrantAlreadyReclaimed);
+    
+    let reclaim_amount = treatment_pass.refund_amount(pool_contribution)?;
+    pool_contribution.refund_claimed = true;
+    
+    if reclaim_amount > 0 {
+        let treatment_pass_account = &ctx.accounts.treatment_pass_account;
+        let pass_id_bytes = treatment_pass.pass_id.to_le_bytes();
+        let seeds = &[
+            b"treatment_pass".as_ref(),
+            treatment_pass.patient.as_ref(),
+            pass_id_bytes.as_ref(),
+            &[treatment_pass_account.bump],
+        ];
+        let signer = &[&seeds[..]];
+    
+        // Transfer the unreleased grant from escrow back to the pool
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.escrow_account.to_account_info(),
+            to: ctx.accounts.community_pool_vault.to_account_info(),
+            authority: treatment_pass_account.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, reclaim_amount)?;
+    }
+    
+    let community_pool = &mut ctx.accounts.community_pool;
+    community_pool.pool.total_received = community_pool.pool.total_received.checked_add(reclaim_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    emit!(OverflowGrantReclaimed {
+        pass_id: treatment_pass.pass_id,
+        amount: reclaim_amount,
+    });
+    
+    Ok(())
+}
+
+/// Events for community overflow pool operations
+#[event]
+pub struct CommunityOverflowPoolInitialized {
+    pub authority: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct OverflowFundsGranted {
+    pub pass_id: u64,
+    pub amount: u64,
+    pub total_funded: u64,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct OverflowGrantReclaimed {
+    pub pass_id: u64,
+    pub amount: u64,
+}
+
+/// Custom error codes for community overflow pool operations
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Signer is not the community overflow pool authority")]
+    UnauthorizedPoolAuthority,
+    #[msg("Token account is not the community overflow pool vault")]
+    InvalidCommunityPoolVault,
+    #[msg("Escrow account is not controlled by this treatment pass")]
+    InvalidEscrowAccount,
+    #[msg("Treatment pass is not accepting funding")]
+    TreatmentPassNotFundable,
+    #[msg("Grant amount must be greater than zero and the pass must still need funding")]
+    InvalidGrantAmount,
+    #[msg("Treatment pass is neither refundable nor completed")]
+    GrantNotReclaimable,
+    #[msg("Grant already reclaimed")]
+    GrantAlreadyReclaimed,
+    #[msg("Signer is not the platform authority")]
+    UnauthorizedPlatformAuthority,
+}
@@ -0,0 +1,515 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::treatment_pass_nft::{SponsorContribution, SponsorContributionAccount, TreatmentPass, TreatmentPassAccount};
+
+/// Diaspora remittance pledges
+/// Contacts listed on a pass's diaspora connection pledge a fixed amount per period, drawn through a token
+/// delegation granted to the pledge PDA; anyone may crank a due pledge into the pass escrow
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RemittancePledge {
+    /// Treatment pass receiving the remittances
+    pub treatment_pass: Pubkey,
+    /// Diaspora contact making the pledge
+    pub contributor: Pubkey,
+    /// Contributor's token account the pledge PDA is delegated to draw from
+    pub source_token_account: Pubkey,
+    /// Amount remitted each period
+    pub amount_per_period: u64,
+    /// Length of a remittance period
+    pub period_seconds: i64,
+    /// Earliest time the next remittance may be executed
+    pub next_due_at: i64,
+    /// Remittances still to be executed
+    pub remaining_periods: u16,
+    /// Total amount remitted into the pass escrow so far
+    pub total_remitted: u64,
+    /// Current pledge status
+    pub status: RemittancePledgeStatus,
+    /// When the pledge was created
+    pub created_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum RemittancePledgeStatus {
+    /// Remittances are executed as they fall due
+    Active,
+    /// Every pledged period was remitted
+    Completed,
+    /// Ended early because the pass closed or the contact left its diaspora network
+    Cancelled,
+}
+
+impl RemittancePledge {
+    /// Moves the due date on by one period, without letting missed periods accumulate
+    fn advance_schedule(&mut self, now: i64) -> Result<()> {
+        let next_due_at = self.next_due_at.checked_add(self.period_seconds)
+            .ok_or(ErrorCode::Overflow)?;
+        self.next_due_at = if next_due_at > now {
+            next_due_at
+        } else {
+            now.checked_add(self.period_seconds).ok_or(ErrorCode::Overflow)?
+        };
+    
+        Ok(())
+    }
+}
+
+/// Bounds on the remittance period (weekly to quarterly)
+pub const MIN_REMITTANCE_PERIOD_SECONDS: i64 = 7 * 24 * 60 * 60;
+pub const MAX_REMITTANCE_PERIOD_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+/// Maximum number of periods a single pledge may cover (three years of monthly remittances)
+pub const MAX_REMITTANCE_PERIODS: u16 = 36;
+
+#[account]
+pub struct RemittancePledgeAccount {
+    pub pledge: RemittancePledge,
+    pub bump: u8,
+}
+
+impl RemittancePledgeAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        32 + // contributor
+        32 + // source_token_account
+        8 + // amount_per_period
+        8 + // period_seconds
+        8 + // next_due_at
+        2 + // remaining_periods
+        8 + // total_remitted
+        1 + // status
+        8 + // created_at
+        1; // bump
+}
+
+/// Whether the pass's diaspora connection still lists `contributor` as a contact able to remit
+fn can_remit(treatment_pass: &TreatmentPass, contributor: &Pubkey) -> bool {
+    treatment_pass.diaspora_connection.as_ref().map_or(false, |diaspora_connection| {
+        diaspora_connection.remittance_capability && diaspora_connection.network_contacts.contains(contributor)
+    })
+}
+
+/// Instructions for diaspora remittances
+#[derive(Accounts)]
+pub struct CreateRemittancePledge<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        init,
+        payer = contributor,
+        space = RemittancePledgeAccount::LEN,
+        seeds = [b"remittance_pledge", treatment_pass_account.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub remittance_pledge: Account<'info, RemittancePledgeAccount>,
+    
+    /// Contributor's sponsor record, credited with each executed remittance
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = SponsorContributionAccount::LEN,
+        seeds = [b"sponsor_contribution", treatment_pass_account.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    /// Diaspora contact listed on the pass
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    /// Token account remittances are drawn from; must not already be delegated elsewhere
+    #[account(
+        mut,
+        constraint = source_token_account.owner == contributor.key() @ ErrorCode::InvalidSourceTokenAccount,
+        constraint = source_token_account.mint == escrow_account.mint @ ErrorCode::InvalidSourceTokenAccount,
+        constraint = source_token_account.delegate.is_none() @ ErrorCode::SourceAccountDelegated
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+    
+    /// Pass's escrow, holding its sponsored funds
+    #[account(
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteRemittancePledge<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"remittance_pledge", treatment_pass_account.key().as_ref(), remittance_pledge.pledge.contributor.as_ref()],
+        bump = remittance_pledge.bump
+    )]
+    pub remittance_pledge: Account<'info, RemittancePledgeAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"sponsor_contribution", treatment_pass_account.key().as_ref(), remittance_pledge.pledge.contributor.as_ref()],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    #[account(
+        mut,
+        constraint = source_token_account.key() == remittance_pledge.pledge.source_token_account @ ErrorCode::InvalidSourceTokenAccount
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+    
+    /// Pass's escrow, receiving the remittance
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount,
+        constraint = escrow_account.mint == source_token_account.mint @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    /// Contributor, when cranking their own pledge; lets an automatic cancellation revoke the delegation
+    #[account(
+        constraint = contributor.key() == remittance_pledge.pledge.contributor @ ErrorCode::UnauthorizedContributor
+    )]
+    pub contributor: Option<Signer<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRemittancePledge<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"remittance_pledge", treatment_pass_account.key().as_ref(), contributor.key().as_ref()],
+        bump = remittance_pledge.bump
+    )]
+    pub remittance_pledge: Account<'info, RemittancePledgeAccount>,
+    
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = source_token_account.key() == remittance_pledge.pledge.source_token_account @ ErrorCode::InvalidSourceTokenAccount
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Diaspora contact pledges a recurring remittance and delegates the pledged total to the pledge PDA
+pub fn create_remittance_pledge(
+    ctx: Context<CreateRemittancePledge>,
+    amount_per_period: u64,
+    period_seconds: i64,
+    periods: u16,
+) -> Result<()> {
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let contributor = ctx.accounts.contributor.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let clock = Clock::get()?;
+    
+    require!(
+        treatment_pass.diaspora_connection.as_ref().map_or(false, |d| d.remittance_capability),
+        ErrorCode::RemittanceNotEnabled
+    );
+    require!(can_remit(treatment_pass, &contributor), ErrorCode::NotNetworkContact);
+    require!(
+        treatment_pass.is_accepting_funding(clock.unix_timestamp),
+        ErrorCode::TreatmentPassNotFundable
+    );
+    require!(amount_per_period > 0, ErrorCode::InvalidRemittanceAmount);
+    require!(
+        (MIN_REMITTANCE_PERIOD_SECONDS..=MAX_REMITTANCE_PERIOD_SECONDS).contains(&period_seconds),
+        ErrorCode::InvalidRemittancePeriod
+    );
+    require!(
+        (1..=MAX_REMITTANCE_PERIODS).contains(&periods),
+        ErrorCode::InvalidRemittancePeriodCount
+    );
+    let pledged_total = amount_per_period.checked_mul(periods as u64)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    // Remittances are credited to the contributor's public sponsor record
+    let sponsor_contribution = &mut ctx.accounts.sponsor_contribution;
+    if sponsor_contribution.contribution.treatment_pass != treatment_pass_key {
+        treatment_pass.sponsor_count = treatment_pass.sponsor_count.checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+    
+        sponsor_contribution.bump = ctx.bumps.sponsor_contribution;
+        sponsor_contribution.contribution = SponsorContribution::new(
+            treatment_pass_key,
+            contributor,
+            clock.unix_timestamp,
+        );
+    }
+    require!(
+        !sponsor_contribution.contribution.anonymous,
+        ErrorCode::AnonymousContributionRecord
+    );
+    
+    // Delegate the pledged total so the crank can draw each period without the contributor signing.
+    // A token account has a single delegate, so accounts already delegated elsewhere are rejected above
+    let cpi_accounts = token::Approve {
+        to: ctx.accounts.source_token_account.to_account_info(),
+        delegate: ctx.accounts.remittance_pledge.to_account_info(),
+        authority: ctx.accounts.contributor.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token::approve(cpi_ctx, pledged_total)?;
+    
+    let remittance_pledge = &mut ctx.accounts.remittance_pledge;
+    remittance_pledge.bump = ctx.bumps.remittance_pledge;
+    remittance_pledge.pledge = RemittancePledge {
+        treatment_pass: treatment_pass_key,
+        contributor,
+        source_token_account: ctx.accounts.source_token_account.key(),
+        amount_per_period,
+        period_seconds,
+        // The first remittance is due straight away
+        next_due_at: clock.unix_timestamp,
+        remaining_periods: periods,
+        total_remitted: 0,
+        status: RemittancePledgeStatus::Active,
+        created_at: clock.unix_timestamp,
+    };
+    
+    emit!(RemittancePledgeCreated {
+        pass_id: treatment_pass.pass_id,
+        contributor,
+        amount_per_period,
+        period_seconds,
+        periods,
+    });
+    
+    Ok(())
+}
+
+/// Permissionless crank executing a due remittance into the pass escrow
+pub fn execute_remittance_pledge(ctx: Context<ExecuteRemittancePledge>) -> Result<()> {
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let remittance_pledge = &mut ctx.accounts.remittance_pledge;
+    let clock = Clock::get()?;
+    
+    require!(
+        remittance_pledge.pledge.status == RemittancePledgeStatus::Active,
+        ErrorCode::RemittancePledgeInactive
+    );
+    
+    // Pledges end with the pass, or once the contributor is no longer a listed contact
+    let contributor = remittance_pledge.pledge.contributor;
+    if treatment_pass.status.is_terminal() || !can_remit(treatment_pass, &contributor) {
+        remittance_pledge.pledge.status = RemittancePledgeStatus::Cancelled;
+    
+        // Only the token account owner can revoke; otherwise closing the pledge revokes it later
+        if let Some(contributor_signer) = &ctx.accounts.contributor {
+            if ctx.accounts.source_token_account.delegate == Some(remittance_pledge.key()).into() {
+                let cpi_accounts = token::Revoke {
+                    source: ctx.accounts.source_token_account.to_account_info(),
+                    authority: contributor_signer.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+                token::revoke(cpi_ctx)?;
+            }
+        }
+    
+        emit!(RemittancePledgeEnded {
+            pass_id: treatment_pass.pass_id,
+            contributor,
+            total_remitted: remittance_pledge.pledge.total_remitted,
+            status: RemittancePledgeStatus::Cancelled,
+        });
+    
+        return Ok(());
+    }
+    
+    require!(
+        clock.unix_timestamp >= remittance_pledge.pledge.next_due_at,
+        ErrorCode::RemittanceNotDue
+    );
+    remittance_pledge.pledge.advance_schedule(clock.unix_timestamp)?;
+    
+    // Periods falling while the pass is not raising funds (funded, under review or past its
+    // deadline) are skipped rather than remitted
+    let amount = remittance_pledge.pledge.amount_per_period.min(treatment_pass.remaining_funding());
+    if !treatment_pass.is_accepting_funding(clock.unix_timestamp) || amount == 0 {
+        emit!(RemittanceSkipped {
+            pass_id: treatment_pass.pass_id,
+            contributor,
+            next_due_at: remittance_pledge.pledge.next_due_at,
+        });
+    
+        return Ok(());
+    }
+    
+    let seeds = &[
+        b"remittance_pledge".as_ref(),
+        treatment_pass_key.as_ref(),
+        contributor.as_ref(),
+        &[remittance_pledge.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.source_token_account.to_account_info(),
+        to: ctx.accounts.escrow_account.to_account_info(),
+        authority: remittance_pledge.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, amount)?;
+    
+    // Remittances go to the general pool rather than any single milestone
+    treatment_pass.unearmarked_funding = treatment_pass.unearmarked_funding.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    treatment_pass.record_funding(amount)?;
+    
+    let contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+    contribution.amount_contributed = contribution.amount_contributed.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    contribution.last_sponsored_at = clock.unix_timestamp;
+    
+    let pledge = &mut remittance_pledge.pledge;
+    pledge.total_remitted = pledge.total_remitted.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    pledge.remaining_periods -= 1;
+    
+    emit!(RemittanceExecuted {
+        pass_id: treatment_pass.pass_id,
+        contributor,
+        amount,
+        total_remitted: pledge.total_remitted,
+        remaining_periods: pledge.remaining_periods,
+        total_funded: treatment_pass.current_funding,
+    });
+    
+    if pledge.remaining_periods == 0 {
+        pledge.status = RemittancePledgeStatus::Completed;
+    
+        emit!(RemittancePledgeEnded {
+            pass_id: treatment_pass.pass_id,
+            contributor,
+            total_remitted: pledge.total_remitted,
+            status: RemittancePledgeStatus::Completed,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Contributor closes their pledge, revoking the pledge PDA's delegation if it is still in place
+pub fn close_remittance_pledge(ctx: Context<CloseRemittancePledge>) -> Result<()> {
+    let remittance_pledge_key = ctx.accounts.remittance_pledge.key();
+    
+    if ctx.accounts.source_token_account.delegate == Some(remittance_pledge_key).into() {
+        let cpi_accounts = token::Revoke {
+            source: ctx.accounts.source_token_account.to_account_info(),
+            authority: ctx.accounts.contributor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::revoke(cpi_ctx)?;
+    }
+    
+    let pledge = &ctx.accounts.remittance_pledge.pledge;
+    if pledge.status == RemittancePledgeStatus::Active {
+        emit!(RemittancePledgeEnded {
+            pass_id: ctx.accounts.treatment_pass_account.treatment_pass.pass_id,
+            contributor: pledge.contributor,
+            total_remitted: pledge.total_remitted,
+            status: RemittancePledgeStatus::Cancelled,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Events for diaspora remittances
+#[event]
+pub struct RemittancePledgeCreated {
+    pub pass_id: u64,
+    pub contributor: Pubkey,
+    pub amount_per_period: u64,
+    pub period_seconds: i64,
+    pub periods: u16,
+}
+
+#[event]
+pub struct RemittanceExecuted {
+    pub pass_id: u64,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub total_remitted: u64,
+    pub remaining_periods: u16,
+    pub total_funded: u64,
+}
+
+#[event]
+pub struct RemittanceSkipped {
+    pub pass_id: u64,
+    pub contributor: Pubkey,
+    pub next_due_at: i64,
+}
+
+#[event]
+pub struct RemittancePledgeEnded {
+    pub pass_id: u64,
+    pub contributor: Pubkey,
+    pub total_remitted: u64,
+    pub status: RemittancePledgeStatus,
+}
+
+/// Custom error codes for diaspora remittances
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Treatment pass diaspora connection does not accept remittances")]
+    RemittanceNotEnabled,
+    #[msg("Contributor is not a contact on the pass's diaspora network")]
+    NotNetworkContact,
+    #[msg("Treatment pass is not accepting funding")]
+    TreatmentPassNotFundable,
+    #[msg("Remittance amount must be greater than zero")]
+    InvalidRemittanceAmount,
+    #[msg("Remittance period must be between one week and 90 days")]
+    InvalidRemittancePeriod,
+    #[msg("Pledge must cover between 1 and 36 periods")]
+    InvalidRemittancePeriodCount,
+    #[msg("Remittances cannot be credited to an anonymous contribution")]
+    AnonymousContributionRecord,
+    #[msg("Token account is not the contributor's pledged source account")]
+    InvalidSourceTokenAccount,
+    #[msg("Escrow account is not controlled by this treatment pass")]
+    InvalidEscrowAccount,
+    #[msg("Remittance pledge is no longer active")]
+    RemittancePledgeInactive,
+    #[msg("Next remittance is not yet due")]
+    RemittanceNotDue,
+    #[msg("Source token account is already delegated; revoke it or close the earlier pledge first")]
+    SourceAccountDelegated,
+    #[msg("Signer is not the pledge's contributor")]
+    UnauthorizedContributor,
+}
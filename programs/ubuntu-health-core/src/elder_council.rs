@@ -0,0 +1,475 @@
+use anchor_lang::prelude::*;
+
+use crate::treatment_pass_nft::{
+    ElderCouncilReview, ElderDecision, ElderReview, TreatmentPassAccount, TreatmentPassStatus, VerificationType,
+    MAX_ELDER_GUIDANCE_LEN, MAX_SUPPORT_RECOMMENDATIONS, MAX_SUPPORT_RECOMMENDATION_LEN, MAX_TRADITIONAL_WISDOM_LEN,
+};
+use crate::verifier_registry::VerifierRegistryAccount;
+
+/// Ubuntu elder council
+/// Membership is managed by governance; treatment decisions take effect only once a quorum of
+/// members agrees, and disapproving a treatment additionally needs a supermajority of the council
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ElderCouncil {
+    /// Governance authority managing membership and thresholds
+    pub authority: Pubkey,
+    /// Current council members
+    pub members: Vec<Pubkey>,
+    /// Members who must agree before a decision takes effect
+    pub approval_quorum: u8,
+    /// Share of all members, in basis points, who must back a disapproval
+    pub disapproval_supermajority_basis_points: u16,
+    /// Council creation timestamp
+    pub created_at: i64,
+}
+
+impl ElderCouncil {
+    pub fn is_member(&self, elder: &Pubkey) -> bool {
+        self.members.contains(elder)
+    }
+    
+    /// Whether enough members back a decision for it to take effect
+    pub fn is_carried(&self, decision: &ElderDecision, votes: u8) -> bool {
+        if votes < self.approval_quorum {
+            return false;
+        }
+    
+        match decision {
+            ElderDecision::Disapproved => {
+                (votes as u64) * (BASIS_POINTS as u64) >=
+                    (self.members.len() as u64) * (self.disapproval_supermajority_basis_points as u64)
+            }
+            _ => true,
+        }
+    }
+}
+
+/// Maximum number of elder council members
+pub const MAX_ELDER_COUNCIL_MEMBERS: usize = 21;
+
+/// Smallest quorum governance may configure, so no single elder decides alone
+pub const MIN_ELDER_QUORUM: u8 = 2;
+
+/// Smallest disapproval supermajority governance may configure (two thirds)
+pub const MIN_DISAPPROVAL_SUPERMAJORITY_BASIS_POINTS: u16 = 6_667;
+
+pub const BASIS_POINTS: u16 = 10_000;
+
+#[account]
+pub struct ElderCouncilAccount {
+    pub council: ElderCouncil,
+    pub bump: u8,
+}
+
+impl ElderCouncilAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        4 + MAX_ELDER_COUNCIL_MEMBERS * 32 + // members
+        1 + // approval_quorum
+        2 + // disapproval_supermajority_basis_points
+        8 + // created_at
+        1; // bump
+}
+
+/// Elder votes gathered in one review round of a treatment pass
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ElderReviewTally {
+    pub treatment_pass: Pubkey,
+    pub round: u16,
+    /// Votes cast in the round; only those of current council members count towards a decision
+    pub votes: Vec<ElderVote>,
+    /// Decision that took effect, closing the round
+    pub resolved_decision: Option<ElderDecision>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ElderVote {
+    pub elder: Pubkey,
+    pub decision: ElderDecision,
+}
+
+impl ElderVote {
+    pub const SIZE: usize = 32 + // elder
+        1; // decision
+}
+
+impl ElderReviewTally {
+    /// Records an elder's decision and returns the votes of current members now backing it
+    fn add_vote(&mut self, council: &ElderCouncil, elder: Pubkey, decision: &ElderDecision) -> u8 {
+        // Elders removed since voting no longer count, which also keeps the list within the council's size
+        self.votes.retain(|vote| council.is_member(&vote.elder));
+        self.votes.push(ElderVote { elder, decision: decision.clone() });
+    
+        self.votes.iter()
+            .filter(|vote| backs_same_outcome(&vote.decision, decision))
+            .count() as u8
+    }
+}
+
+/// Approvals with and without guidance back the same outcome
+fn backs_same_outcome(vote: &ElderDecision, decision: &ElderDecision) -> bool {
+    let approves = |d: &ElderDecision| matches!(d, ElderDecision::Approved | ElderDecision::ApprovedWithGuidance);
+    vote == decision || (approves(vote) && approves(decision))
+}
+
+#[account]
+pub struct ElderReviewTallyAccount {
+    pub tally: ElderReviewTally,
+    pub bump: u8,
+}
+
+impl ElderReviewTallyAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        2 + // round
+        4 + MAX_ELDER_COUNCIL_MEMBERS * ElderVote::SIZE + // votes
+        1 + 1 + // resolved_decision
+        1; // bump
+}
+
+/// One elder's review in a review round; its existence prevents a second review from the same elder
+#[account]
+pub struct ElderReviewRecordAccount {
+    pub review: ElderReview,
+    pub bump: u8,
+}
+
+impl ElderReviewRecordAccount {
+    pub const LEN: usize = 8 + // discriminator
+        ElderReview::MAX_SIZE + // review
+        1; // bump
+}
+
+/// Instructions for elder council operations
+#[derive(Accounts)]
+pub struct InitializeElderCouncil<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ElderCouncilAccount::LEN,
+        seeds = [b"elder_council"],
+        bump
+    )]
+    pub elder_council: Account<'info, ElderCouncilAccount>,
+    
+    /// Only the platform authority may establish the council
+    #[account(
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump,
+        constraint = registry_account.registry.platform_authority == authority.key() @ ErrorCode::UnauthorizedCouncilAuthority
+    )]
+    pub registry_account: Account<'info, VerifierRegistryAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageElderCouncil<'info> {
+    #[account(
+        mut,
+        seeds = [b"elder_council"],
+        bump = elder_council.bump,
+        constraint = elder_council.council.authority == authority.key() @ ErrorCode::UnauthorizedCouncilAuthority
+    )]
+    pub elder_council: Account<'info, ElderCouncilAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(pass_id: u64)]
+pub struct ElderCouncilReviewContext<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Account<'info, ElderCouncilAccount>,
+    
+    /// Votes in the pass's current review round, created by the round's first review
+    #[account(
+        init_if_needed,
+        payer = elder,
+        space = ElderReviewTallyAccount::LEN,
+        seeds = [b"elder_review_tally", treatment_pass_account.key().as_ref(), treatment_pass_account.treatment_pass.elder_review_round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub review_tally: Account<'info, ElderReviewTallyAccount>,
+    
+    #[account(
+        init,
+        payer = elder,
+        space = ElderReviewRecordAccount::LEN,
+        seeds = [b"elder_review", review_tally.key().as_ref(), elder.key().as_ref()],
+        bump
+    )]
+    pub review_record: Account<'info, ElderReviewRecordAccount>,
+    
+    /// Elder council member performing the review
+    #[account(mut)]
+    pub elder: Signer<'info>,
+    
+    /// System program
+    pub system_program: Program<'info, System>,
+}
+
+/// Elder council instruction implementations
+pub fn initialize_elder_council(
+    ctx: Context<InitializeElderCouncil>,
+    approval_quorum: u8,
+    disapproval_supermajority_basis_points: u16,
+) -> Result<()> {
+    validate_thresholds(approval_quorum, disapproval_supermajority_basis_points)?;
+    
+    let elder_council = &mut ctx.accounts.elder_council;
+    elder_council.bump = ctx.bumps.elder_council;
+    elder_council.council = ElderCouncil {
+        authority: ctx.accounts.authority.key(),
+        members: Vec::new(),
+        approval_quorum,
+        disapproval_supermajority_basis_points,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+    
+    emit!(ElderCouncilInitialized {
+        authority: ctx.accounts.authority.key(),
+        approval_quorum,
+        disapproval_supermajority_basis_points,
+    });
+    
+    Ok(())
+}
+
+pub fn add_elder_council_member(ctx: Context<ManageElderCouncil>, elder: Pubkey) -> Result<()> {
+    let council = &mut ctx.accounts.elder_council.council;
+    require!(!council.is_member(&elder), ErrorCode::AlreadyCouncilMember);
+    require!(council.members.len() < MAX_ELDER_COUNCIL_MEMBERS, ErrorCode::ElderCouncilFull);
+    
+    council.members.push(elder);
+    
+    emit!(ElderCouncilMembershipChanged {
+        elder,
+        added: true,
+        member_count: council.members.len() as u8,
+    });
+    
+    Ok(())
+}
+
+pub fn remove_elder_council_member(ctx: Context<ManageElderCouncil>, elder: Pubkey) -> Result<()> {
+    let council = &mut ctx.accounts.elder_council.council;
+    let index = council.members.iter().position(|m| *m == elder)
+        .ok_or(ErrorCode::NotCouncilMember)?;
+    
+    council.members.remove(index);
+    
+    emit!(ElderCouncilMembershipChanged {
+        elder,
+        added: false,
+        member_count: council.members.len() as u8,
+    });
+    
+    Ok(())
+}
+
+pub fn set_elder_council_thresholds(
+    ctx: Context<ManageElderCouncil>,
+    approval_quorum: u8,
+    disapproval_supermajority_basis_points: u16,
+) -> Result<()> {
+    validate_thresholds(approval_quorum, disapproval_supermajority_basis_points)?;
+    
+    let council = &mut ctx.accounts.elder_council.council;
+    council.approval_quorum = approval_quorum;
+    council.disapproval_supermajority_basis_points = disapproval_supermajority_basis_points;
+    
+    emit!(ElderCouncilThresholdsUpdated {
+        approval_quorum,
+        disapproval_supermajority_basis_points,
+    });
+    
+    Ok(())
+}
+
+fn validate_thresholds(approval_quorum: u8, disapproval_supermajority_basis_points: u16) -> Result<()> {
+    require!(
+        (MIN_ELDER_QUORUM..=MAX_ELDER_COUNCIL_MEMBERS as u8).contains(&approval_quorum),
+        ErrorCode::InvalidElderQuorum
+    );
+    require!(
+        (MIN_DISAPPROVAL_SUPERMAJORITY_BASIS_POINTS..=BASIS_POINTS).contains(&disapproval_supermajority_basis_points),
+        ErrorCode::InvalidDisapprovalSupermajority
+    );
+    
+    Ok(())
+}
+
+/// Records one elder's review and applies the decision once the council threshold is met
+pub fn elder_council_review(
+    ctx: Context<ElderCouncilReviewContext>,
+    pass_id: u64,
+    decision: ElderDecision,
+    elder_guidance: String,
+    traditional_wisdom: Option<String>,
+    community_support_recommendations: Vec<String>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let elder = ctx.accounts.elder.key();
+    let council = &ctx.accounts.elder_council.council;
+    
+    require!(council.is_member(&elder), ErrorCode::NotCouncilMember);
+    require!(elder_guidance.len() <= MAX_ELDER_GUIDANCE_LEN, ErrorCode::ElderReviewTooLong);
+    require!(
+        traditional_wisdom.as_ref().map_or(0, |w| w.len()) <= MAX_TRADITIONAL_WISDOM_LEN,
+        ErrorCode::ElderReviewTooLong
+    );
+    require!(
+        community_support_recommendations.len() <= MAX_SUPPORT_RECOMMENDATIONS &&
+            community_support_recommendations.iter().all(|r| r.len() <= MAX_SUPPORT_RECOMMENDATION_LEN),
+        ErrorCode::ElderReviewTooLong
+    );
+    
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    // Open the round's tally on its first review
+    let review_tally = &mut ctx.accounts.review_tally;
+    if review_tally.tally.treatment_pass != treatment_pass_key {
+        review_tally.bump = ctx.bumps.review_tally;
+        review_tally.tally = ElderReviewTally {
+            treatment_pass: treatment_pass_key,
+            round: treatment_pass.elder_review_round,
+            votes: Vec::new(),
+            resolved_decision: None,
+        };
+    }
+    let votes = review_tally.tally.add_vote(council, elder, &decision);
+    
+    let elder_review = ElderReview {
+        reviewing_elder: elder,
+        review_timestamp: clock.unix_timestamp,
+        decision: decision.clone(),
+        elder_guidance,
+        traditional_wisdom: traditional_wisdom.clone(),
+        community_support_recommendations,
+    };
+    let review_record_key = ctx.accounts.review_record.key();
+    let review_record = &mut ctx.accounts.review_record;
+    review_record.bump = ctx.bumps.review_record;
+    review_record.review = elder_review;
+    
+    emit!(ElderCouncilReview {
+        pass_id,
+        reviewing_elder: elder,
+        decision: decision.clone(),
+        traditional_wisdom_shared: traditional_wisdom.is_some(),
+    });
+    
+    if !council.is_carried(&decision, votes) {
+        return Ok(());
+    }
+    
+    // Update treatment pass based on the council decision
+    match decision {
+        ElderDecision::Approved | ElderDecision::ApprovedWithGuidance => {
+            treatment_pass.elder_council_approval = true;
+            treatment_pass.approving_elder = Some(elder);
+            if treatment_pass.status == TreatmentPassStatus::ElderCouncilReview {
+                let next = treatment_pass.funding_status();
+                treatment_pass.transition_to(next)?;
+            }
+        }
+        ElderDecision::RequiresMoreInformation => {
+            treatment_pass.transition_to(TreatmentPassStatus::CommunityReview)?;
+        }
+        ElderDecision::Disapproved => {
+            treatment_pass.transition_to(TreatmentPassStatus::TreatmentCancelled)?;
+        }
+        ElderDecision::DeferToCommunity => {
+            treatment_pass.transition_to(TreatmentPassStatus::CommunityReview)?;
+        }
+    }
+    
+    // Point relevant milestones at the deciding review
+    for milestone in &mut treatment_pass.milestones {
+        if milestone.verification_required == VerificationType::ElderCouncilVerification ||
+           milestone.verification_required == VerificationType::IntegratedVerification {
+            milestone.elder_council_review = Some(review_record_key);
+        }
+    }
+    
+    // Close the round; later reviews start a fresh tally
+    let round = treatment_pass.elder_review_round;
+    review_tally.tally.resolved_decision = Some(decision.clone());
+    treatment_pass.elder_review_round = round.checked_add(1).ok_or(ErrorCode::Overflow)?;
+    
+    emit!(ElderCouncilDecisionReached {
+        pass_id,
+        round,
+        decision,
+        votes,
+        member_count: council.members.len() as u8,
+    });
+    
+    Ok(())
+}
+
+/// Events for elder council operations
+#[event]
+pub struct ElderCouncilInitialized {
+    pub authority: Pubkey,
+    pub approval_quorum: u8,
+    pub disapproval_supermajority_basis_points: u16,
+}
+
+#[event]
+pub struct ElderCouncilMembershipChanged {
+    pub elder: Pubkey,
+    pub added: bool,
+    pub member_count: u8,
+}
+
+#[event]
+pub struct ElderCouncilThresholdsUpdated {
+    pub approval_quorum: u8,
+    pub disapproval_supermajority_basis_points: u16,
+}
+
+#[event]
+pub struct ElderCouncilDecisionReached {
+    pub pass_id: u64,
+    pub round: u16,
+    pub decision: ElderDecision,
+    pub votes: u8,
+    pub member_count: u8,
+}
+
+/// Custom error codes for elder council operations
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Signer is not the elder council governance authority")]
+    UnauthorizedCouncilAuthority,
+    #[msg("Signer is not an elder council member")]
+    NotCouncilMember,
+    #[msg("Elder is already a council member")]
+    AlreadyCouncilMember,
+    #[msg("Elder council has reached its maximum size")]
+    ElderCouncilFull,
+    #[msg("Elder council quorum is outside the allowed range")]
+    InvalidElderQuorum,
+    #[msg("Disapproval supermajority is outside the allowed range")]
+    InvalidDisapprovalSupermajority,
+    #[msg("Elder review content exceeds maximum length")]
+    ElderReviewTooLong,
+}
@@ -0,0 +1,397 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::treatment_pass_nft::{
+    SponsorContribution, SponsorContributionAccount, TreatmentPassAccount, TreatmentPassStatus,
+};
+use crate::elder_council::ElderCouncilAccount;
+use crate::verifier_registry::VerifierRegistryAccount;
+
+/// Endorser stakes
+/// Endorsers lock $LIVES when endorsing a treatment pass; the stake returns once the pass is settled
+/// honestly and is slashed to the pass's sponsors if the pass is marked fraudulent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EndorsementStake {
+    /// Endorsed treatment pass
+    pub treatment_pass: Pubkey,
+    /// Endorser who locked the stake
+    pub endorser: Pubkey,
+    /// $LIVES held in the stake vault
+    pub amount: u64,
+    /// When the stake was locked
+    pub staked_at: i64,
+    /// Current stake status
+    pub status: EndorsementStakeStatus,
+    /// Earliest release of a stake on a cancelled or expired pass, set when the challenge period starts
+    pub release_eligible_at: Option<i64>,
+    /// Pass funding when the stake was slashed, the basis for sponsors' shares
+    pub funding_at_slash: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum EndorsementStakeStatus {
+    /// Held until the pass is settled
+    Locked,
+    /// Returned to the endorser
+    Returned,
+    /// Forfeited to the pass's sponsors
+    Slashed,
+}
+
+impl EndorsementStake {
+    pub fn new(treatment_pass: Pubkey, endorser: Pubkey, amount: u64, now: i64) -> Self {
+        EndorsementStake {
+            treatment_pass,
+            endorser,
+            amount,
+            staked_at: now,
+            status: EndorsementStakeStatus::Locked,
+            release_eligible_at: None,
+            funding_at_slash: 0,
+        }
+    }
+}
+
+/// Whole $LIVES an endorser stakes per endorsement, scaled by the mint's decimals
+pub const ENDORSEMENT_STAKE_LIVES: u64 = 100;
+
+/// Time a cancelled or expired pass can still be marked fraudulent before its stake returns (14 days)
+pub const STAKE_CHALLENGE_PERIOD_SECONDS: i64 = 14 * 24 * 60 * 60;
+
+/// Maximum length of a fraud report reason
+pub const MAX_FRAUD_REASON_LEN: usize = 256;
+
+#[account]
+pub struct EndorsementStakeAccount {
+    pub stake: EndorsementStake,
+    pub bump: u8,
+}
+
+impl EndorsementStakeAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        32 + // endorser
+        8 + // amount
+        8 + // staked_at
+        1 + // status
+        1 + 8 + // release_eligible_at
+        8 + // funding_at_slash
+        1; // bump
+}
+
+/// Instructions for endorsement stakes
+#[derive(Accounts)]
+pub struct MarkTreatmentFraudulent<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Platform governance authority or the elder council's governance authority
+    pub authority: Signer<'info>,
+    
+    /// Verifier registry, whose platform authority acts for governance; omitted when the council acts
+    #[account(
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump
+    )]
+    pub registry_account: Option<Account<'info, VerifierRegistryAccount>>,
+    
+    /// Elder council; omitted when platform governance acts
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Option<Account<'info, ElderCouncilAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct SettleEndorsementStake<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"endorsement_stake", treatment_pass_account.key().as_ref()],
+        bump = endorsement_stake.bump
+    )]
+    pub endorsement_stake: Account<'info, EndorsementStakeAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"endorsement_stake_vault", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    /// Endorser's $LIVES account receiving a returned stake
+    #[account(
+        mut,
+        constraint = endorser_token_account.owner == endorsement_stake.stake.endorser @ ErrorCode::InvalidStakeAccount,
+        constraint = endorser_token_account.mint == stake_vault.mint @ ErrorCode::InvalidStakeAccount
+    )]
+    pub endorser_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(identity_salt: Option<[u8; 32]>)]
+pub struct ClaimSlashedStakeShare<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        seeds = [b"endorsement_stake", treatment_pass_account.key().as_ref()],
+        bump = endorsement_stake.bump
+    )]
+    pub endorsement_stake: Account<'info, EndorsementStakeAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"endorsement_stake_vault", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), identity_salt).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    pub sponsor: Signer<'info>,
+    
+    /// Sponsor's $LIVES account receiving their share
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::InvalidStakeAccount,
+        constraint = sponsor_token_account.mint == stake_vault.mint @ ErrorCode::InvalidStakeAccount
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Endorsement stake instruction implementations
+/// Governance or the elder council marks a pass fraudulent, cancelling it so sponsors can be refunded
+pub fn mark_treatment_fraudulent(ctx: Context<MarkTreatmentFraudulent>, reason: String) -> Result<()> {
+    require!(reason.len() <= MAX_FRAUD_REASON_LEN, ErrorCode::FraudReasonTooLong);
+    
+    let authority = ctx.accounts.authority.key();
+    let is_governance = ctx.accounts.registry_account.as_ref()
+        .map_or(false, |registry_account| registry_account.registry.platform_authority == authority);
+    let is_elder_council = ctx.accounts.elder_council.as_ref()
+        .map_or(false, |elder_council| elder_council.council.authority == authority);
+    require!(is_governance || is_elder_council, ErrorCode::UnauthorizedFraudReport);
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    require!(!treatment_pass.flagged_fraudulent, ErrorCode::AlreadyMarkedFraudulent);
+    require!(
+        treatment_pass.status != TreatmentPassStatus::TreatmentCompleted,
+        ErrorCode::TreatmentAlreadySettled
+    );
+    
+    // Passes that are already cancelled or expired keep their status
+    if !treatment_pass.status.is_terminal() {
+        treatment_pass.transition_to(TreatmentPassStatus::TreatmentCancelled)?;
+    }
+    treatment_pass.flagged_fraudulent = true;
+    
+    emit!(TreatmentMarkedFraudulent {
+        pass_id: treatment_pass.pass_id,
+        marked_by: authority,
+        reason,
+    });
+    
+    Ok(())
+}
+
+/// Permissionless crank that returns or slashes an endorser's stake once the pass is settled
+pub fn settle_endorsement_stake(ctx: Context<SettleEndorsementStake>) -> Result<()> {
+    let clock = Clock::get()?;
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass = &ctx.accounts.treatment_pass_account.treatment_pass;
+    let stake_bump = ctx.accounts.endorsement_stake.bump;
+    let stake = &mut ctx.accounts.endorsement_stake.stake;
+    
+    require!(stake.status == EndorsementStakeStatus::Locked, ErrorCode::StakeAlreadySettled);
+    
+    if treatment_pass.flagged_fraudulent {
+        // Slashed stake stays in the vault for the pass's sponsors to claim
+        stake.status = EndorsementStakeStatus::Slashed;
+        stake.funding_at_slash = treatment_pass.current_funding;
+    } else {
+        match treatment_pass.status {
+            TreatmentPassStatus::TreatmentCompleted => {}
+            TreatmentPassStatus::TreatmentCancelled | TreatmentPassStatus::FundingExpired => {
+                match stake.release_eligible_at {
+                    Some(release_eligible_at) => require!(
+                        clock.unix_timestamp >= release_eligible_at,
+                        ErrorCode::StakeChallengePeriodActive
+                    ),
+                    None => {
+                        // First settlement attempt opens the window for fraud reports
+                        let release_eligible_at = clock.unix_timestamp
+                            .checked_add(STAKE_CHALLENGE_PERIOD_SECONDS)
+                            .ok_or(ErrorCode::Overflow)?;
+                        stake.release_eligible_at = Some(release_eligible_at);
+    
+                        emit!(EndorsementStakeChallengeStarted {
+                            pass_id: treatment_pass.pass_id,
+                            endorser: stake.endorser,
+                            release_eligible_at,
+                        });
+    
+                        return Ok(());
+                    }
+                }
+            }
+            _ => return err!(ErrorCode::TreatmentNotSettled),
+        }
+    
+        let seeds = &[b"endorsement_stake".as_ref(), treatment_pass_key.as_ref(), &[stake_bump]];
+        let signer = &[&seeds[..]];
+    
+        // Return the stake to the endorser
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.endorser_token_account.to_account_info(),
+            authority: ctx.accounts.endorsement_stake.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, ctx.accounts.endorsement_stake.stake.amount)?;
+    
+        ctx.accounts.endorsement_stake.stake.status = EndorsementStakeStatus::Returned;
+    }
+    
+    let stake = &ctx.accounts.endorsement_stake.stake;
+    emit!(EndorsementStakeSettled {
+        pass_id: treatment_pass.pass_id,
+        endorser: stake.endorser,
+        amount: stake.amount,
+        status: stake.status.clone(),
+    });
+    
+    Ok(())
+}
+
+/// Sponsor claims their pro-rata share of a slashed endorsement stake
+pub fn claim_slashed_stake_share(ctx: Context<ClaimSlashedStakeShare>) -> Result<()> {
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let stake = &ctx.accounts.endorsement_stake.stake;
+    require!(stake.status == EndorsementStakeStatus::Slashed, ErrorCode::StakeNotSlashed);
+    
+    let contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+    require!(!contribution.slashed_stake_claimed, ErrorCode::SlashedShareAlreadyClaimed);
+    
+    let share = (stake.amount as u128)
+        .checked_mul(contribution.amount_contributed as u128)
+        .and_then(|scaled| scaled.checked_div(stake.funding_at_slash as u128))
+        .ok_or(ErrorCode::Overflow)? as u64;
+    contribution.slashed_stake_claimed = true;
+    
+    if share > 0 {
+        let seeds = &[
+            b"endorsement_stake".as_ref(),
+            treatment_pass_key.as_ref(),
+            &[ctx.accounts.endorsement_stake.bump],
+        ];
+        let signer = &[&seeds[..]];
+    
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.sponsor_token_account.to_account_info(),
+            authority: ctx.accounts.endorsement_stake.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, share)?;
+    }
+    
+    emit!(SlashedStakeShareClaimed {
+        pass_id: ctx.accounts.treatment_pass_account.treatment_pass.pass_id,
+        sponsor: contribution.public_wallet(),
+        amount: share,
+    });
+    
+    Ok(())
+}
+
+/// Events for endorsement stakes
+#[event]
+pub struct EndorsementStakeLocked {
+    pub pass_id: u64,
+    pub endorser: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreatmentMarkedFraudulent {
+    pub pass_id: u64,
+    pub marked_by: Pubkey,
+    pub reason: String,
+}
+
+#[event]
+pub struct EndorsementStakeChallengeStarted {
+    pub pass_id: u64,
+    pub endorser: Pubkey,
+    pub release_eligible_at: i64,
+}
+
+#[event]
+pub struct EndorsementStakeSettled {
+    pub pass_id: u64,
+    pub endorser: Pubkey,
+    pub amount: u64,
+    pub status: EndorsementStakeStatus,
+}
+
+#[event]
+pub struct SlashedStakeShareClaimed {
+    pub pass_id: u64,
+    pub sponsor: Option<Pubkey>,
+    pub amount: u64,
+}
+
+/// Custom error codes for endorsement stakes
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Token account does not match the endorsement stake")]
+    InvalidStakeAccount,
+    #[msg("Only governance or the elder council may mark a pass fraudulent")]
+    UnauthorizedFraudReport,
+    #[msg("Fraud reason exceeds maximum length")]
+    FraudReasonTooLong,
+    #[msg("Treatment pass is already marked fraudulent")]
+    AlreadyMarkedFraudulent,
+    #[msg("Completed treatment passes cannot be marked fraudulent")]
+    TreatmentAlreadySettled,
+    #[msg("Endorsement stake has already been returned or slashed")]
+    StakeAlreadySettled,
+    #[msg("Treatment pass has not been completed, cancelled or expired")]
+    TreatmentNotSettled,
+    #[msg("Stake cannot be released until the challenge period ends")]
+    StakeChallengePeriodActive,
+    #[msg("Endorsement stake has not been slashed")]
+    StakeNotSlashed,
+    #[msg("Slashed stake share already claimed")]
+    SlashedShareAlreadyClaimed,
+}
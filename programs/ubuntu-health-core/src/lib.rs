@@ -4,10 +4,27 @@ use anchor_lang::prelude::*;
 pub mod treatment_pass_nft;
 pub mod milestone_escrow;
 pub mod lives_token;
+pub mod verifier_registry;
+pub mod community_pool;
+pub mod overdue_milestones;
+pub mod milestone_amendments;
+pub mod member_registry;
+pub mod endorsement_stake;
+pub mod elder_council;
+pub mod patient_representatives;
+pub mod diaspora_remittance;
 
 use treatment_pass_nft::*;
 use milestone_escrow::*;
 use lives_token::*;
+use verifier_registry::*;
+use community_pool::*;
+use overdue_milestones::*;
+use milestone_amendments::*;
+use endorsement_stake::*;
+use elder_council::*;
+use patient_representatives::*;
+use diaspora_remittance::*;
 
 declare_id!("UbuntuHea1thPassNFT111111111111111111111111");
 
@@ -16,6 +33,7 @@ pub mod ubuntu_health_core {
     use super::*;
 
     /// Creates a new treatment pass NFT for a patient
+    #[allow(clippy::too_many_arguments)]
     pub fn create_treatment_pass(
         ctx: Context<CreateTreatmentPass>,
         pass_id: u64,
@@ -23,6 +41,10 @@ pub mod ubuntu_health_core {
         treatment_description: String,
         treatment_category: String,
         funding_target: u64,
+        funding_deadline: i64,
+        overfunding_policy: OverfundingPolicy,
+        overdue_grace_period_seconds: i64,
+        amendment_approval_basis_points: u16,
         milestones: Vec<TreatmentMilestone>,
         traditional_healing_component: Option<TraditionalHealing>,
     ) -> Result<()> {
@@ -33,6 +55,10 @@ pub mod ubuntu_health_core {
             treatment_description,
             treatment_category,
             funding_target,
+            funding_deadline,
+            overfunding_policy,
+            overdue_grace_period_seconds,
+            amendment_approval_basis_points,
             milestones,
             traditional_healing_component,
         )
@@ -43,16 +69,53 @@ pub mod ubuntu_health_core {
         ctx: Context<SponsorTreatment>,
         amount: u64,
         sponsor_message: Option<String>,
-        sponsored_milestones: Vec<u8>,
+        milestone_allocations: Vec<MilestoneAllocation>,
+        identity_salt: Option<[u8; 32]>,
     ) -> Result<()> {
         treatment_pass_nft::sponsor_treatment(
             ctx,
             amount,
             sponsor_message,
-            sponsored_milestones,
+            milestone_allocations,
+            identity_salt,
         )
     }
 
+    /// Governance or the elder council marks a treatment pass fraudulent
+    pub fn mark_treatment_fraudulent(
+        ctx: Context<MarkTreatmentFraudulent>,
+        reason: String,
+    ) -> Result<()> {
+        endorsement_stake::mark_treatment_fraudulent(ctx, reason)
+    }
+
+    /// Returns or slashes an endorser's stake once the treatment pass is settled
+    pub fn settle_endorsement_stake(ctx: Context<SettleEndorsementStake>) -> Result<()> {
+        endorsement_stake::settle_endorsement_stake(ctx)
+    }
+
+    /// Sponsor claims their share of a slashed endorsement stake
+    pub fn claim_slashed_stake_share(
+        ctx: Context<ClaimSlashedStakeShare>,
+        _identity_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        endorsement_stake::claim_slashed_stake_share(ctx)
+    }
+
+    /// Hides an abusive sponsor message, keeping its hash for auditability
+    pub fn hide_sponsor_message(ctx: Context<HideSponsorMessage>) -> Result<()> {
+        treatment_pass_nft::hide_sponsor_message(ctx)
+    }
+
+    /// Proves or reveals the wallet behind an anonymous sponsorship
+    pub fn reveal_sponsor_identity(
+        ctx: Context<RevealSponsorIdentity>,
+        salt: [u8; 32],
+        make_public: bool,
+    ) -> Result<()> {
+        treatment_pass_nft::reveal_sponsor_identity(ctx, salt, make_public)
+    }
+
     /// Verifies milestone completion and releases escrowed funds
     pub fn verify_milestone(
         ctx: Context<VerifyMilestone>,
@@ -68,7 +131,176 @@ pub mod ubuntu_health_core {
         )
     }
 
-    /// Updates treatment pass with elder council review
+    /// Patient or care team reports a milestone complete for verification
+    pub fn submit_milestone_completion(
+        ctx: Context<SubmitMilestoneCompletion>,
+        milestone_id: u8,
+        evidence_hash: String,
+    ) -> Result<()> {
+        treatment_pass_nft::submit_milestone_completion(ctx, milestone_id, evidence_hash)
+    }
+
+    /// Verifier rejects a reported milestone back to in progress
+    pub fn reject_milestone_completion(
+        ctx: Context<RejectMilestoneCompletion>,
+        milestone_id: u8,
+        reason: String,
+    ) -> Result<()> {
+        treatment_pass_nft::reject_milestone_completion(ctx, milestone_id, reason)
+    }
+
+    /// Flags a milestone whose expected completion has passed
+    pub fn flag_overdue_milestone(
+        ctx: Context<FlagOverdueMilestone>,
+        milestone_id: u8,
+    ) -> Result<()> {
+        overdue_milestones::flag_overdue_milestone(ctx, milestone_id)
+    }
+
+    /// Votes to extend, reallocate or fail an overdue milestone
+    pub fn vote_overdue_resolution(
+        ctx: Context<VoteOverdueResolution>,
+        milestone_id: u8,
+        resolution: OverdueResolution,
+        _identity_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        overdue_milestones::vote_overdue_resolution(ctx, milestone_id, resolution)
+    }
+
+    /// Refunds a sponsor's earmark for a failed milestone, plus their unspent pool share once treatment completes
+    pub fn claim_failed_milestone_refund(
+        ctx: Context<ClaimFailedMilestoneRefund>,
+        milestone_id: u8,
+        _identity_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        overdue_milestones::claim_failed_milestone_refund(ctx, milestone_id)
+    }
+
+    /// Patient proposes a replacement for the open milestones
+    pub fn propose_milestone_amendment(
+        ctx: Context<ProposeMilestoneAmendment>,
+        proposed_milestones: Vec<TreatmentMilestone>,
+        reason: String,
+    ) -> Result<()> {
+        milestone_amendments::propose_milestone_amendment(ctx, proposed_milestones, reason)
+    }
+
+    /// Sponsor approves or objects to a pending milestone amendment
+    pub fn vote_on_amendment(
+        ctx: Context<VoteOnAmendment>,
+        amendment_id: u16,
+        approve: bool,
+        _identity_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        milestone_amendments::vote_on_amendment(ctx, amendment_id, approve)
+    }
+
+    /// Settles a milestone amendment once its timelock has passed
+    pub fn finalize_milestone_amendment(
+        ctx: Context<FinalizeMilestoneAmendment>,
+        amendment_id: u16,
+    ) -> Result<()> {
+        milestone_amendments::finalize_milestone_amendment(ctx, amendment_id)
+    }
+
+    /// Patient withdraws a pending milestone amendment
+    pub fn withdraw_milestone_amendment(
+        ctx: Context<WithdrawMilestoneAmendment>,
+        amendment_id: u16,
+    ) -> Result<()> {
+        milestone_amendments::withdraw_milestone_amendment(ctx, amendment_id)
+    }
+
+    /// Expires an underfunded treatment pass after its funding deadline
+    pub fn expire_treatment_pass(
+        ctx: Context<ExpireTreatmentPass>,
+    ) -> Result<()> {
+        treatment_pass_nft::expire_treatment_pass(ctx)
+    }
+
+    /// Refunds a sponsor's unreleased earmarks and pool share from a cancelled or expired pass;
+    /// anonymous sponsors locate their record with the salt behind their identity commitment
+    pub fn claim_sponsor_refund(
+        ctx: Context<ClaimSponsorRefund>,
+        _identity_salt: Option<[u8; 32]>,
+    ) -> Result<()> {
+        treatment_pass_nft::claim_sponsor_refund(ctx)
+    }
+
+    /// Patient assigns the accredited provider delivering the treatment
+    pub fn assign_treating_provider(
+        ctx: Context<AssignTreatingProvider>,
+    ) -> Result<()> {
+        treatment_pass_nft::assign_treating_provider(ctx)
+    }
+
+    /// Assigned provider starts treatment on a fully funded pass
+    pub fn start_treatment(
+        ctx: Context<UpdateTreatmentStatus>,
+    ) -> Result<()> {
+        treatment_pass_nft::start_treatment(ctx)
+    }
+
+    /// Patient or assigned provider pauses treatment
+    pub fn pause_treatment(
+        ctx: Context<UpdateTreatmentStatus>,
+        reason: String,
+    ) -> Result<()> {
+        treatment_pass_nft::pause_treatment(ctx, reason)
+    }
+
+    /// Patient or assigned provider resumes paused treatment
+    pub fn resume_treatment(
+        ctx: Context<UpdateTreatmentStatus>,
+    ) -> Result<()> {
+        treatment_pass_nft::resume_treatment(ctx)
+    }
+
+    /// Patient or elder council cancels treatment, opening sponsor refunds
+    pub fn cancel_treatment(
+        ctx: Context<UpdateTreatmentStatus>,
+        reason: String,
+    ) -> Result<()> {
+        treatment_pass_nft::cancel_treatment(ctx, reason)
+    }
+
+    /// Patient refers a treatment pass to the elder council for review
+    pub fn request_elder_council_review(
+        ctx: Context<UpdateTreatmentStatus>,
+        reason: String,
+    ) -> Result<()> {
+        treatment_pass_nft::request_elder_council_review(ctx, reason)
+    }
+
+    // Patient Representative Instructions
+    /// Patient appoints a family member, caseworker or caregiver to act on their behalf within the granted scopes
+    pub fn appoint_patient_representative(
+        ctx: Context<AppointPatientRepresentative>,
+        representative: Pubkey,
+        role: RepresentativeRole,
+        scopes: Vec<RepresentativeScope>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        patient_representatives::appoint_patient_representative(ctx, representative, role, scopes, expires_at)
+    }
+
+    /// Patient changes a representative's scopes or expiry
+    pub fn update_patient_representative(
+        ctx: Context<UpdatePatientRepresentative>,
+        scopes: Vec<RepresentativeScope>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        patient_representatives::update_patient_representative(ctx, scopes, expires_at)
+    }
+
+    /// Patient revokes a representative's appointment
+    pub fn revoke_patient_representative(
+        ctx: Context<RevokePatientRepresentative>,
+    ) -> Result<()> {
+        patient_representatives::revoke_patient_representative(ctx)
+    }
+
+    /// Records an elder council member's review of a treatment pass
     pub fn elder_council_review(
         ctx: Context<ElderCouncilReviewContext>,
         pass_id: u64,
@@ -77,64 +309,48 @@ pub mod ubuntu_health_core {
         traditional_wisdom: Option<String>,
         community_support_recommendations: Vec<String>,
     ) -> Result<()> {
-        let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
-        let treatment_pass = &mut treatment_pass_account.treatment_pass;
-        let clock = Clock::get()?;
-
-        // Create elder review
-        let elder_review = ElderReview {
-            reviewing_elder: ctx.accounts.elder.key(),
-            review_timestamp: clock.unix_timestamp,
-            decision: decision.clone(),
+        elder_council::elder_council_review(
+            ctx,
+            pass_id,
+            decision,
             elder_guidance,
-            traditional_wisdom: traditional_wisdom.clone(),
+            traditional_wisdom,
             community_support_recommendations,
-        };
+        )
+    }
 
-        // Update treatment pass based on elder decision
-        match decision {
-            ElderDecision::Approved => {
-                treatment_pass.elder_council_approval = true;
-                treatment_pass.approving_elder = Some(ctx.accounts.elder.key());
-                if treatment_pass.status == TreatmentPassStatus::ElderCouncilReview {
-                    treatment_pass.status = TreatmentPassStatus::FundingRequired;
-                }
-            }
-            ElderDecision::ApprovedWithGuidance => {
-                treatment_pass.elder_council_approval = true;
-                treatment_pass.approving_elder = Some(ctx.accounts.elder.key());
-                if treatment_pass.status == TreatmentPassStatus::ElderCouncilReview {
-                    treatment_pass.status = TreatmentPassStatus::FundingRequired;
-                }
-            }
-            ElderDecision::RequiresMoreInformation => {
-                treatment_pass.status = TreatmentPassStatus::CommunityReview;
-            }
-            ElderDecision::Disapproved => {
-                treatment_pass.status = TreatmentPassStatus::TreatmentCancelled;
-            }
-            ElderDecision::DeferToCommunity => {
-                treatment_pass.status = TreatmentPassStatus::CommunityReview;
-            }
-        }
-
-        // Add elder review to relevant milestones if applicable
-        for milestone in &mut treatment_pass.milestones {
-            if milestone.verification_required == VerificationType::ElderCouncilVerification ||
-               milestone.verification_required == VerificationType::IntegratedVerification {
-                milestone.elder_council_review = Some(elder_review.clone());
-            }
-        }
-
-        // Emit event
-        emit!(ElderCouncilReview {
-            pass_id,
-            reviewing_elder: ctx.accounts.elder.key(),
-            decision,
-            traditional_wisdom_shared: traditional_wisdom.is_some(),
-        });
+    /// Establishes the elder council under the platform authority's governance
+    pub fn initialize_elder_council(
+        ctx: Context<InitializeElderCouncil>,
+        approval_quorum: u8,
+        disapproval_supermajority_basis_points: u16,
+    ) -> Result<()> {
+        elder_council::initialize_elder_council(ctx, approval_quorum, disapproval_supermajority_basis_points)
+    }
 
-        Ok(())
+    /// Governance adds a member to the elder council
+    pub fn add_elder_council_member(
+        ctx: Context<ManageElderCouncil>,
+        elder: Pubkey,
+    ) -> Result<()> {
+        elder_council::add_elder_council_member(ctx, elder)
+    }
+
+    /// Governance removes a member from the elder council
+    pub fn remove_elder_council_member(
+        ctx: Context<ManageElderCouncil>,
+        elder: Pubkey,
+    ) -> Result<()> {
+        elder_council::remove_elder_council_member(ctx, elder)
+    }
+
+    /// Governance updates the elder council quorum and disapproval supermajority
+    pub fn set_elder_council_thresholds(
+        ctx: Context<ManageElderCouncil>,
+        approval_quorum: u8,
+        disapproval_supermajority_basis_points: u16,
+    ) -> Result<()> {
+        elder_council::set_elder_council_thresholds(ctx, approval_quorum, disapproval_supermajority_basis_points)
     }
 
     /// Updates diaspora connection information for treatment pass
@@ -146,16 +362,28 @@ pub mod ubuntu_health_core {
         network_contacts: Vec<Pubkey>,
         remittance_capability: bool,
     ) -> Result<()> {
+        require!(
+            acts_for_patient(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::ManageTreatment,
+            )?,
+            treatment_pass_nft::ErrorCode::UnauthorizedPatientAction
+        );
+
         let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
         let treatment_pass = &mut treatment_pass_account.treatment_pass;
 
-        treatment_pass.diaspora_connection = Some(DiasporaInfo {
+        let diaspora_connection = DiasporaInfo {
             origin_community,
             current_location,
             connection_strength,
             network_contacts,
             remittance_capability,
-        });
+        };
+        diaspora_connection.validate()?;
+        treatment_pass.diaspora_connection = Some(diaspora_connection);
 
         Ok(())
     }
@@ -165,19 +393,62 @@ pub mod ubuntu_health_core {
         ctx: Context<AddTraditionalHealingKnowledge>,
         sponsor_wallet: Pubkey,
         healing_knowledge: String,
+        _identity_salt: Option<[u8; 32]>,
     ) -> Result<()> {
-        let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
-        let treatment_pass = &mut treatment_pass_account.treatment_pass;
+        require_keys_eq!(
+            ctx.accounts.sponsor.key(),
+            sponsor_wallet,
+            treatment_pass_nft::ErrorCode::UnauthorizedSponsor
+        );
+        require!(!healing_knowledge.is_empty(), treatment_pass_nft::ErrorCode::MissingHealingKnowledge);
+        require!(
+            healing_knowledge.len() <= MAX_HEALING_KNOWLEDGE_LEN,
+            treatment_pass_nft::ErrorCode::HealingKnowledgeTooLong
+        );
 
-        // Find and update sponsor with traditional healing knowledge
-        if let Some(sponsor) = treatment_pass.sponsors.iter_mut()
-            .find(|s| s.wallet == sponsor_wallet) {
-            sponsor.traditional_healing_knowledge = Some(healing_knowledge);
-        }
+        // Update the sponsor's contribution record with traditional healing knowledge;
+        // anonymous records leave the wallet unset, so the salted PDA seed is what ties them to the sponsor
+        let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+        let sponsor_contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+        require_keys_eq!(
+            sponsor_contribution.treatment_pass,
+            treatment_pass_key,
+            treatment_pass_nft::ErrorCode::ContributionPassMismatch
+        );
+        require!(
+            sponsor_contribution.anonymous || sponsor_contribution.wallet == sponsor_wallet,
+            treatment_pass_nft::ErrorCode::UnauthorizedSponsor
+        );
+        sponsor_contribution.traditional_healing_knowledge = Some(healing_knowledge);
 
         Ok(())
     }
 
+    // Diaspora Remittance Instructions
+    /// Diaspora contact pledges a fixed remittance per period to a treatment pass
+    pub fn create_remittance_pledge(
+        ctx: Context<CreateRemittancePledge>,
+        amount_per_period: u64,
+        period_seconds: i64,
+        periods: u16,
+    ) -> Result<()> {
+        diaspora_remittance::create_remittance_pledge(ctx, amount_per_period, period_seconds, periods)
+    }
+
+    /// Permissionless crank remitting a due pledge into the pass escrow
+    pub fn execute_remittance_pledge(
+        ctx: Context<ExecuteRemittancePledge>,
+    ) -> Result<()> {
+        diaspora_remittance::execute_remittance_pledge(ctx)
+    }
+
+    /// Contributor closes their remittance pledge and revokes its token delegation
+    pub fn close_remittance_pledge(
+        ctx: Context<CloseRemittancePledge>,
+    ) -> Result<()> {
+        diaspora_remittance::close_remittance_pledge(ctx)
+    }
+
     // Milestone Escrow Instructions
     /// Creates a milestone-based escrow for treatment funding
     pub fn create_milestone_escrow(
@@ -185,12 +456,16 @@ pub mod ubuntu_health_core {
         treatment_pass_id: u64,
         milestone_releases: Vec<MilestoneRelease>,
         ubuntu_health_verification_required: bool,
+        emergency_releasers: Vec<Pubkey>,
+        emergency_delay_seconds: i64,
     ) -> Result<()> {
         milestone_escrow::create_milestone_escrow(
             ctx,
             treatment_pass_id,
             milestone_releases,
             ubuntu_health_verification_required,
+            emergency_releasers,
+            emergency_delay_seconds,
         )
     }
 
@@ -203,7 +478,31 @@ pub mod ubuntu_health_core {
         milestone_escrow::fund_escrow(ctx, treatment_pass_id, amount)
     }
 
-    /// Releases milestone funds upon verification
+    /// Records a verifier's Ed25519-signed attestation towards a milestone's release quorum
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_escrow_verification(
+        ctx: Context<SubmitEscrowVerification>,
+        treatment_pass_id: u64,
+        milestone_id: u8,
+        verifier: Pubkey,
+        verification_type: EscrowVerificationType,
+        evidence_hash: Option<String>,
+        attested_at: i64,
+        verification_signature: [u8; 64],
+    ) -> Result<()> {
+        milestone_escrow::submit_escrow_verification(
+            ctx,
+            treatment_pass_id,
+            milestone_id,
+            verifier,
+            verification_type,
+            evidence_hash,
+            attested_at,
+            verification_signature,
+        )
+    }
+
+    /// Releases milestone funds once the verification quorum is met
     pub fn release_milestone_funds(
         ctx: Context<ReleaseMilestoneFunds>,
         treatment_pass_id: u64,
@@ -212,6 +511,39 @@ pub mod ubuntu_health_core {
         milestone_escrow::release_milestone_funds(ctx, treatment_pass_id, milestone_id)
     }
 
+    /// Starts the emergency release timelock; callable only by listed releasers who are active verifiers or elders
+    pub fn initiate_emergency_release(
+        ctx: Context<InitiateEmergencyRelease>,
+        treatment_pass_id: u64,
+        reason: String,
+    ) -> Result<()> {
+        milestone_escrow::initiate_emergency_release(ctx, treatment_pass_id, reason)
+    }
+
+    /// Cancels a pending emergency release (patient, or majority of releasers)
+    pub fn cancel_emergency_release(
+        ctx: Context<CancelEmergencyRelease>,
+        treatment_pass_id: u64,
+    ) -> Result<()> {
+        milestone_escrow::cancel_emergency_release(ctx, treatment_pass_id)
+    }
+
+    /// Releases remaining escrow funds to the patient once the emergency delay has passed
+    pub fn execute_emergency_release(
+        ctx: Context<ExecuteEmergencyRelease>,
+        treatment_pass_id: u64,
+    ) -> Result<()> {
+        milestone_escrow::execute_emergency_release(ctx, treatment_pass_id)
+    }
+
+    /// Refunds a depositor's share of the unreleased escrow after the pass is cancelled or expired
+    pub fn claim_escrow_refund(
+        ctx: Context<ClaimEscrowRefund>,
+        treatment_pass_id: u64,
+    ) -> Result<()> {
+        milestone_escrow::claim_escrow_refund(ctx, treatment_pass_id)
+    }
+
     // $LIVES Token Instructions
     /// Initializes the $LIVES token economics system
     pub fn initialize_lives_token(
@@ -245,26 +577,58 @@ pub mod ubuntu_health_core {
     ) -> Result<()> {
         lives_token::claim_pending_rewards(ctx)
     }
-}
 
-// Additional context structs for new instructions
-#[derive(Accounts)]
-#[instruction(pass_id: u64)]
-pub struct ElderCouncilReviewContext<'info> {
-    #[account(
-        mut,
-        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), pass_id.to_le_bytes().as_ref()],
-        bump = treatment_pass_account.bump
-    )]
-    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
-    
-    /// Elder council member performing the review
-    pub elder: Signer<'info>,
-    
-    /// System program
-    pub system_program: Program<'info, System>,
+    // Verifier Registry Instructions
+    /// Initializes the accredited verifier registry under a platform authority
+    pub fn initialize_verifier_registry(
+        ctx: Context<InitializeVerifierRegistry>,
+    ) -> Result<()> {
+        verifier_registry::initialize_verifier_registry(ctx)
+    }
+
+    /// Registers an accredited provider, laboratory or community witness
+    pub fn register_verifier(
+        ctx: Context<RegisterVerifier>,
+        verifier: Pubkey,
+        verifier_kind: VerifierKind,
+        credential: String,
+    ) -> Result<()> {
+        verifier_registry::register_verifier(ctx, verifier, verifier_kind, credential)
+    }
+
+    /// Activates or deactivates a registered verifier
+    pub fn set_verifier_status(
+        ctx: Context<SetVerifierStatus>,
+        is_active: bool,
+    ) -> Result<()> {
+        verifier_registry::set_verifier_status(ctx, is_active)
+    }
+
+    // Community Overflow Pool Instructions
+    /// Initializes the pool that collects surplus sponsorship from overfunded passes
+    pub fn initialize_community_overflow_pool(
+        ctx: Context<InitializeCommunityOverflowPool>,
+    ) -> Result<()> {
+        community_pool::initialize_community_overflow_pool(ctx)
+    }
+
+    /// Grants pooled surplus to a treatment pass still seeking funding
+    pub fn grant_overflow_funds(
+        ctx: Context<GrantOverflowFunds>,
+        amount: u64,
+    ) -> Result<()> {
+        community_pool::grant_overflow_funds(ctx, amount)
+    }
+
+    /// Returns the pool's unreleased grant from a cancelled, expired or completed pass
+    pub fn reclaim_overflow_grant(
+        ctx: Context<ReclaimOverflowGrant>,
+    ) -> Result<()> {
+        community_pool::reclaim_overflow_grant(ctx)
+    }
 }
 
+// Additional context structs for new instructions
 #[derive(Accounts)]
 pub struct UpdateDiasporaConnection<'info> {
     #[account(
@@ -276,17 +640,35 @@ pub struct UpdateDiasporaConnection<'info> {
     
     /// Patient or authorized representative
     pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
 }
 
 #[derive(Accounts)]
+#[instruction(sponsor_wallet: Pubkey, healing_knowledge: String, identity_salt: Option<[u8; 32]>)]
 pub struct AddTraditionalHealingKnowledge<'info> {
     #[account(
-        mut,
         seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
         bump = treatment_pass_account.bump
     )]
     pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
     
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor_wallet, identity_salt).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
     /// Sponsor adding traditional healing knowledge
     pub sponsor: Signer<'info>,
 }
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer, MintTo};
+use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
 
 /// $LIVES Token Economics for Ubuntu Health Platform
 /// Implements token rewards for sponsorship, recovery logging, and community participation
@@ -313,7 +313,7 @@ impl LivesTokenConfigAccount {
             RewardType::Sponsorship => {
                 self.config.reward_rates.sponsorship_base_reward
                     .checked_mul(base_amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?
+                    .ok_or(ErrorCode::Overflow)?
             },
             RewardType::MilestoneCompletion => self.config.reward_rates.milestone_completion_bonus,
             RewardType::RecoveryLogging => self.config.reward_rates.recovery_logging_reward,
@@ -335,9 +335,9 @@ impl LivesTokenConfigAccount {
 
             let bonus_reward = base_reward
                 .checked_mul(multiplier as u64)
-                .ok_or(ProgramError::ArithmeticOverflow)?
+                .ok_or(ErrorCode::Overflow)?
                 .checked_div(100)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
+                .ok_or(ErrorCode::Overflow)?;
 
             return Ok(bonus_reward);
         }
@@ -375,7 +375,7 @@ impl UserTokenAccountData {
     pub fn add_pending_reward(&mut self, amount: u64) -> Result<()> {
         self.account.pending_rewards = self.account.pending_rewards
             .checked_add(amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(ErrorCode::Overflow)?;
         Ok(())
     }
 
@@ -384,10 +384,10 @@ impl UserTokenAccountData {
         self.account.pending_rewards = 0;
         self.account.current_balance = self.account.current_balance
             .checked_add(reward_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(ErrorCode::Overflow)?;
         self.account.total_earned = self.account.total_earned
             .checked_add(reward_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(ErrorCode::Overflow)?;
         self.account.last_claim_timestamp = Clock::get()?.unix_timestamp;
         
         Ok(reward_amount)
@@ -399,7 +399,7 @@ impl UserTokenAccountData {
                 self.account.activity_tracking.treatments_sponsored += 1;
                 self.account.activity_tracking.total_sponsored_usd = self.account.activity_tracking.total_sponsored_usd
                     .checked_add(amount)
-                    .ok_or(ProgramError::ArithmeticOverflow)?;
+                    .ok_or(ErrorCode::Overflow)?;
             },
             RewardType::MilestoneCompletion => {
                 self.account.activity_tracking.milestones_completed += 1;
@@ -431,7 +431,7 @@ pub fn initialize_lives_token(
     max_supply: u64,
 ) -> Result<()> {
     let config_account = &mut ctx.accounts.config_account;
-    config_account.bump = *ctx.bumps.get("config_account").unwrap();
+    config_account.bump = ctx.bumps.config_account;
     
     config_account.initialize(
         ctx.accounts.token_mint.key(),
@@ -455,7 +455,7 @@ pub fn initialize_user_token_account(
     ubuntu_health_member: bool,
 ) -> Result<()> {
     let user_account = &mut ctx.accounts.user_token_account;
-    user_account.bump = *ctx.bumps.get("user_token_account").unwrap();
+    user_account.bump = ctx.bumps.user_token_account;
     
     user_account.initialize(user, ubuntu_health_member)?;
 
@@ -506,7 +506,7 @@ pub fn claim_pending_rewards(ctx: Context<ClaimPendingRewards>) -> Result<()> {
     if reward_amount > 0 {
         // Create PDA seeds for treasury authority
         let seeds = &[
-            b"lives_token_config",
+            b"lives_token_config".as_ref(),
             &[ctx.accounts.config_account.bump],
         ];
         let signer = &[&seeds[..]];
@@ -559,3 +559,10 @@ pub struct RewardsClaimed {
     pub user: Pubkey,
     pub reward_amount: u64,
 }
+
+/// Custom error codes for $LIVES token economics
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+/// Ubuntu community member registry
+/// Member profiles live in the separate member registry program (contracts/programs/ubuntu-health-core);
+/// they are read here without a crate dependency by mirroring the account layout.
+/// Must match the registry's `declare_id!` and its `ubuntu_health_core` entries in contracts/Anchor.toml
+pub const MEMBER_REGISTRY_PROGRAM_ID: Pubkey = pubkey!("UbuntuMemberRegistry11111111111111111111111");
+
+/// Mirror of the registry's `UbuntuMemberProfile` account, in field order
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UbuntuMemberProfile {
+    pub wallet: Pubkey,
+    pub member_type: UbuntuMemberType,
+    pub profile_ipfs: String,
+    pub cultural_background: String,
+    pub ubuntu_community_id: String,
+    pub reputation_score: u64,
+    pub ubuntu_contributions: u64,
+    pub healing_journeys_count: u64,
+    pub is_active: bool,
+    pub elder_verified: bool,
+    pub registered_at: i64,
+}
+
+/// Mirror of the registry's `UbuntuMemberType`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum UbuntuMemberType {
+    Patient,
+    Sponsor,
+    TraditionalHealer,
+    ModernMedicalProfessional,
+    CommunityElder,
+    Researcher,
+    UbuntuAdvocate,
+}
+
+impl UbuntuMemberProfile {
+    /// Loads a member's profile, checking it is the registry-owned PDA for that wallet
+    pub fn load(profile_info: &AccountInfo, wallet: &Pubkey) -> Result<Self> {
+        require_keys_eq!(*profile_info.owner, MEMBER_REGISTRY_PROGRAM_ID, ErrorCode::InvalidMemberProfile);
+    
+        let (expected_profile, _) = Pubkey::find_program_address(
+            &[b"member_profile", wallet.as_ref()],
+            &MEMBER_REGISTRY_PROGRAM_ID,
+        );
+        require_keys_eq!(profile_info.key(), expected_profile, ErrorCode::InvalidMemberProfile);
+    
+        let data = profile_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == Self::discriminator(),
+            ErrorCode::InvalidMemberProfile
+        );
+        let profile = Self::deserialize(&mut &data[8..])
+            .map_err(|_| error!(ErrorCode::InvalidMemberProfile))?;
+        require_keys_eq!(profile.wallet, *wallet, ErrorCode::InvalidMemberProfile);
+    
+        Ok(profile)
+    }
+    
+    /// Anchor account discriminator used by the registry program
+    fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash(b"account:UbuntuMemberProfile").to_bytes()[..8]);
+        discriminator
+    }
+}
+
+/// Endorsement reputation kept alongside a member's registry profile,
+/// since the profile itself can only be written by the registry program
+#[account]
+pub struct EndorserReputationAccount {
+    pub endorser: Pubkey,
+    /// Treatment passes this member has endorsed
+    pub endorsement_count: u32,
+    pub last_endorsed_at: i64,
+    pub bump: u8,
+}
+
+impl EndorserReputationAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // endorser
+        4 + // endorsement_count
+        8 + // last_endorsed_at
+        1; // bump
+}
+
+/// Custom error codes for member registry checks
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Account is not a member profile from the Ubuntu member registry")]
+    InvalidMemberProfile,
+}
@@ -0,0 +1,655 @@
+use anchor_lang::prelude::*;
+
+use crate::treatment_pass_nft::{
+    validate_milestone_schedule, MilestoneStatus, SponsorContribution, SponsorContributionAccount,
+    TreatmentMilestone, TreatmentPass, TreatmentPassAccount, FULLY_FUNDED_BASIS_POINTS, MAX_MILESTONES,
+};
+use crate::patient_representatives::{acts_for_patient, PatientRepresentativeAccount, RepresentativeScope};
+
+/// Milestone plan amendments
+/// The patient proposes a replacement for the open milestones; it applies once sponsors holding the
+/// pass's approval share consent, or when the timelock passes without enough objections
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneAmendment {
+    /// Treatment pass being amended
+    pub treatment_pass: Pubkey,
+    /// Sequential amendment number on the pass
+    pub amendment_id: u16,
+    /// Replacement for every milestone that is not yet closed
+    pub proposed_milestones: Vec<TreatmentMilestone>,
+    /// Patient's explanation of the change (e.g. hospital transfer)
+    pub reason: String,
+    /// Proposal timestamp
+    pub proposed_at: i64,
+    /// After this time, sponsors who have not objected count as consenting
+    pub timelock_ends_at: i64,
+    /// Contribution weight of sponsors approving the amendment
+    pub approval_weight: u64,
+    /// Contribution weight of sponsors objecting to the amendment
+    pub objection_weight: u64,
+    /// Current amendment status
+    pub status: AmendmentStatus,
+    /// When the amendment was applied, rejected, found outdated or withdrawn
+    pub resolved_at: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AmendmentStatus {
+    /// Awaiting sponsor consent
+    Pending,
+    /// Milestone plan replaced
+    Applied,
+    /// Too many sponsors objected
+    Rejected,
+    /// Milestones closed or changed since the proposal, so it no longer fits the plan
+    Outdated,
+    /// Withdrawn by the patient or their representative before it was settled
+    Withdrawn,
+}
+
+/// Resolved amendment, kept on the treatment pass as its amendment history
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AmendmentRecord {
+    pub amendment_id: u16,
+    pub status: AmendmentStatus,
+    pub approval_weight: u64,
+    pub objection_weight: u64,
+    pub resolved_at: i64,
+}
+
+impl AmendmentRecord {
+    pub const SIZE: usize = 2 + // amendment_id
+        1 + // status
+        8 + // approval_weight
+        8 + // objection_weight
+        8; // resolved_at
+}
+
+/// Maximum number of amendments a treatment pass may go through
+pub const MAX_AMENDMENT_HISTORY: usize = 8;
+
+/// Maximum length of the patient's reason for an amendment
+pub const MAX_AMENDMENT_REASON_LEN: usize = 256;
+
+/// Time sponsors have to object before silence counts as consent (7 days)
+pub const AMENDMENT_TIMELOCK_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+#[account]
+pub struct MilestoneAmendmentAccount {
+    pub amendment: MilestoneAmendment,
+    pub bump: u8,
+}
+
+impl MilestoneAmendmentAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        2 + // amendment_id
+        4 + MAX_MILESTONES * TreatmentMilestone::MAX_SIZE + // proposed_milestones
+        4 + MAX_AMENDMENT_REASON_LEN + // reason
+        8 + // proposed_at
+        8 + // timelock_ends_at
+        8 + // approval_weight
+        8 + // objection_weight
+        1 + // status
+        1 + 8 + // resolved_at
+        1; // bump
+}
+
+/// One sponsor's vote on a milestone amendment
+#[account]
+pub struct AmendmentVoteAccount {
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl AmendmentVoteAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // voter
+        1 + // approve
+        8 + // weight
+        8 + // voted_at
+        1; // bump
+}
+
+/// Instructions for milestone amendments
+#[derive(Accounts)]
+pub struct ProposeMilestoneAmendment<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = MilestoneAmendmentAccount::LEN,
+        seeds = [b"milestone_amendment", treatment_pass_account.key().as_ref(), treatment_pass_account.treatment_pass.amendment_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub amendment_account: Account<'info, MilestoneAmendmentAccount>,
+    
+    /// Patient or their representative proposing the amendment
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    /// Proposer's appointment as the patient's representative; omitted when the patient proposes
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), proposer.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
+    /// Pass's milestone escrow, which must not exist since its release schedule cannot follow an amendment
+    /// CHECK: Address is derived from the treatment pass; only checked to be empty
+    #[account(
+        seeds = [b"milestone_escrow", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub milestone_escrow: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amendment_id: u16, approve: bool, identity_salt: Option<[u8; 32]>)]
+pub struct VoteOnAmendment<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"milestone_amendment", treatment_pass_account.key().as_ref(), amendment_id.to_le_bytes().as_ref()],
+        bump = amendment_account.bump
+    )]
+    pub amendment_account: Account<'info, MilestoneAmendmentAccount>,
+    
+    /// Vote record; its existence prevents a second vote from the same sponsor
+    #[account(
+        init,
+        payer = sponsor,
+        space = AmendmentVoteAccount::LEN,
+        seeds = [b"amendment_vote", amendment_account.key().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, AmendmentVoteAccount>,
+    
+    #[account(
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), identity_salt).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    
+    /// Pass's milestone escrow; an escrow created after the proposal leaves the amendment outdated
+    /// CHECK: Address is derived from the treatment pass; only checked to be empty
+    #[account(
+        seeds = [b"milestone_escrow", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub milestone_escrow: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amendment_id: u16)]
+pub struct FinalizeMilestoneAmendment<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"milestone_amendment", treatment_pass_account.key().as_ref(), amendment_id.to_le_bytes().as_ref()],
+        bump = amendment_account.bump
+    )]
+    pub amendment_account: Account<'info, MilestoneAmendmentAccount>,
+    
+    /// Pass's milestone escrow; an escrow created after the proposal leaves the amendment outdated
+    /// CHECK: Address is derived from the treatment pass; only checked to be empty
+    #[account(
+        seeds = [b"milestone_escrow", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub milestone_escrow: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(amendment_id: u16)]
+pub struct WithdrawMilestoneAmendment<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"milestone_amendment", treatment_pass_account.key().as_ref(), amendment_id.to_le_bytes().as_ref()],
+        bump = amendment_account.bump
+    )]
+    pub amendment_account: Account<'info, MilestoneAmendmentAccount>,
+    
+    /// Patient or their representative withdrawing the amendment
+    pub proposer: Signer<'info>,
+    
+    /// Proposer's appointment as the patient's representative; omitted when the patient acts
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), proposer.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+}
+
+/// Milestone amendment instruction implementations
+pub fn propose_milestone_amendment(
+    ctx: Context<ProposeMilestoneAmendment>,
+    proposed_milestones: Vec<TreatmentMilestone>,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        acts_for_patient(
+            &ctx.accounts.proposer.key(),
+            &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+            ctx.accounts.patient_representative.as_deref(),
+            RepresentativeScope::ManageMilestones,
+        )?,
+        ErrorCode::UnauthorizedAmendmentProposer
+    );
+    
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    require!(!treatment_pass.status.is_terminal(), ErrorCode::TreatmentPassClosed);
+    require!(treatment_pass.pending_amendment.is_none(), ErrorCode::AmendmentAlreadyPending);
+    require!(
+        treatment_pass.amendment_history.len() < MAX_AMENDMENT_HISTORY,
+        ErrorCode::TooManyAmendments
+    );
+    require!(reason.len() <= MAX_AMENDMENT_REASON_LEN, ErrorCode::AmendmentReasonTooLong);
+    require!(ctx.accounts.milestone_escrow.data_is_empty(), ErrorCode::MilestoneEscrowBound);
+    validate_amendment(treatment_pass, &proposed_milestones, clock.unix_timestamp)?;
+    
+    let amendment_id = treatment_pass.amendment_count;
+    treatment_pass.amendment_count = treatment_pass.amendment_count.checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    treatment_pass.pending_amendment = Some(amendment_id);
+    
+    let timelock_ends_at = clock.unix_timestamp.checked_add(AMENDMENT_TIMELOCK_SECONDS)
+        .ok_or(ErrorCode::Overflow)?;
+    let milestone_count = proposed_milestones.len() as u8;
+    
+    let amendment_account = &mut ctx.accounts.amendment_account;
+    amendment_account.bump = ctx.bumps.amendment_account;
+    amendment_account.amendment = MilestoneAmendment {
+        treatment_pass: treatment_pass_key,
+        amendment_id,
+        proposed_milestones,
+        reason,
+        proposed_at: clock.unix_timestamp,
+        timelock_ends_at,
+        approval_weight: 0,
+        objection_weight: 0,
+        status: AmendmentStatus::Pending,
+        resolved_at: None,
+    };
+    
+    emit!(MilestoneAmendmentProposed {
+        pass_id: treatment_pass.pass_id,
+        amendment_id,
+        proposed_by: ctx.accounts.proposer.key(),
+        milestone_count,
+        timelock_ends_at,
+    });
+    
+    Ok(())
+}
+
+/// Sponsor approves or objects to a pending amendment, weighted by their contribution
+pub fn vote_on_amendment(
+    ctx: Context<VoteOnAmendment>,
+    amendment_id: u16,
+    approve: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let contribution = &ctx.accounts.sponsor_contribution.contribution;
+    let weight = if contribution.refund_claimed { 0 } else { contribution.amount_contributed };
+    require!(weight > 0, ErrorCode::UnauthorizedAmendmentVoter);
+    
+    let amendment = &mut ctx.accounts.amendment_account.amendment;
+    require!(amendment.status == AmendmentStatus::Pending, ErrorCode::AmendmentNotPending);
+    
+    if approve {
+        amendment.approval_weight = amendment.approval_weight.checked_add(weight)
+            .ok_or(ErrorCode::Overflow)?;
+    } else {
+        amendment.objection_weight = amendment.objection_weight.checked_add(weight)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.voter = ctx.accounts.sponsor.key();
+    vote_record.approve = approve;
+    vote_record.weight = weight;
+    vote_record.voted_at = clock.unix_timestamp;
+    vote_record.bump = ctx.bumps.vote_record;
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let escrow_bound = !ctx.accounts.milestone_escrow.data_is_empty();
+    
+    emit!(AmendmentVoteCast {
+        pass_id: treatment_pass.pass_id,
+        amendment_id,
+        voter: ctx.accounts.sponsor.key(),
+        approve,
+        weight,
+    });
+    
+    // Apply as soon as enough sponsors have explicitly approved
+    if meets_approval_share(treatment_pass, amendment.approval_weight) {
+        resolve_amendment(treatment_pass, amendment, true, escrow_bound, clock.unix_timestamp)?;
+    }
+    
+    Ok(())
+}
+
+/// Permissionless crank that settles an amendment once its timelock has passed
+pub fn finalize_milestone_amendment(
+    ctx: Context<FinalizeMilestoneAmendment>,
+    _amendment_id: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let amendment = &mut ctx.accounts.amendment_account.amendment;
+    
+    require!(amendment.status == AmendmentStatus::Pending, ErrorCode::AmendmentNotPending);
+    require!(
+        clock.unix_timestamp >= amendment.timelock_ends_at,
+        ErrorCode::AmendmentTimelockActive
+    );
+    
+    // Sponsors who stayed silent through the timelock count as consenting
+    let consenting_weight = treatment_pass.current_funding.saturating_sub(amendment.objection_weight);
+    let approved = meets_approval_share(treatment_pass, consenting_weight);
+    let escrow_bound = !ctx.accounts.milestone_escrow.data_is_empty();
+    
+    resolve_amendment(treatment_pass, amendment, approved, escrow_bound, clock.unix_timestamp)
+}
+
+/// Patient or their representative withdraws a pending amendment, freeing the pass for a new proposal
+pub fn withdraw_milestone_amendment(
+    ctx: Context<WithdrawMilestoneAmendment>,
+    _amendment_id: u16,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        acts_for_patient(
+            &ctx.accounts.proposer.key(),
+            &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+            ctx.accounts.patient_representative.as_deref(),
+            RepresentativeScope::ManageMilestones,
+        )?,
+        ErrorCode::UnauthorizedAmendmentProposer
+    );
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let amendment = &mut ctx.accounts.amendment_account.amendment;
+    require!(amendment.status == AmendmentStatus::Pending, ErrorCode::AmendmentNotPending);
+    
+    close_amendment(treatment_pass, amendment, AmendmentStatus::Withdrawn, clock.unix_timestamp);
+    
+    Ok(())
+}
+
+fn meets_approval_share(treatment_pass: &TreatmentPass, weight: u64) -> bool {
+    (weight as u128) * (FULLY_FUNDED_BASIS_POINTS as u128) >=
+        (treatment_pass.current_funding as u128) * (treatment_pass.amendment_approval_basis_points as u128)
+}
+
+fn resolve_amendment(
+    treatment_pass: &mut TreatmentPass,
+    amendment: &mut MilestoneAmendment,
+    approved: bool,
+    escrow_bound: bool,
+    now: i64,
+) -> Result<()> {
+    // The plan may have moved on since the proposal (e.g. a milestone was verified or failed);
+    // completion dates are judged as of the proposal so the timelock alone cannot outdate them
+    let status = if !approved {
+        AmendmentStatus::Rejected
+    } else if escrow_bound ||
+        validate_amendment(treatment_pass, &amendment.proposed_milestones, amendment.proposed_at).is_err() {
+        AmendmentStatus::Outdated
+    } else {
+        apply_amendment(treatment_pass, &amendment.proposed_milestones)?;
+        AmendmentStatus::Applied
+    };
+    close_amendment(treatment_pass, amendment, status, now);
+    
+    Ok(())
+}
+
+/// Records the amendment's final status on the amendment and in the pass's history
+fn close_amendment(
+    treatment_pass: &mut TreatmentPass,
+    amendment: &mut MilestoneAmendment,
+    status: AmendmentStatus,
+    now: i64,
+) {
+    amendment.status = status;
+    amendment.resolved_at = Some(now);
+    
+    treatment_pass.pending_amendment = None;
+    treatment_pass.amendment_history.push(AmendmentRecord {
+        amendment_id: amendment.amendment_id,
+        status: amendment.status.clone(),
+        approval_weight: amendment.approval_weight,
+        objection_weight: amendment.objection_weight,
+        resolved_at: now,
+    });
+    
+    emit!(MilestoneAmendmentResolved {
+        pass_id: treatment_pass.pass_id,
+        amendment_id: amendment.amendment_id,
+        status: amendment.status.clone(),
+        approval_weight: amendment.approval_weight,
+        objection_weight: amendment.objection_weight,
+    });
+}
+
+/// Checks a proposed replacement against the current plan; closed milestones are immutable
+fn validate_amendment(
+    treatment_pass: &TreatmentPass,
+    proposed_milestones: &[TreatmentMilestone],
+    now: i64,
+) -> Result<()> {
+    require!(!proposed_milestones.is_empty(), ErrorCode::EmptyAmendment);
+    
+    // Sponsors consented to a total; amendments restructure it rather than change it
+    let open_total = treatment_pass.milestones.iter()
+        .filter(|m| !m.completion_status.is_closed())
+        .try_fold(0u64, |total, m| total.checked_add(m.funding_amount))
+        .ok_or(ErrorCode::Overflow)?;
+    let proposed_total = proposed_milestones.iter()
+        .try_fold(0u64, |total, m| total.checked_add(m.funding_amount))
+        .ok_or(ErrorCode::Overflow)?;
+    require!(proposed_total == open_total, ErrorCode::AmendmentChangesTotal);
+    // The replacement must hold up as a plan of its own, exactly as a new pass's milestones would
+    validate_milestone_schedule(proposed_milestones, open_total, now)?;
+    
+    // Dropped milestones holding pledges stay on the pass as closed records so their earmarks can be traced
+    let retained_count = treatment_pass.milestones.iter()
+        .filter(|m| {
+            m.completion_status.is_closed() ||
+                (m.pledged_amount > 0 && !proposed_milestones.iter().any(|p| p.milestone_id == m.milestone_id))
+        })
+        .count();
+    require!(
+        retained_count + proposed_milestones.len() <= MAX_MILESTONES,
+        ErrorCode::TooManyMilestones
+    );
+    
+    for milestone in proposed_milestones {
+        require!(
+            !treatment_pass.milestones.iter()
+                .any(|m| m.milestone_id == milestone.milestone_id && m.completion_status.is_closed()),
+            ErrorCode::ClosedMilestoneImmutable
+        );
+        // Sponsors' earmarks must stay whole; shrinking below them would strand part of each allocation
+        require!(
+            treatment_pass.milestones.iter()
+                .find(|m| m.milestone_id == milestone.milestone_id)
+                .map_or(true, |m| milestone.funding_amount >= m.pledged_amount),
+            ErrorCode::MilestoneBelowPledged
+        );
+    }
+    
+    Ok(())
+}
+
+/// Replaces the open milestones, carrying progress and pledges over to milestones that keep their id;
+/// pledges to dropped milestones fall back to the general pool
+fn apply_amendment(treatment_pass: &mut TreatmentPass, proposed_milestones: &[TreatmentMilestone]) -> Result<()> {
+    let mut released_pledges: u64 = 0;
+    let mut milestones: Vec<TreatmentMilestone> = treatment_pass.milestones.iter()
+        .filter(|m| m.completion_status.is_closed())
+        .cloned()
+        .collect();
+    
+    for proposed in proposed_milestones {
+        let mut milestone = proposed.clone();
+        milestone.completion_timestamp = None;
+        milestone.verifying_provider = None;
+        milestone.verification_evidence = None;
+        milestone.reallocated_to = None;
+        milestone.ubuntu_community_validation = false;
+    
+        match treatment_pass.milestones.iter()
+            .find(|m| m.milestone_id == proposed.milestone_id && !m.completion_status.is_closed()) {
+            Some(existing) => {
+                milestone.pledged_amount = existing.pledged_amount;
+                milestone.completion_status = existing.completion_status.clone();
+                milestone.completion_evidence = existing.completion_evidence.clone();
+                milestone.overdue_since = existing.overdue_since;
+                milestone.overdue_round = existing.overdue_round;
+                milestone.elder_council_review = existing.elder_council_review;
+            }
+            None => {
+                milestone.pledged_amount = 0;
+                milestone.completion_status = MilestoneStatus::NotStarted;
+                milestone.completion_evidence = None;
+                milestone.overdue_since = None;
+                milestone.overdue_round = 0;
+                milestone.elder_council_review = None;
+            }
+        }
+    
+        milestones.push(milestone);
+    }
+    
+    // Dropped milestones with pledges are closed with no successor, so their earmarks resolve to the general pool
+    for existing in treatment_pass.milestones.iter().filter(|m| !m.completion_status.is_closed()) {
+        if existing.pledged_amount > 0 &&
+            !proposed_milestones.iter().any(|m| m.milestone_id == existing.milestone_id) {
+            released_pledges = released_pledges.checked_add(existing.pledged_amount)
+                .ok_or(ErrorCode::Overflow)?;
+            
+            let mut dropped = existing.clone();
+            dropped.completion_status = MilestoneStatus::Reallocated;
+            dropped.reallocated_to = None;
+            dropped.funding_amount = 0;
+            dropped.pledged_amount = 0;
+            dropped.overdue_since = None;
+            milestones.push(dropped);
+        }
+    }
+    
+    treatment_pass.milestones = milestones;
+    treatment_pass.unearmarked_funding = treatment_pass.unearmarked_funding.checked_add(released_pledges)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    Ok(())
+}
+
+/// Events for milestone amendments
+#[event]
+pub struct MilestoneAmendmentProposed {
+    pub pass_id: u64,
+    pub amendment_id: u16,
+    pub proposed_by: Pubkey,
+    pub milestone_count: u8,
+    pub timelock_ends_at: i64,
+}
+
+#[event]
+pub struct AmendmentVoteCast {
+    pub pass_id: u64,
+    pub amendment_id: u16,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct MilestoneAmendmentResolved {
+    pub pass_id: u64,
+    pub amendment_id: u16,
+    pub status: AmendmentStatus,
+    pub approval_weight: u64,
+    pub objection_weight: u64,
+}
+
+/// Custom error codes for milestone amendments
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Only the patient or their representative may propose a milestone amendment")]
+    UnauthorizedAmendmentProposer,
+    #[msg("Only sponsors with an outstanding contribution may vote on amendments")]
+    UnauthorizedAmendmentVoter,
+    #[msg("Treatment pass is closed")]
+    TreatmentPassClosed,
+    #[msg("Another amendment is already pending")]
+    AmendmentAlreadyPending,
+    #[msg("Treatment pass has reached its maximum number of amendments")]
+    TooManyAmendments,
+    #[msg("Amendment reason exceeds maximum length")]
+    AmendmentReasonTooLong,
+    #[msg("Amendment is not pending")]
+    AmendmentNotPending,
+    #[msg("Amendment timelock has not elapsed")]
+    AmendmentTimelockActive,
+    #[msg("Amendment must propose at least one milestone")]
+    EmptyAmendment,
+    #[msg("Too many milestones")]
+    TooManyMilestones,
+    #[msg("Verified or closed milestones cannot be amended")]
+    ClosedMilestoneImmutable,
+    #[msg("Amended milestones must keep the same total funding")]
+    AmendmentChangesTotal,
+    #[msg("Milestone funding cannot drop below the amount sponsors have earmarked to it")]
+    MilestoneBelowPledged,
+    #[msg("Milestones cannot be amended once a milestone escrow is bound to the pass")]
+    MilestoneEscrowBound,
+}
@@ -1,16 +1,28 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions as sysvar_instructions};
 use anchor_spl::token::{self, Token, TokenAccount, Mint, Transfer};
 
+use crate::patient_representatives::{acts_for_patient, PatientRepresentativeAccount, RepresentativeScope};
+use crate::elder_council::ElderCouncilAccount;
+use crate::treatment_pass_nft::TreatmentPassAccount;
+use crate::verifier_registry::{VerifierEntryAccount, VerifierKind};
+
 /// Milestone-based escrow system for treatment pass funding
 /// Implements secure fund holding with milestone-triggered releases
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MilestoneEscrow {
     /// Treatment pass this escrow is associated with
     pub treatment_pass_id: u64,
+    /// Treatment pass account this escrow funds
+    pub treatment_pass: Pubkey,
+    /// Patient recorded on the treatment pass; the only recipient of releases
+    pub patient: Pubkey,
     /// Total amount held in escrow
     pub total_amount: u64,
     /// Amount already released
     pub released_amount: u64,
+    /// Amount refunded to depositors after the bound pass was cancelled or expired
+    pub refunded_amount: u64,
     /// Escrow creation timestamp
     pub created_at: i64,
     /// Whether escrow is active
@@ -39,6 +51,18 @@ pub struct MilestoneRelease {
     pub received_verifications: Vec<ReceivedVerification>,
 }
 
+impl MilestoneRelease {
+    /// Borsh-serialized size of this release schedule
+    pub fn serialized_size(&self) -> usize {
+        1 + // milestone_id
+            8 + // release_amount
+            1 + // is_released
+            1 + 8 + // release_timestamp
+            4 + self.required_verifications.iter().map(|rv| rv.serialized_size()).sum::<usize>() +
+            4 + self.received_verifications.len() * ReceivedVerification::MAXIMUM_SIZE
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct VerificationRequirement {
     /// Type of verification required
@@ -47,6 +71,77 @@ pub struct VerificationRequirement {
     pub required_verifier: Option<Pubkey>,
     /// Whether this verification is mandatory or optional
     pub is_mandatory: bool,
+    /// Verifiers eligible to satisfy this requirement, each of whom must be an active registry verifier
+    pub eligible_verifiers: Vec<Pubkey>,
+    /// Number of distinct verifications needed to satisfy this requirement (M of N)
+    pub threshold: u8,
+}
+
+impl VerificationRequirement {
+    /// Borsh-serialized size of this requirement
+    pub fn serialized_size(&self) -> usize {
+        1 + // verification_type
+            1 + 32 + // required_verifier
+            1 + // is_mandatory
+            4 + self.eligible_verifiers.len() * 32 + // eligible_verifiers
+            1 // threshold
+    }
+
+    /// Whether the given verifier may contribute towards this requirement
+    pub fn is_eligible(&self, verifier: &Pubkey) -> bool {
+        if let Some(required_verifier) = self.required_verifier {
+            return required_verifier == *verifier;
+        }
+        self.eligible_verifiers.contains(verifier)
+    }
+
+    /// Whether enough distinct eligible verifiers have signed off on this requirement
+    pub fn is_satisfied_by(&self, received_verifications: &[ReceivedVerification]) -> bool {
+        let matching_verifications = received_verifications
+            .iter()
+            .filter(|rv| rv.verification_type == self.verification_type && self.is_eligible(&rv.verifier))
+            .count();
+        matching_verifications >= self.threshold as usize
+    }
+
+    fn validate(&self, patient: &Pubkey) -> Result<()> {
+        require!(self.threshold > 0, ErrorCode::InvalidVerificationRequirement);
+
+        // Self reports are recorded alongside independent verification but never gate a release
+        if self.verification_type == EscrowVerificationType::PatientSelfReport {
+            require!(
+                !self.is_mandatory &&
+                    self.required_verifier == Some(*patient) &&
+                    self.threshold == 1 &&
+                    self.eligible_verifiers.is_empty(),
+                ErrorCode::InvalidVerificationRequirement
+            );
+            return Ok(());
+        }
+
+        // Every other requirement names its verifiers, and the patient is never one of them
+        require!(
+            self.required_verifier.is_some() || !self.eligible_verifiers.is_empty(),
+            ErrorCode::InvalidVerificationRequirement
+        );
+        require!(
+            self.required_verifier != Some(*patient) && !self.eligible_verifiers.contains(patient),
+            ErrorCode::PatientCannotVerify
+        );
+        if self.required_verifier.is_some() {
+            require!(
+                self.threshold == 1 && self.eligible_verifiers.is_empty(),
+                ErrorCode::InvalidVerificationRequirement
+            );
+        }
+        if !self.eligible_verifiers.is_empty() {
+            require!(
+                self.threshold as usize <= self.eligible_verifiers.len(),
+                ErrorCode::InvalidVerificationRequirement
+            );
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -63,6 +158,113 @@ pub struct ReceivedVerification {
     pub verification_signature: [u8; 64],
 }
 
+impl ReceivedVerification {
+    pub const MAXIMUM_SIZE: usize = 32 + // verifier
+        1 + // verification_type
+        8 + // verified_at
+        1 + 4 + MAX_EVIDENCE_HASH_LEN + // evidence_hash
+        64; // verification_signature
+}
+
+/// Maximum length of a verification evidence hash (IPFS CID, records hash, etc.)
+pub const MAX_EVIDENCE_HASH_LEN: usize = 128;
+
+/// Domain separator prefixed to every off-chain milestone attestation
+pub const ATTESTATION_DOMAIN: &[u8] = b"UBUNTU_HEALTH_MILESTONE_ATTESTATION";
+/// Oldest attestation accepted for submission (7 days)
+pub const MAX_ATTESTATION_AGE_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Canonical message a verifier signs off-chain to attest a milestone:
+/// domain || escrow account || milestone id || verification type || timestamp (LE) ||
+/// evidence presence byte || evidence length (u32 LE) || evidence hash
+pub fn attestation_message(
+    escrow: &Pubkey,
+    milestone_id: u8,
+    verification_type: &EscrowVerificationType,
+    evidence_hash: &Option<String>,
+    attested_at: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(
+        ATTESTATION_DOMAIN.len() + 32 + 1 + 1 + 8 + 1 + 4 + evidence_hash.as_ref().map_or(0, |h| h.len()),
+    );
+    message.extend_from_slice(ATTESTATION_DOMAIN);
+    message.extend_from_slice(escrow.as_ref());
+    message.push(milestone_id);
+    message.push(verification_type.clone() as u8);
+    message.extend_from_slice(&attested_at.to_le_bytes());
+    match evidence_hash {
+        Some(evidence_hash) => {
+            message.push(1);
+            message.extend_from_slice(&(evidence_hash.len() as u32).to_le_bytes());
+            message.extend_from_slice(evidence_hash.as_bytes());
+        }
+        None => message.push(0),
+    }
+    message
+}
+
+/// Checks that the instruction immediately preceding this one is an Ed25519 precompile
+/// verification of `signature` by `signer` over exactly `message`
+fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    // Ed25519SignatureOffsets layout, all u16 little-endian
+    const HEADER_LEN: usize = 2;
+    const OFFSETS_LEN: usize = 14;
+    const SAME_INSTRUCTION: u16 = u16::MAX;
+
+    let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidVerificationSignature);
+
+    let ed25519_ix = sysvar_instructions::load_instruction_at_checked(
+        (current_index - 1) as usize,
+        instructions_sysvar,
+    )?;
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID && ed25519_ix.accounts.is_empty(),
+        ErrorCode::InvalidVerificationSignature
+    );
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= HEADER_LEN + OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidVerificationSignature
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_offset = read_u16(HEADER_LEN) as usize;
+    let signature_ix_index = read_u16(HEADER_LEN + 2);
+    let public_key_offset = read_u16(HEADER_LEN + 4) as usize;
+    let public_key_ix_index = read_u16(HEADER_LEN + 6);
+    let message_offset = read_u16(HEADER_LEN + 8) as usize;
+    let message_size = read_u16(HEADER_LEN + 10) as usize;
+    let message_ix_index = read_u16(HEADER_LEN + 12);
+
+    // Signature, key and message must all live in the precompile instruction itself
+    require!(
+        signature_ix_index == SAME_INSTRUCTION &&
+            public_key_ix_index == SAME_INSTRUCTION &&
+            message_ix_index == SAME_INSTRUCTION,
+        ErrorCode::InvalidVerificationSignature
+    );
+
+    let signed_key = data.get(public_key_offset..public_key_offset + 32);
+    let signed_signature = data.get(signature_offset..signature_offset + 64);
+    let signed_message = data.get(message_offset..message_offset + message_size);
+
+    require!(
+        signed_key == Some(signer.as_ref()) &&
+            signed_signature == Some(signature.as_ref()) &&
+            signed_message == Some(message),
+        ErrorCode::InvalidVerificationSignature
+    );
+
+    Ok(())
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum EscrowVerificationType {
     /// Medical provider verification
@@ -79,6 +281,21 @@ pub enum EscrowVerificationType {
     EmergencyOverride,
 }
 
+impl EscrowVerificationType {
+    /// Whether a registry verifier of the given kind may attest this type of verification
+    pub fn accepts(&self, verifier_kind: &VerifierKind) -> bool {
+        match self {
+            EscrowVerificationType::HealthcareProvider |
+            EscrowVerificationType::EmergencyOverride => *verifier_kind == VerifierKind::AccreditedProvider,
+            EscrowVerificationType::ThirdPartyMedical |
+            EscrowVerificationType::AutomatedVerification => *verifier_kind == VerifierKind::AccreditedLaboratory,
+            EscrowVerificationType::UbuntuHealthCommunity => *verifier_kind == VerifierKind::CommunityWitness,
+            // Self reports come from the patient, who is not a registry verifier for their own escrow
+            EscrowVerificationType::PatientSelfReport => false,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct EmergencyReleaseConditions {
     /// Whether emergency release is enabled
@@ -91,17 +308,60 @@ pub struct EmergencyReleaseConditions {
     pub emergency_initiated: Option<i64>,
     /// Emergency release reason
     pub emergency_reason: Option<String>,
+    /// Releaser who initiated the pending emergency release
+    pub emergency_initiated_by: Option<Pubkey>,
+    /// Releasers who have voted to cancel the pending emergency release
+    pub cancel_approvals: Vec<Pubkey>,
+}
+
+impl EmergencyReleaseConditions {
+    /// Borsh-serialized size with room for a maximal reason and a full set of cancel votes
+    pub fn maximum_size(releaser_count: usize) -> usize {
+        1 + // emergency_release_enabled
+            4 + releaser_count * 32 + // emergency_releasers
+            8 + // emergency_delay_seconds
+            1 + 8 + // emergency_initiated
+            1 + 4 + MAX_EMERGENCY_REASON_LEN + // emergency_reason
+            1 + 32 + // emergency_initiated_by
+            4 + releaser_count * 32 // cancel_approvals
+    }
+
+    fn is_releaser(&self, key: &Pubkey) -> bool {
+        self.emergency_releasers.contains(key)
+    }
+
+    fn clear_pending(&mut self) {
+        self.emergency_initiated = None;
+        self.emergency_reason = None;
+        self.emergency_initiated_by = None;
+        self.cancel_approvals.clear();
+    }
 }
 
+/// Maximum number of emergency releasers per escrow
+pub const MAX_EMERGENCY_RELEASERS: usize = 5;
+/// Maximum length of an emergency release reason
+pub const MAX_EMERGENCY_REASON_LEN: usize = 256;
+/// Shortest delay allowed between initiating and executing an emergency release (72 hours),
+/// long enough for sponsors and elders to notice and cancel
+pub const MIN_EMERGENCY_DELAY_SECONDS: i64 = 72 * 60 * 60;
+/// Longest delay allowed between initiating and executing an emergency release (30 days)
+pub const MAX_EMERGENCY_DELAY_SECONDS: i64 = 30 * 24 * 60 * 60;
+
 /// Instructions for milestone escrow operations
 #[derive(Accounts)]
-#[instruction(treatment_pass_id: u64)]
+#[instruction(
+    treatment_pass_id: u64,
+    milestone_releases: Vec<MilestoneRelease>,
+    ubuntu_health_verification_required: bool,
+    emergency_releasers: Vec<Pubkey>
+)]
 pub struct CreateMilestoneEscrow<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + std::mem::size_of::<MilestoneEscrowAccount>(),
-        seeds = [b"milestone_escrow", treatment_pass_id.to_le_bytes().as_ref()],
+        space = MilestoneEscrowAccount::space(&milestone_releases, emergency_releasers.len()),
+        seeds = [b"milestone_escrow", treatment_pass_account.key().as_ref()],
         bump
     )]
     pub escrow_account: Account<'info, MilestoneEscrowAccount>,
@@ -111,11 +371,32 @@ pub struct CreateMilestoneEscrow<'info> {
         payer = payer,
         token::mint = funding_mint,
         token::authority = escrow_account,
-        seeds = [b"escrow_vault", treatment_pass_id.to_le_bytes().as_ref()],
+        seeds = [b"escrow_vault", treatment_pass_account.key().as_ref()],
         bump
     )]
     pub escrow_vault: Account<'info, TokenAccount>,
     
+    /// Treatment pass this escrow funds
+    #[account(
+        seeds = [b"treatment_pass", patient.key().as_ref(), treatment_pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Patient who owns the treatment pass
+    /// CHECK: Only used as a key; bound to the treatment pass by its seeds
+    pub patient: UncheckedAccount<'info>,
+    
+    /// Patient or their representative setting up the escrow
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    #[account(
+        seeds = [b"patient_representative", patient.key().as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
     pub funding_mint: Account<'info, Mint>,
     
     #[account(mut)]
@@ -127,22 +408,37 @@ pub struct CreateMilestoneEscrow<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(treatment_pass_id: u64, amount: u64)]
+#[instruction(treatment_pass_id: u64)]
 pub struct FundEscrow<'info> {
     #[account(
         mut,
-        seeds = [b"milestone_escrow", treatment_pass_id.to_le_bytes().as_ref()],
-        bump = escrow_account.bump
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
     )]
     pub escrow_account: Account<'info, MilestoneEscrowAccount>,
     
     #[account(
         mut,
-        seeds = [b"escrow_vault", treatment_pass_id.to_le_bytes().as_ref()],
+        seeds = [b"escrow_vault", escrow_account.escrow.treatment_pass.as_ref()],
         bump
     )]
     pub escrow_vault: Account<'info, TokenAccount>,
     
+    /// Treatment pass the escrow funds
+    #[account(address = escrow_account.escrow.treatment_pass @ ErrorCode::TreatmentPassMismatch)]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Sponsor's running deposit total, refundable if the pass closes
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = EscrowDepositAccount::LEN,
+        seeds = [b"escrow_deposit", escrow_account.key().as_ref(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub escrow_deposit: Account<'info, EscrowDepositAccount>,
+    
     #[account(mut)]
     pub sponsor_token_account: Account<'info, TokenAccount>,
     
@@ -150,30 +446,216 @@ pub struct FundEscrow<'info> {
     pub sponsor: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(treatment_pass_id: u64, milestone_id: u8)]
+#[instruction(treatment_pass_id: u64)]
 pub struct ReleaseMilestoneFunds<'info> {
     #[account(
         mut,
-        seeds = [b"milestone_escrow", treatment_pass_id.to_le_bytes().as_ref()],
-        bump = escrow_account.bump
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
     )]
     pub escrow_account: Account<'info, MilestoneEscrowAccount>,
     
     #[account(
         mut,
-        seeds = [b"escrow_vault", treatment_pass_id.to_le_bytes().as_ref()],
+        seeds = [b"escrow_vault", escrow_account.escrow.treatment_pass.as_ref()],
         bump
     )]
     pub escrow_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    /// Patient's token account receiving the release
+    #[account(
+        mut,
+        constraint = patient_token_account.owner == escrow_account.escrow.patient @ ErrorCode::InvalidPatientTokenAccount
+    )]
     pub patient_token_account: Account<'info, TokenAccount>,
     
+    /// Treatment pass the escrow funds
+    #[account(address = escrow_account.escrow.treatment_pass @ ErrorCode::TreatmentPassMismatch)]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Anyone may execute the release once the verification quorum is met
+    pub executor: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(treatment_pass_id: u64, milestone_id: u8, verifier: Pubkey)]
+pub struct SubmitEscrowVerification<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch,
+        realloc = escrow_account.to_account_info().data_len() + ReceivedVerification::MAXIMUM_SIZE,
+        realloc::payer = submitter,
+        realloc::zero = false
+    )]
+    pub escrow_account: Account<'info, MilestoneEscrowAccount>,
+    
+    /// Submits the verifier's attestation; need not be the verifier
     #[account(mut)]
-    pub healthcare_provider: Signer<'info>,
+    pub submitter: Signer<'info>,
+    
+    /// Verifier's registry entry; omitted only for the patient's self report
+    #[account(
+        seeds = [b"verifier", verifier.as_ref()],
+        bump = verifier_entry.bump
+    )]
+    pub verifier_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// CHECK: Instructions sysvar, used to introspect the Ed25519 precompile instruction
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(treatment_pass_id: u64)]
+pub struct InitiateEmergencyRelease<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
+    )]
+    pub escrow_account: Account<'info, MilestoneEscrowAccount>,
+    
+    /// Listed emergency releaser
+    pub releaser: Signer<'info>,
+    
+    /// Releaser's registry entry; omitted when the releaser initiates as a council elder
+    #[account(
+        seeds = [b"verifier", releaser.key().as_ref()],
+        bump = releaser_entry.bump
+    )]
+    pub releaser_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// Elder council; required when the releaser initiates as a council elder
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Option<Account<'info, ElderCouncilAccount>>,
+    
+    /// Releaser's would-be appointment as the patient's representative, which must not exist
+    /// CHECK: Address is derived from the escrow's patient and the releaser; only checked to be empty
+    #[account(
+        seeds = [b"patient_representative", escrow_account.escrow.patient.as_ref(), releaser.key().as_ref()],
+        bump
+    )]
+    pub releaser_representative: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(treatment_pass_id: u64)]
+pub struct CancelEmergencyRelease<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
+    )]
+    pub escrow_account: Account<'info, MilestoneEscrowAccount>,
+    
+    /// Patient, their representative or one of the listed emergency releasers
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted unless a representative acts
+    #[account(
+        seeds = [b"patient_representative", escrow_account.escrow.patient.as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(treatment_pass_id: u64)]
+pub struct ExecuteEmergencyRelease<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
+    )]
+    pub escrow_account: Account<'info, MilestoneEscrowAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow_vault", escrow_account.escrow.treatment_pass.as_ref()],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    /// Patient's token account receiving the emergency release
+    #[account(
+        mut,
+        constraint = patient_token_account.owner == escrow_account.escrow.patient @ ErrorCode::InvalidPatientTokenAccount
+    )]
+    pub patient_token_account: Account<'info, TokenAccount>,
+    
+    /// Treatment pass the escrow funds
+    #[account(address = escrow_account.escrow.treatment_pass @ ErrorCode::TreatmentPassMismatch)]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Patient, their representative or one of the listed emergency releasers
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted unless a representative acts
+    #[account(
+        seeds = [b"patient_representative", escrow_account.escrow.patient.as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(treatment_pass_id: u64)]
+pub struct ClaimEscrowRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", escrow_account.escrow.treatment_pass.as_ref()],
+        bump = escrow_account.bump,
+        constraint = escrow_account.escrow.treatment_pass_id == treatment_pass_id @ ErrorCode::TreatmentPassMismatch
+    )]
+    pub escrow_account: Account<'info, MilestoneEscrowAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"escrow_vault", escrow_account.escrow.treatment_pass.as_ref()],
+        bump
+    )]
+    pub escrow_vault: Account<'info, TokenAccount>,
+    
+    /// Treatment pass the escrow funds; must be cancelled or expired
+    #[account(address = escrow_account.escrow.treatment_pass @ ErrorCode::TreatmentPassMismatch)]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Depositor's running deposit total
+    #[account(
+        mut,
+        seeds = [b"escrow_deposit", escrow_account.key().as_ref(), depositor.key().as_ref()],
+        bump = escrow_deposit.bump
+    )]
+    pub escrow_deposit: Account<'info, EscrowDepositAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    /// Depositor's token account receiving the refund
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key() @ ErrorCode::InvalidRefundAccount,
+        constraint = depositor_token_account.mint == escrow_vault.mint @ ErrorCode::InvalidRefundAccount
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
 }
@@ -185,39 +667,123 @@ pub struct MilestoneEscrowAccount {
 }
 
 impl MilestoneEscrowAccount {
-    pub const MAXIMUM_SIZE: usize = 8 + // discriminator
-        8 + // treatment_pass_id
-        8 + // total_amount
-        8 + // released_amount
-        8 + // created_at
-        1 + // is_active
-        4 + (32 * 10) + // milestone_releases (assuming max 10 milestones)
-        4 + 8 + 4 + (32 * 5) + 8 + 4 + 100 + // emergency_conditions
-        1 + // ubuntu_health_verification_required
+    /// Account space needed for an escrow with the given release schedule and releasers
+    pub fn space(milestone_releases: &[MilestoneRelease], emergency_releaser_count: usize) -> usize {
+        8 + // discriminator
+            8 + // treatment_pass_id
+            32 + // treatment_pass
+            32 + // patient
+            8 + // total_amount
+            8 + // released_amount
+            8 + // refunded_amount
+            8 + // created_at
+            1 + // is_active
+            4 + milestone_releases.iter().map(|mr| mr.serialized_size()).sum::<usize>() +
+            EmergencyReleaseConditions::maximum_size(emergency_releaser_count) +
+            1 + // ubuntu_health_verification_required
+            1 // bump
+    }
+}
+
+/// Running total a single depositor has paid into an escrow through `fund_escrow`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EscrowDeposit {
+    /// Escrow the deposits were made to
+    pub escrow: Pubkey,
+    /// Wallet that made the deposits
+    pub depositor: Pubkey,
+    /// Total amount deposited
+    pub amount: u64,
+    /// Whether the depositor has reclaimed their share after the pass closed
+    pub refunded: bool,
+}
+
+#[account]
+pub struct EscrowDepositAccount {
+    pub deposit: EscrowDeposit,
+    pub bump: u8,
+}
+
+impl EscrowDepositAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // escrow
+        32 + // depositor
+        8 + // amount
+        1 + // refunded
         1; // bump
 }
 
 /// Core escrow functionality implementations
 impl MilestoneEscrowAccount {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_escrow(
         &mut self,
         treatment_pass_id: u64,
+        treatment_pass: Pubkey,
+        patient: Pubkey,
         milestone_releases: Vec<MilestoneRelease>,
         ubuntu_health_verification_required: bool,
+        emergency_releasers: Vec<Pubkey>,
+        emergency_delay_seconds: i64,
     ) -> Result<()> {
+        require!(
+            emergency_releasers.len() <= MAX_EMERGENCY_RELEASERS,
+            ErrorCode::TooManyEmergencyReleasers
+        );
+        require!(
+            emergency_delay_seconds >= MIN_EMERGENCY_DELAY_SECONDS,
+            ErrorCode::EmergencyDelayTooShort
+        );
+        require!(
+            emergency_delay_seconds <= MAX_EMERGENCY_DELAY_SECONDS,
+            ErrorCode::EmergencyDelayTooLong
+        );
+        
+        // Releasers are independent of the patient and each counts once towards a cancellation majority
+        for (index, releaser) in emergency_releasers.iter().enumerate() {
+            require_keys_neq!(*releaser, patient, ErrorCode::InvalidEmergencyReleaser);
+            require!(
+                !emergency_releasers[..index].contains(releaser),
+                ErrorCode::DuplicateEmergencyReleaser
+            );
+        }
+
+        for milestone_release in &milestone_releases {
+            require!(
+                !milestone_release.is_released && milestone_release.release_timestamp.is_none(),
+                ErrorCode::MilestoneScheduleMismatch
+            );
+            require!(
+                milestone_release.received_verifications.is_empty(),
+                ErrorCode::InvalidVerificationRequirement
+            );
+            require!(
+                milestone_release.required_verifications.iter().any(|rv| rv.is_mandatory),
+                ErrorCode::MissingMandatoryVerification
+            );
+            for requirement in &milestone_release.required_verifications {
+                requirement.validate(&patient)?;
+            }
+        }
+
         self.escrow = MilestoneEscrow {
             treatment_pass_id,
+            treatment_pass,
+            patient,
             total_amount: 0,
             released_amount: 0,
+            refunded_amount: 0,
             created_at: Clock::get()?.unix_timestamp,
             is_active: true,
             milestone_releases,
             emergency_conditions: EmergencyReleaseConditions {
-                emergency_release_enabled: false,
-                emergency_releasers: vec![],
-                emergency_delay_seconds: 86400, // 24 hours default
+                emergency_release_enabled: !emergency_releasers.is_empty(),
+                emergency_releasers,
+                emergency_delay_seconds,
                 emergency_initiated: None,
                 emergency_reason: None,
+                emergency_initiated_by: None,
+                cancel_approvals: vec![],
             },
             ubuntu_health_verification_required,
         };
@@ -227,10 +793,25 @@ impl MilestoneEscrowAccount {
     pub fn add_funds(&mut self, amount: u64) -> Result<()> {
         self.escrow.total_amount = self.escrow.total_amount
             .checked_add(amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(ErrorCode::Overflow)?;
         Ok(())
     }
 
+    /// Depositor's pro-rata share of the funds left unreleased when the bound pass closed
+    pub fn deposit_refund(&self, deposited: u64) -> Result<u64> {
+        if self.escrow.total_amount == 0 {
+            return Ok(0);
+        }
+        let unreleased = self.escrow.total_amount
+            .checked_sub(self.escrow.released_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        let refund = (deposited as u128)
+            .checked_mul(unreleased as u128)
+            .and_then(|share| share.checked_div(self.escrow.total_amount as u128))
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(refund as u64)
+    }
+
     pub fn can_release_milestone(&self, milestone_id: u8) -> Result<bool> {
         let milestone_release = self.escrow.milestone_releases
             .iter()
@@ -241,26 +822,24 @@ impl MilestoneEscrowAccount {
             return Ok(false);
         }
 
-        // Check if all required verifications are received
-        let mandatory_verifications: Vec<_> = milestone_release.required_verifications
+        // Check that every mandatory requirement has reached its verification quorum;
+        // a release with no mandatory requirement is never releasable
+        let mut mandatory_requirements = milestone_release.required_verifications
             .iter()
             .filter(|rv| rv.is_mandatory)
-            .collect();
-
-        for required_verification in mandatory_verifications {
-            let verification_received = milestone_release.received_verifications
-                .iter()
-                .any(|rv| rv.verification_type == required_verification.verification_type);
-            
-            if !verification_received {
-                return Ok(false);
-            }
+            .peekable();
+        if mandatory_requirements.peek().is_none() {
+            return Ok(false);
         }
+        let quorum_reached = mandatory_requirements
+            .all(|rv| rv.is_satisfied_by(&milestone_release.received_verifications));
 
-        Ok(true)
+        Ok(quorum_reached)
     }
 
     pub fn release_milestone_funds(&mut self, milestone_id: u8) -> Result<u64> {
+        require!(self.escrow.is_active, ErrorCode::EscrowInactive);
+
         if !self.can_release_milestone(milestone_id)? {
             return Err(ErrorCode::MilestoneVerificationIncomplete.into());
         }
@@ -279,24 +858,167 @@ impl MilestoneEscrowAccount {
 
         self.escrow.released_amount = self.escrow.released_amount
             .checked_add(milestone_release.release_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+            .ok_or(ErrorCode::Overflow)?;
 
         Ok(milestone_release.release_amount)
     }
 
+    /// Records a verification; `verifier_kind` is the verifier's active registry accreditation, if any
     pub fn add_verification(
         &mut self,
         milestone_id: u8,
         verification: ReceivedVerification,
+        verifier_kind: Option<&VerifierKind>,
     ) -> Result<()> {
         let milestone_release = self.escrow.milestone_releases
             .iter_mut()
             .find(|mr| mr.milestone_id == milestone_id)
             .ok_or(ErrorCode::MilestoneNotFound)?;
 
+        require!(self.escrow.is_active, ErrorCode::EscrowInactive);
+
+        if milestone_release.is_released {
+            return Err(ErrorCode::MilestoneAlreadyReleased.into());
+        }
+
+        if let Some(evidence_hash) = &verification.evidence_hash {
+            require!(
+                evidence_hash.len() <= MAX_EVIDENCE_HASH_LEN,
+                ErrorCode::EvidenceHashTooLong
+            );
+        }
+
+        // Only the patient self-reports; every other verification needs a matching registry accreditation
+        if verification.verification_type == EscrowVerificationType::PatientSelfReport {
+            require_keys_eq!(verification.verifier, self.escrow.patient, ErrorCode::UnauthorizedEscrowVerifier);
+        } else {
+            require_keys_neq!(verification.verifier, self.escrow.patient, ErrorCode::PatientCannotVerify);
+            require!(
+                verifier_kind.map_or(false, |kind| verification.verification_type.accepts(kind)),
+                ErrorCode::UnregisteredEscrowVerifier
+            );
+        }
+
+        // Each verifier may only sign off on a milestone once
+        require!(
+            !milestone_release.received_verifications
+                .iter()
+                .any(|rv| rv.verifier == verification.verifier),
+            ErrorCode::DuplicateVerifier
+        );
+
+        let mut matching_requirements = milestone_release.required_verifications
+            .iter()
+            .filter(|rv| rv.verification_type == verification.verification_type)
+            .peekable();
+
+        if matching_requirements.peek().is_none() {
+            return Err(ErrorCode::VerificationTypeNotRequired.into());
+        }
+
+        require!(
+            matching_requirements.any(|rv| rv.is_eligible(&verification.verifier)),
+            ErrorCode::UnauthorizedEscrowVerifier
+        );
+
         milestone_release.received_verifications.push(verification);
         Ok(())
     }
+
+    pub fn initiate_emergency_release(
+        &mut self,
+        releaser: Pubkey,
+        reason: String,
+        now: i64,
+    ) -> Result<()> {
+        let conditions = &mut self.escrow.emergency_conditions;
+
+        require!(
+            conditions.emergency_release_enabled && conditions.is_releaser(&releaser),
+            ErrorCode::EmergencyReleaseNotAuthorized
+        );
+        require!(self.escrow.is_active, ErrorCode::EscrowInactive);
+        require!(
+            conditions.emergency_initiated.is_none(),
+            ErrorCode::EmergencyReleaseAlreadyInitiated
+        );
+        require!(
+            reason.len() <= MAX_EMERGENCY_REASON_LEN,
+            ErrorCode::EmergencyReasonTooLong
+        );
+
+        conditions.emergency_initiated = Some(now);
+        conditions.emergency_reason = Some(reason);
+        conditions.emergency_initiated_by = Some(releaser);
+        conditions.cancel_approvals.clear();
+        Ok(())
+    }
+
+    /// Records a cancellation vote and returns whether the pending release was cancelled.
+    /// The patient or their representative cancels outright; releasers cancel once a strict majority agrees.
+    pub fn cancel_emergency_release(&mut self, authority: Pubkey, authority_acts_for_patient: bool) -> Result<bool> {
+        let conditions = &mut self.escrow.emergency_conditions;
+
+        require!(
+            conditions.emergency_initiated.is_some(),
+            ErrorCode::EmergencyReleaseNotInitiated
+        );
+
+        if authority_acts_for_patient {
+            conditions.clear_pending();
+            return Ok(true);
+        }
+
+        require!(
+            conditions.is_releaser(&authority),
+            ErrorCode::EmergencyReleaseNotAuthorized
+        );
+        require!(
+            !conditions.cancel_approvals.contains(&authority),
+            ErrorCode::DuplicateCancelApproval
+        );
+
+        conditions.cancel_approvals.push(authority);
+
+        if conditions.cancel_approvals.len() * 2 > conditions.emergency_releasers.len() {
+            conditions.clear_pending();
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    /// Closes out the escrow after the emergency delay, returning the amount to release
+    pub fn execute_emergency_release(
+        &mut self,
+        authority: Pubkey,
+        authority_acts_for_patient: bool,
+        now: i64,
+    ) -> Result<u64> {
+        require!(self.escrow.is_active, ErrorCode::EscrowInactive);
+
+        let conditions = &self.escrow.emergency_conditions;
+        require!(
+            authority_acts_for_patient || conditions.is_releaser(&authority),
+            ErrorCode::EmergencyReleaseNotAuthorized
+        );
+
+        let initiated_at = conditions.emergency_initiated
+            .ok_or(ErrorCode::EmergencyReleaseNotInitiated)?;
+        let executable_at = initiated_at
+            .checked_add(conditions.emergency_delay_seconds)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(now >= executable_at, ErrorCode::EmergencyDelayNotElapsed);
+
+        let release_amount = self.escrow.total_amount
+            .checked_sub(self.escrow.released_amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.escrow.released_amount = self.escrow.total_amount;
+        self.escrow.is_active = false;
+
+        Ok(release_amount)
+    }
 }
 
 /// Escrow instruction implementations
@@ -305,14 +1027,51 @@ pub fn create_milestone_escrow(
     treatment_pass_id: u64,
     milestone_releases: Vec<MilestoneRelease>,
     ubuntu_health_verification_required: bool,
+    emergency_releasers: Vec<Pubkey>,
+    emergency_delay_seconds: i64,
 ) -> Result<()> {
+    require!(
+        acts_for_patient(
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.patient.key(),
+            ctx.accounts.patient_representative.as_deref(),
+            RepresentativeScope::ManageTreatment,
+        )?,
+        ErrorCode::UnauthorizedEscrowCreator
+    );
+
+    let treatment_pass = &ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    // Release schedule must mirror the treatment pass milestones one-to-one
+    require!(
+        milestone_releases.len() == treatment_pass.milestones.len(),
+        ErrorCode::MilestoneScheduleMismatch
+    );
+    for (milestone_release, milestone) in milestone_releases.iter().zip(treatment_pass.milestones.iter()) {
+        require!(
+            milestone_release.milestone_id == milestone.milestone_id &&
+                milestone_release.release_amount == milestone.funding_amount,
+            ErrorCode::MilestoneScheduleMismatch
+        );
+    }
+    
+    // Whoever sets up the escrow cannot also hold an emergency key to it
+    require!(
+        !emergency_releasers.contains(&ctx.accounts.authority.key()),
+        ErrorCode::InvalidEmergencyReleaser
+    );
+    
     let escrow_account = &mut ctx.accounts.escrow_account;
-    escrow_account.bump = *ctx.bumps.get("escrow_account").unwrap();
+    escrow_account.bump = ctx.bumps.escrow_account;
     
     escrow_account.create_escrow(
         treatment_pass_id,
+        ctx.accounts.treatment_pass_account.key(),
+        ctx.accounts.patient.key(),
         milestone_releases,
         ubuntu_health_verification_required,
+        emergency_releasers,
+        emergency_delay_seconds,
     )?;
 
     emit!(MilestoneEscrowCreated {
@@ -329,6 +1088,12 @@ pub fn fund_escrow(
     treatment_pass_id: u64,
     amount: u64,
 ) -> Result<()> {
+    require!(
+        !ctx.accounts.treatment_pass_account.treatment_pass.status.allows_sponsor_refunds(),
+        ErrorCode::TreatmentPassClosed
+    );
+    
+    let escrow_key = ctx.accounts.escrow_account.key();
     let escrow_account = &mut ctx.accounts.escrow_account;
     
     // Transfer tokens from sponsor to escrow vault
@@ -342,8 +1107,22 @@ pub fn fund_escrow(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
 
-    // Update escrow account
+    // Update escrow account and the sponsor's refundable deposit total
     escrow_account.add_funds(amount)?;
+    
+    let escrow_deposit = &mut ctx.accounts.escrow_deposit;
+    if escrow_deposit.deposit.escrow != escrow_key {
+        escrow_deposit.bump = ctx.bumps.escrow_deposit;
+        escrow_deposit.deposit = EscrowDeposit {
+            escrow: escrow_key,
+            depositor: ctx.accounts.sponsor.key(),
+            amount: 0,
+            refunded: false,
+        };
+    }
+    escrow_deposit.deposit.amount = escrow_deposit.deposit.amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
 
     emit!(EscrowFunded {
         treatment_pass_id,
@@ -355,20 +1134,93 @@ pub fn fund_escrow(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn submit_escrow_verification(
+    ctx: Context<SubmitEscrowVerification>,
+    treatment_pass_id: u64,
+    milestone_id: u8,
+    verifier: Pubkey,
+    verification_type: EscrowVerificationType,
+    evidence_hash: Option<String>,
+    attested_at: i64,
+    verification_signature: [u8; 64],
+) -> Result<()> {
+    // Attestations must be dated no later than the cluster clock and no older than the maximum age
+    let now = Clock::get()?.unix_timestamp;
+    let attestation_age = now.checked_sub(attested_at)
+        .ok_or(ErrorCode::AttestationExpired)?;
+    require!(
+        (0..=MAX_ATTESTATION_AGE_SECONDS).contains(&attestation_age),
+        ErrorCode::AttestationExpired
+    );
+
+    // Verifier must have signed the canonical attestation via the Ed25519 precompile
+    let message = attestation_message(
+        &ctx.accounts.escrow_account.key(),
+        milestone_id,
+        &verification_type,
+        &evidence_hash,
+        attested_at,
+    );
+    verify_ed25519_attestation(
+        &ctx.accounts.instructions_sysvar,
+        &verifier,
+        &message,
+        &verification_signature,
+    )?;
+
+    let verifier_kind = ctx.accounts.verifier_entry
+        .as_ref()
+        .filter(|verifier_entry| verifier_entry.entry.is_active)
+        .map(|verifier_entry| &verifier_entry.entry.verifier_kind);
+    let escrow_account = &mut ctx.accounts.escrow_account;
+
+    escrow_account.add_verification(
+        milestone_id,
+        ReceivedVerification {
+            verifier,
+            verification_type: verification_type.clone(),
+            verified_at: attested_at,
+            evidence_hash,
+            verification_signature,
+        },
+        verifier_kind,
+    )?;
+
+    let quorum_reached = escrow_account.can_release_milestone(milestone_id)?;
+
+    emit!(EscrowVerificationSubmitted {
+        treatment_pass_id,
+        milestone_id,
+        verifier,
+        submitted_by: ctx.accounts.submitter.key(),
+        verification_type,
+        quorum_reached,
+    });
+
+    Ok(())
+}
+
 pub fn release_milestone_funds(
     ctx: Context<ReleaseMilestoneFunds>,
     treatment_pass_id: u64,
     milestone_id: u8,
 ) -> Result<()> {
+    // Funds of a cancelled or expired pass are refunded to depositors, never released
+    require!(
+        !ctx.accounts.treatment_pass_account.treatment_pass.status.allows_sponsor_refunds(),
+        ErrorCode::TreatmentPassClosed
+    );
+    
     let escrow_account = &mut ctx.accounts.escrow_account;
     
     let release_amount = escrow_account.release_milestone_funds(milestone_id)?;
 
     // Create PDA seeds for escrow vault authority
-    let treatment_pass_id_bytes = treatment_pass_id.to_le_bytes();
+    let treatment_pass_key = escrow_account.escrow.treatment_pass;
     let seeds = &[
-        b"milestone_escrow",
-        treatment_pass_id_bytes.as_ref(),
+        b"milestone_escrow".as_ref(),
+        treatment_pass_key.as_ref(),
         &[escrow_account.bump],
     ];
     let signer = &[&seeds[..]];
@@ -388,13 +1240,181 @@ pub fn release_milestone_funds(
         treatment_pass_id,
         milestone_id,
         release_amount,
-        verifying_provider: ctx.accounts.healthcare_provider.key(),
+        executed_by: ctx.accounts.executor.key(),
         total_released: escrow_account.escrow.released_amount,
     });
 
     Ok(())
 }
 
+pub fn initiate_emergency_release(
+    ctx: Context<InitiateEmergencyRelease>,
+    treatment_pass_id: u64,
+    reason: String,
+) -> Result<()> {
+    let releaser = ctx.accounts.releaser.key();
+    
+    // Only an active registry verifier or a sitting elder who does not represent the patient may initiate
+    let is_active_verifier = ctx.accounts.releaser_entry
+        .as_ref()
+        .map_or(false, |releaser_entry| releaser_entry.entry.is_active);
+    let is_elder = ctx.accounts.elder_council
+        .as_ref()
+        .map_or(false, |elder_council| elder_council.council.is_member(&releaser));
+    require!(is_active_verifier || is_elder, ErrorCode::EmergencyReleaserNotAccredited);
+    require!(
+        ctx.accounts.releaser_representative.data_is_empty(),
+        ErrorCode::InvalidEmergencyReleaser
+    );
+    
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let clock = Clock::get()?;
+
+    escrow_account.initiate_emergency_release(
+        ctx.accounts.releaser.key(),
+        reason.clone(),
+        clock.unix_timestamp,
+    )?;
+
+    let executable_at = clock.unix_timestamp
+        .checked_add(escrow_account.escrow.emergency_conditions.emergency_delay_seconds)
+        .ok_or(ErrorCode::Overflow)?;
+
+    emit!(EmergencyReleaseInitiated {
+        treatment_pass_id,
+        initiated_by: ctx.accounts.releaser.key(),
+        reason,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+pub fn cancel_emergency_release(
+    ctx: Context<CancelEmergencyRelease>,
+    treatment_pass_id: u64,
+) -> Result<()> {
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let authority_acts_for_patient = acts_for_patient(
+        &ctx.accounts.authority.key(),
+        &escrow_account.escrow.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageTreatment,
+    )?;
+
+    let cancelled = escrow_account.cancel_emergency_release(ctx.accounts.authority.key(), authority_acts_for_patient)?;
+
+    emit!(EmergencyReleaseCancelVote {
+        treatment_pass_id,
+        voter: ctx.accounts.authority.key(),
+        cancel_approvals: escrow_account.escrow.emergency_conditions.cancel_approvals.len() as u8,
+        cancelled,
+    });
+
+    Ok(())
+}
+
+pub fn execute_emergency_release(
+    ctx: Context<ExecuteEmergencyRelease>,
+    treatment_pass_id: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.treatment_pass_account.treatment_pass.status.allows_sponsor_refunds(),
+        ErrorCode::TreatmentPassClosed
+    );
+    
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let authority_acts_for_patient = acts_for_patient(
+        &ctx.accounts.authority.key(),
+        &escrow_account.escrow.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageTreatment,
+    )?;
+
+    let release_amount = escrow_account.execute_emergency_release(
+        ctx.accounts.authority.key(),
+        authority_acts_for_patient,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let treatment_pass_key = escrow_account.escrow.treatment_pass;
+    let seeds = &[
+        b"milestone_escrow".as_ref(),
+        treatment_pass_key.as_ref(),
+        &[escrow_account.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Transfer the remaining escrow balance to the patient
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.escrow_vault.to_account_info(),
+        to: ctx.accounts.patient_token_account.to_account_info(),
+        authority: escrow_account.to_account_info(),
+    };
+    
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, release_amount)?;
+
+    emit!(EmergencyReleaseExecuted {
+        treatment_pass_id,
+        executed_by: ctx.accounts.authority.key(),
+        release_amount,
+        reason: escrow_account.escrow.emergency_conditions.emergency_reason.clone(),
+    });
+
+    Ok(())
+}
+
+/// Returns a depositor's share of the unreleased escrow once the bound pass is cancelled or expired
+pub fn claim_escrow_refund(
+    ctx: Context<ClaimEscrowRefund>,
+    treatment_pass_id: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.treatment_pass_account.treatment_pass.status.allows_sponsor_refunds(),
+        ErrorCode::EscrowRefundNotAvailable
+    );
+    require!(!ctx.accounts.escrow_deposit.deposit.refunded, ErrorCode::EscrowRefundAlreadyClaimed);
+    
+    let escrow_account = &mut ctx.accounts.escrow_account;
+    let refund_amount = escrow_account.deposit_refund(ctx.accounts.escrow_deposit.deposit.amount)?;
+    
+    ctx.accounts.escrow_deposit.deposit.refunded = true;
+    escrow_account.escrow.refunded_amount = escrow_account.escrow.refunded_amount
+        .checked_add(refund_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    if refund_amount > 0 {
+        let treatment_pass_key = escrow_account.escrow.treatment_pass;
+        let seeds = &[
+            b"milestone_escrow".as_ref(),
+            treatment_pass_key.as_ref(),
+            &[escrow_account.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow_vault.to_account_info(),
+            to: ctx.accounts.depositor_token_account.to_account_info(),
+            authority: escrow_account.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, refund_amount)?;
+    }
+    
+    emit!(EscrowDepositRefunded {
+        treatment_pass_id,
+        depositor: ctx.accounts.depositor.key(),
+        refund_amount,
+        total_refunded: escrow_account.escrow.refunded_amount,
+    });
+    
+    Ok(())
+}
+
 /// Events for escrow operations
 #[event]
 pub struct MilestoneEscrowCreated {
@@ -416,10 +1436,52 @@ pub struct MilestoneFundsReleased {
     pub treatment_pass_id: u64,
     pub milestone_id: u8,
     pub release_amount: u64,
-    pub verifying_provider: Pubkey,
+    pub executed_by: Pubkey,
     pub total_released: u64,
 }
 
+#[event]
+pub struct EmergencyReleaseInitiated {
+    pub treatment_pass_id: u64,
+    pub initiated_by: Pubkey,
+    pub reason: String,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct EmergencyReleaseCancelVote {
+    pub treatment_pass_id: u64,
+    pub voter: Pubkey,
+    pub cancel_approvals: u8,
+    pub cancelled: bool,
+}
+
+#[event]
+pub struct EmergencyReleaseExecuted {
+    pub treatment_pass_id: u64,
+    pub executed_by: Pubkey,
+    pub release_amount: u64,
+    pub reason: Option<String>,
+}
+
+#[event]
+pub struct EscrowDepositRefunded {
+    pub treatment_pass_id: u64,
+    pub depositor: Pubkey,
+    pub refund_amount: u64,
+    pub total_refunded: u64,
+}
+
+#[event]
+pub struct EscrowVerificationSubmitted {
+    pub treatment_pass_id: u64,
+    pub milestone_id: u8,
+    pub verifier: Pubkey,
+    pub submitted_by: Pubkey,
+    pub verification_type: EscrowVerificationType,
+    pub quorum_reached: bool,
+}
+
 /// Custom error codes for escrow operations
 #[error_code]
 pub enum ErrorCode {
@@ -435,4 +1497,64 @@ pub enum ErrorCode {
     EmergencyReleaseNotAuthorized,
     #[msg("Invalid verification signature")]
     InvalidVerificationSignature,
+    #[msg("Verification requirement is misconfigured")]
+    InvalidVerificationRequirement,
+    #[msg("Verification type is not required for this milestone")]
+    VerificationTypeNotRequired,
+    #[msg("Signer is not an eligible verifier for this milestone")]
+    UnauthorizedEscrowVerifier,
+    #[msg("Verifier has already verified this milestone")]
+    DuplicateVerifier,
+    #[msg("Evidence hash exceeds maximum length")]
+    EvidenceHashTooLong,
+    #[msg("Verification attestation is too old or timestamped in the future")]
+    AttestationExpired,
+    #[msg("Escrow is no longer active")]
+    EscrowInactive,
+    #[msg("Too many emergency releasers")]
+    TooManyEmergencyReleasers,
+    #[msg("Emergency release delay is too short")]
+    EmergencyDelayTooShort,
+    #[msg("Emergency release delay is too long")]
+    EmergencyDelayTooLong,
+    #[msg("Emergency release already initiated")]
+    EmergencyReleaseAlreadyInitiated,
+    #[msg("No emergency release has been initiated")]
+    EmergencyReleaseNotInitiated,
+    #[msg("Emergency release delay has not elapsed")]
+    EmergencyDelayNotElapsed,
+    #[msg("Emergency release reason exceeds maximum length")]
+    EmergencyReasonTooLong,
+    #[msg("Releaser has already voted to cancel")]
+    DuplicateCancelApproval,
+    #[msg("Treatment pass does not match this escrow")]
+    TreatmentPassMismatch,
+    #[msg("Milestone releases do not match the treatment pass milestones")]
+    MilestoneScheduleMismatch,
+    #[msg("Token account does not belong to the patient")]
+    InvalidPatientTokenAccount,
+    #[msg("Only the patient or their representative can create the escrow")]
+    UnauthorizedEscrowCreator,
+    #[msg("Every milestone release needs at least one mandatory verification requirement")]
+    MissingMandatoryVerification,
+    #[msg("The patient cannot verify their own milestone")]
+    PatientCannotVerify,
+    #[msg("Verifier is not an active registry verifier accredited for this verification type")]
+    UnregisteredEscrowVerifier,
+    #[msg("Emergency releasers cannot be the patient, their representative or the escrow's creator")]
+    InvalidEmergencyReleaser,
+    #[msg("Emergency releaser is listed more than once")]
+    DuplicateEmergencyReleaser,
+    #[msg("Emergency releaser is neither an active registry verifier nor a council elder")]
+    EmergencyReleaserNotAccredited,
+    #[msg("Treatment pass is cancelled or expired")]
+    TreatmentPassClosed,
+    #[msg("Escrow refunds are only available for cancelled or expired treatment passes")]
+    EscrowRefundNotAvailable,
+    #[msg("Escrow deposit already refunded")]
+    EscrowRefundAlreadyClaimed,
+    #[msg("Refund token account does not belong to the depositor")]
+    InvalidRefundAccount,
+    #[msg("Overflow occurred")]
+    Overflow,
 }
@@ -0,0 +1,618 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::treatment_pass_nft::{
+    MilestoneStatus, SponsorContribution, SponsorContributionAccount, TreatmentPass, TreatmentPassAccount,
+    TreatmentPassStatus,
+};
+use crate::elder_council::ElderCouncilAccount;
+
+/// Overdue milestone handling
+/// A permissionless crank flags milestones past their expected completion; once the pass's grace
+/// period has elapsed, sponsors and elder council members vote on how to resolve the stall
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OverdueMilestoneReview {
+    /// Treatment pass the milestone belongs to
+    pub treatment_pass: Pubkey,
+    /// Overdue milestone under review
+    pub milestone_id: u8,
+    /// Overdue round of the milestone this review belongs to
+    pub round: u8,
+    /// When the milestone was flagged overdue
+    pub flagged_at: i64,
+    /// When voting on a resolution opens
+    pub voting_opens_at: i64,
+    /// Votes gathered for each proposed resolution
+    pub tallies: Vec<ResolutionTally>,
+    /// Whether a resolution has been carried out
+    pub resolved: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum OverdueResolution {
+    /// Give the care team more time
+    ExtendDeadline { new_expected_completion: i64 },
+    /// Move the milestone's funding and pledges to another milestone
+    ReallocateFunds { target_milestone_id: u8 },
+    /// Mark the milestone failed so sponsors can reclaim its earmarked funds
+    MarkFailed,
+}
+
+impl OverdueResolution {
+    pub const MAX_SIZE: usize = 1 + // variant
+        8; // largest payload (new_expected_completion)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ResolutionTally {
+    /// Proposed resolution
+    pub resolution: OverdueResolution,
+    /// Sum of the voting sponsors' contributions
+    pub sponsor_weight: u64,
+    /// Number of elder council members in favour
+    pub elder_votes: u8,
+}
+
+impl ResolutionTally {
+    pub const SIZE: usize = OverdueResolution::MAX_SIZE + // resolution
+        8 + // sponsor_weight
+        1; // elder_votes
+}
+
+/// Maximum number of distinct resolutions that can be proposed for one overdue milestone
+pub const MAX_RESOLUTION_OPTIONS: usize = 8;
+
+#[account]
+pub struct OverdueMilestoneReviewAccount {
+    pub review: OverdueMilestoneReview,
+    pub bump: u8,
+}
+
+impl OverdueMilestoneReviewAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        1 + // milestone_id
+        1 + // round
+        8 + // flagged_at
+        8 + // voting_opens_at
+        4 + MAX_RESOLUTION_OPTIONS * ResolutionTally::SIZE + // tallies
+        1 + // resolved
+        1; // bump
+}
+
+/// One voter's ballot on an overdue milestone review
+#[account]
+pub struct OverdueResolutionVoteAccount {
+    pub voter: Pubkey,
+    pub resolution: OverdueResolution,
+    pub sponsor_weight: u64,
+    pub elder_vote: bool,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl OverdueResolutionVoteAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // voter
+        OverdueResolution::MAX_SIZE + // resolution
+        8 + // sponsor_weight
+        1 + // elder_vote
+        8 + // voted_at
+        1; // bump
+}
+
+/// Instructions for overdue milestone handling
+#[derive(Accounts)]
+#[instruction(milestone_id: u8)]
+pub struct FlagOverdueMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        init,
+        payer = cranker,
+        space = OverdueMilestoneReviewAccount::LEN,
+        seeds = [
+            b"overdue_review",
+            treatment_pass_account.key().as_ref(),
+            &[milestone_id, treatment_pass_account.treatment_pass.milestone_overdue_round(milestone_id)]
+        ],
+        bump
+    )]
+    pub overdue_review: Account<'info, OverdueMilestoneReviewAccount>,
+    
+    /// Anyone may flag an overdue milestone and pays for the review account
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_id: u8, resolution: OverdueResolution, identity_salt: Option<[u8; 32]>)]
+pub struct VoteOverdueResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"overdue_review",
+            treatment_pass_account.key().as_ref(),
+            &[milestone_id, overdue_review.review.round]
+        ],
+        bump = overdue_review.bump
+    )]
+    pub overdue_review: Account<'info, OverdueMilestoneReviewAccount>,
+    
+    /// Ballot record; its existence prevents a second vote from the same voter
+    #[account(
+        init,
+        payer = voter,
+        space = OverdueResolutionVoteAccount::LEN,
+        seeds = [b"overdue_vote", overdue_review.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, OverdueResolutionVoteAccount>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    /// Voter's contribution to the pass, when voting as a sponsor
+    #[account(
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &voter.key(), identity_salt).as_ref()
+        ],
+        bump = voter_contribution.bump
+    )]
+    pub voter_contribution: Option<Account<'info, SponsorContributionAccount>>,
+    
+    /// Elder council, when voting as a council member
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Option<Account<'info, ElderCouncilAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_id: u8, identity_salt: Option<[u8; 32]>)]
+pub struct ClaimFailedMilestoneRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), identity_salt).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    pub sponsor: Signer<'info>,
+    
+    /// Escrow account holding the sponsored funds
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    /// Sponsor's token account to receive the refund
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::InvalidRefundAccount,
+        constraint = sponsor_token_account.mint == escrow_account.mint @ ErrorCode::InvalidRefundAccount
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Overdue milestone instruction implementations
+/// Permissionless crank that flags a milestone whose expected completion has passed
+pub fn flag_overdue_milestone(ctx: Context<FlagOverdueMilestone>, milestone_id: u8) -> Result<()> {
+    let clock = Clock::get()?;
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    require!(
+        treatment_pass.status == TreatmentPassStatus::TreatmentInProgress,
+        ErrorCode::TreatmentNotActive
+    );
+    
+    let voting_opens_at = clock.unix_timestamp.checked_add(treatment_pass.overdue_grace_period_seconds)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    let milestone = treatment_pass.milestones.iter_mut()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    require!(
+        matches!(
+            milestone.completion_status,
+            MilestoneStatus::NotStarted | MilestoneStatus::InProgress
+        ) && clock.unix_timestamp > milestone.expected_completion,
+        ErrorCode::MilestoneNotOverdue
+    );
+    require!(milestone.overdue_since.is_none(), ErrorCode::MilestoneAlreadyFlagged);
+    
+    milestone.overdue_since = Some(clock.unix_timestamp);
+    let expected_completion = milestone.expected_completion;
+    let round = milestone.overdue_round;
+    
+    let overdue_review = &mut ctx.accounts.overdue_review;
+    overdue_review.bump = ctx.bumps.overdue_review;
+    overdue_review.review = OverdueMilestoneReview {
+        treatment_pass: treatment_pass_key,
+        milestone_id,
+        round,
+        flagged_at: clock.unix_timestamp,
+        voting_opens_at,
+        tallies: Vec::new(),
+        resolved: false,
+    };
+    
+    emit!(MilestoneOverdue {
+        pass_id: treatment_pass.pass_id,
+        milestone_id,
+        expected_completion,
+        flagged_at: clock.unix_timestamp,
+        voting_opens_at,
+    });
+    
+    Ok(())
+}
+
+/// Sponsor or elder council member votes on how to resolve an overdue milestone
+pub fn vote_overdue_resolution(
+    ctx: Context<VoteOverdueResolution>,
+    milestone_id: u8,
+    resolution: OverdueResolution,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let voter = ctx.accounts.voter.key();
+    
+    // Sponsors vote with their outstanding contribution, elder council members by head count
+    let sponsor_weight = ctx.accounts.voter_contribution.as_ref().map_or(0, |voter_contribution| {
+        if voter_contribution.contribution.refund_claimed {
+            0
+        } else {
+            voter_contribution.contribution.amount_contributed
+        }
+    });
+    let elder_vote = ctx.accounts.elder_council.as_ref()
+        .map_or(false, |elder_council| elder_council.council.is_member(&voter));
+    require!(sponsor_weight > 0 || elder_vote, ErrorCode::UnauthorizedResolutionVoter);
+    
+    let review = &mut ctx.accounts.overdue_review.review;
+    require!(!review.resolved, ErrorCode::ReviewAlreadyResolved);
+    require!(
+        clock.unix_timestamp >= review.voting_opens_at,
+        ErrorCode::GracePeriodNotElapsed
+    );
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    require!(
+        treatment_pass.status == TreatmentPassStatus::TreatmentInProgress,
+        ErrorCode::TreatmentNotActive
+    );
+    validate_resolution(treatment_pass, milestone_id, &resolution, clock.unix_timestamp)?;
+    
+    // Add the ballot to the tally for this resolution
+    let tally_index = match review.tallies.iter().position(|t| t.resolution == resolution) {
+        Some(index) => index,
+        None => {
+            require!(
+                review.tallies.len() < MAX_RESOLUTION_OPTIONS,
+                ErrorCode::TooManyResolutionOptions
+            );
+            review.tallies.push(ResolutionTally {
+                resolution: resolution.clone(),
+                sponsor_weight: 0,
+                elder_votes: 0,
+            });
+            review.tallies.len() - 1
+        }
+    };
+    let tally = &mut review.tallies[tally_index];
+    tally.sponsor_weight = tally.sponsor_weight.checked_add(sponsor_weight)
+        .ok_or(ErrorCode::Overflow)?;
+    if elder_vote {
+        tally.elder_votes = tally.elder_votes.checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    
+    // Carried by sponsors holding a majority of the funding or by an elder council quorum
+    let carried = (tally.sponsor_weight as u128) * 2 > treatment_pass.current_funding as u128 ||
+        ctx.accounts.elder_council.as_ref().map_or(false, |elder_council| {
+            tally.elder_votes >= elder_council.council.approval_quorum
+        });
+    
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.voter = voter;
+    vote_record.resolution = resolution.clone();
+    vote_record.sponsor_weight = sponsor_weight;
+    vote_record.elder_vote = elder_vote;
+    vote_record.voted_at = clock.unix_timestamp;
+    vote_record.bump = ctx.bumps.vote_record;
+    
+    emit!(OverdueResolutionVoteCast {
+        pass_id: treatment_pass.pass_id,
+        milestone_id,
+        voter,
+        resolution: resolution.clone(),
+        sponsor_weight,
+        elder_vote,
+    });
+    
+    if carried {
+        review.resolved = true;
+        apply_resolution(treatment_pass, milestone_id, &resolution)?;
+    
+        emit!(OverdueMilestoneResolved {
+            pass_id: treatment_pass.pass_id,
+            milestone_id,
+            resolution,
+            resolved_at: clock.unix_timestamp,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Sponsor reclaims the amount they earmarked for a milestone that was marked failed, and once
+/// treatment has completed, their share of the general pool that failed milestones never drew on
+pub fn claim_failed_milestone_refund(
+    ctx: Context<ClaimFailedMilestoneRefund>,
+    milestone_id: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+    
+    require!(
+        treatment_pass.milestones.iter()
+            .find(|m| m.milestone_id == milestone_id)
+            .ok_or(ErrorCode::MilestoneNotFound)?
+            .completion_status == MilestoneStatus::Failed,
+        ErrorCode::MilestoneNotFailed
+    );
+    
+    // Earmarks made to earlier milestones whose funds were reallocated here are refunded too
+    let mut refund_amount: u64 = 0;
+    for allocation in contribution.milestone_allocations.iter_mut().filter(|a| !a.refunded) {
+        if treatment_pass.earmark_destination(allocation.milestone_id) == Some(milestone_id) {
+            refund_amount = refund_amount.checked_add(allocation.amount)
+                .ok_or(ErrorCode::Overflow)?;
+            allocation.refunded = true;
+        }
+    }
+    
+    // Refunding an earmark lowers the contribution and the pass totals alike, so the pool share is unaffected
+    let mut pool_share: u64 = 0;
+    if treatment_pass.status == TreatmentPassStatus::TreatmentCompleted && !contribution.refund_claimed {
+        pool_share = treatment_pass.pool_refund_share(contribution)?;
+        contribution.refund_claimed = true;
+    }
+    require!(refund_amount > 0 || pool_share > 0, ErrorCode::NoRefundableEarmark);
+    
+    // Refunded earmarks leave the pass totals so later pro-rata refunds stay consistent
+    let milestone = treatment_pass.milestones.iter_mut()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    milestone.pledged_amount = milestone.pledged_amount.checked_sub(refund_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    contribution.amount_contributed = contribution.amount_contributed.checked_sub(refund_amount)
+        .ok_or(ErrorCode::Overflow)?;
+    treatment_pass.record_refund(refund_amount)?;
+    let pass_id = treatment_pass.pass_id;
+    
+    let treatment_pass_account = &ctx.accounts.treatment_pass_account;
+    let pass_id_bytes = pass_id.to_le_bytes();
+    let seeds = &[
+        b"treatment_pass".as_ref(),
+        treatment_pass_account.treatment_pass.patient.as_ref(),
+        pass_id_bytes.as_ref(),
+        &[treatment_pass_account.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer the earmarked amount and any pool share from escrow back to the sponsor
+    let cpi_accounts = token::Transfer {
+        from: ctx.accounts.escrow_account.to_account_info(),
+        to: ctx.accounts.sponsor_token_account.to_account_info(),
+        authority: treatment_pass_account.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(cpi_ctx, refund_amount.checked_add(pool_share).ok_or(ErrorCode::Overflow)?)?;
+    
+    emit!(FailedMilestoneRefundClaimed {
+        pass_id,
+        milestone_id,
+        sponsor: ctx.accounts.sponsor.key(),
+        refund_amount,
+        pool_share,
+        claimed_at: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+fn validate_resolution(
+    treatment_pass: &TreatmentPass,
+    milestone_id: u8,
+    resolution: &OverdueResolution,
+    now: i64,
+) -> Result<()> {
+    let milestone = treatment_pass.milestones.iter()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    require!(
+        milestone.overdue_since.is_some() &&
+            matches!(
+                milestone.completion_status,
+                MilestoneStatus::NotStarted | MilestoneStatus::InProgress
+            ),
+        ErrorCode::MilestoneNotOverdue
+    );
+    
+    match resolution {
+        OverdueResolution::ExtendDeadline { new_expected_completion } => {
+            require!(*new_expected_completion > now, ErrorCode::InvalidExtension);
+        }
+        OverdueResolution::ReallocateFunds { target_milestone_id } => {
+            let target = treatment_pass.milestones.iter()
+                .find(|m| m.milestone_id == *target_milestone_id)
+                .ok_or(ErrorCode::InvalidReallocationTarget)?;
+            require!(
+                *target_milestone_id != milestone_id && !target.completion_status.is_closed(),
+                ErrorCode::InvalidReallocationTarget
+            );
+        }
+        OverdueResolution::MarkFailed => {}
+    }
+    
+    Ok(())
+}
+
+fn apply_resolution(
+    treatment_pass: &mut TreatmentPass,
+    milestone_id: u8,
+    resolution: &OverdueResolution,
+) -> Result<()> {
+    let milestone = treatment_pass.milestones.iter_mut()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    milestone.overdue_since = None;
+    
+    match resolution {
+        OverdueResolution::ExtendDeadline { new_expected_completion } => {
+            milestone.expected_completion = *new_expected_completion;
+            milestone.overdue_round = milestone.overdue_round.checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        OverdueResolution::ReallocateFunds { target_milestone_id } => {
+            milestone.completion_status = MilestoneStatus::Reallocated;
+            milestone.reallocated_to = Some(*target_milestone_id);
+            let funding_amount = std::mem::take(&mut milestone.funding_amount);
+            let pledged_amount = std::mem::take(&mut milestone.pledged_amount);
+    
+            let target = treatment_pass.milestones.iter_mut()
+                .find(|m| m.milestone_id == *target_milestone_id)
+                .ok_or(ErrorCode::InvalidReallocationTarget)?;
+            target.funding_amount = target.funding_amount.checked_add(funding_amount)
+                .ok_or(ErrorCode::Overflow)?;
+            target.pledged_amount = target.pledged_amount.checked_add(pledged_amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        OverdueResolution::MarkFailed => {
+            milestone.completion_status = MilestoneStatus::Failed;
+        }
+    }
+    
+    // Close out the treatment once no milestone remains open
+    if treatment_pass.milestones.iter().all(|m| m.completion_status.is_closed()) {
+        let any_verified = treatment_pass.milestones.iter()
+            .any(|m| m.completion_status == MilestoneStatus::Verified);
+        treatment_pass.transition_to(if any_verified {
+            TreatmentPassStatus::TreatmentCompleted
+        } else {
+            TreatmentPassStatus::TreatmentCancelled
+        })?;
+    }
+    
+    Ok(())
+}
+
+/// Events for overdue milestone handling
+#[event]
+pub struct MilestoneOverdue {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub expected_completion: i64,
+    pub flagged_at: i64,
+    pub voting_opens_at: i64,
+}
+
+#[event]
+pub struct OverdueResolutionVoteCast {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub voter: Pubkey,
+    pub resolution: OverdueResolution,
+    pub sponsor_weight: u64,
+    pub elder_vote: bool,
+}
+
+#[event]
+pub struct OverdueMilestoneResolved {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub resolution: OverdueResolution,
+    pub resolved_at: i64,
+}
+
+#[event]
+pub struct FailedMilestoneRefundClaimed {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub sponsor: Pubkey,
+    pub refund_amount: u64,
+    pub pool_share: u64,
+    pub claimed_at: i64,
+}
+
+/// Custom error codes for overdue milestone handling
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Milestone not found")]
+    MilestoneNotFound,
+    #[msg("Treatment is not in progress")]
+    TreatmentNotActive,
+    #[msg("Milestone is not overdue")]
+    MilestoneNotOverdue,
+    #[msg("Milestone is already flagged as overdue")]
+    MilestoneAlreadyFlagged,
+    #[msg("Overdue grace period has not elapsed")]
+    GracePeriodNotElapsed,
+    #[msg("Overdue review already resolved")]
+    ReviewAlreadyResolved,
+    #[msg("Only sponsors of this pass and elder council members may vote")]
+    UnauthorizedResolutionVoter,
+    #[msg("Too many distinct resolutions proposed")]
+    TooManyResolutionOptions,
+    #[msg("Extended deadline must be in the future")]
+    InvalidExtension,
+    #[msg("Reallocation target must be another open milestone")]
+    InvalidReallocationTarget,
+    #[msg("Milestone has not been marked failed")]
+    MilestoneNotFailed,
+    #[msg("Nothing left to refund for this milestone")]
+    NoRefundableEarmark,
+    #[msg("Escrow account is not controlled by this treatment pass")]
+    InvalidEscrowAccount,
+    #[msg("Refund token account does not belong to the sponsor")]
+    InvalidRefundAccount,
+}
@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+
+/// Patient representatives
+/// A patient may appoint family members, caseworkers or caregivers to act on their behalf; each appointment is a
+/// PDA keyed by patient and representative, so instructions accept it alongside the representative's signature.
+/// Appointments are limited to the scopes the patient grants and may carry an expiry; the recovery-logs and
+/// treatment-passes programs read the same PDA to accept representatives
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PatientRepresentative {
+    /// Patient being represented
+    pub patient: Pubkey,
+    /// Wallet acting for the patient
+    pub representative: Pubkey,
+    /// Representative's relationship to the patient
+    pub role: RepresentativeRole,
+    /// When the patient made the appointment
+    pub appointed_at: i64,
+    /// Bitmask of the `RepresentativeScope`s granted
+    pub scopes: u8,
+    /// When the appointment lapses, if it is temporary
+    pub expires_at: Option<i64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum RepresentativeRole {
+    /// Relative or household member
+    FamilyMember,
+    /// Social worker or patient advocate
+    Caseworker,
+    /// Professional or volunteer carer
+    Caregiver,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RepresentativeScope {
+    /// Create treatment passes and manage their status, diaspora details and sponsor messages
+    ManageTreatment,
+    /// Report, confirm and amend treatment milestones
+    ManageMilestones,
+    /// Keep recovery logs, milestone summaries and outcome reports
+    LogRecovery,
+    /// Receive released treatment funds on the patient's behalf
+    ReceiveFunds,
+}
+
+impl RepresentativeScope {
+    pub fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+impl PatientRepresentative {
+    /// Whether the appointment lets `representative` act for `patient` within `scope` at `now`
+    pub fn permits(&self, patient: &Pubkey, representative: &Pubkey, scope: RepresentativeScope, now: i64) -> bool {
+        self.patient == *patient &&
+            self.representative == *representative &&
+            self.scopes & scope.bit() != 0 &&
+            self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+}
+
+#[account]
+pub struct PatientRepresentativeAccount {
+    pub appointment: PatientRepresentative,
+    pub bump: u8,
+}
+
+impl PatientRepresentativeAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // patient
+        32 + // representative
+        1 + // role
+        8 + // appointed_at
+        1 + // scopes
+        1 + 8 + // expires_at
+        1; // bump
+}
+
+/// Whether `signer` is the patient or holds a current appointment covering `scope`
+pub fn acts_for_patient(
+    signer: &Pubkey,
+    patient: &Pubkey,
+    representative: Option<&PatientRepresentativeAccount>,
+    scope: RepresentativeScope,
+) -> Result<bool> {
+    if signer == patient {
+        return Ok(true);
+    }
+    
+    Ok(match representative {
+        Some(representative) => {
+            representative.appointment.permits(patient, signer, scope, Clock::get()?.unix_timestamp)
+        }
+        None => false,
+    })
+}
+
+/// Instructions for patient representatives
+#[derive(Accounts)]
+#[instruction(representative: Pubkey)]
+pub struct AppointPatientRepresentative<'info> {
+    #[account(
+        init,
+        payer = patient,
+        space = PatientRepresentativeAccount::LEN,
+        seeds = [b"patient_representative", patient.key().as_ref(), representative.as_ref()],
+        bump
+    )]
+    pub representative_account: Account<'info, PatientRepresentativeAccount>,
+    
+    #[account(mut)]
+    pub patient: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePatientRepresentative<'info> {
+    #[account(
+        mut,
+        seeds = [b"patient_representative", patient.key().as_ref(), representative_account.appointment.representative.as_ref()],
+        bump = representative_account.bump
+    )]
+    pub representative_account: Account<'info, PatientRepresentativeAccount>,
+    
+    pub patient: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokePatientRepresentative<'info> {
+    #[account(
+        mut,
+        close = patient,
+        seeds = [b"patient_representative", patient.key().as_ref(), representative_account.appointment.representative.as_ref()],
+        bump = representative_account.bump
+    )]
+    pub representative_account: Account<'info, PatientRepresentativeAccount>,
+    
+    #[account(mut)]
+    pub patient: Signer<'info>,
+}
+
+/// Checks a grant of scopes and expiry, returning the scope bitmask
+fn validate_grant(scopes: &[RepresentativeScope], expires_at: Option<i64>, now: i64) -> Result<u8> {
+    require!(!scopes.is_empty(), ErrorCode::NoRepresentativeScopes);
+    require!(
+        expires_at.map_or(true, |expires_at| expires_at > now),
+        ErrorCode::InvalidRepresentativeExpiry
+    );
+    
+    Ok(scopes.iter().fold(0, |mask, scope| mask | scope.bit()))
+}
+
+pub fn appoint_patient_representative(
+    ctx: Context<AppointPatientRepresentative>,
+    representative: Pubkey,
+    role: RepresentativeRole,
+    scopes: Vec<RepresentativeScope>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let patient = ctx.accounts.patient.key();
+    let now = Clock::get()?.unix_timestamp;
+    require_keys_neq!(representative, patient, ErrorCode::SelfRepresentation);
+    let scope_mask = validate_grant(&scopes, expires_at, now)?;
+    
+    let representative_account = &mut ctx.accounts.representative_account;
+    representative_account.bump = ctx.bumps.representative_account;
+    representative_account.appointment = PatientRepresentative {
+        patient,
+        representative,
+        role: role.clone(),
+        appointed_at: now,
+        scopes: scope_mask,
+        expires_at,
+    };
+    
+    emit!(PatientRepresentativeAppointed {
+        patient,
+        representative,
+        role,
+        scopes: scope_mask,
+        expires_at,
+    });
+    
+    Ok(())
+}
+
+/// Patient changes the scopes or expiry of an existing appointment
+pub fn update_patient_representative(
+    ctx: Context<UpdatePatientRepresentative>,
+    scopes: Vec<RepresentativeScope>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let scope_mask = validate_grant(&scopes, expires_at, Clock::get()?.unix_timestamp)?;
+    
+    let appointment = &mut ctx.accounts.representative_account.appointment;
+    appointment.scopes = scope_mask;
+    appointment.expires_at = expires_at;
+    
+    emit!(PatientRepresentativeUpdated {
+        patient: appointment.patient,
+        representative: appointment.representative,
+        scopes: scope_mask,
+        expires_at,
+    });
+    
+    Ok(())
+}
+
+pub fn revoke_patient_representative(ctx: Context<RevokePatientRepresentative>) -> Result<()> {
+    emit!(PatientRepresentativeRevoked {
+        patient: ctx.accounts.patient.key(),
+        representative: ctx.accounts.representative_account.appointment.representative,
+    });
+    
+    Ok(())
+}
+
+/// Events for patient representatives
+#[event]
+pub struct PatientRepresentativeAppointed {
+    pub patient: Pubkey,
+    pub representative: Pubkey,
+    pub role: RepresentativeRole,
+    pub scopes: u8,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct PatientRepresentativeUpdated {
+    pub patient: Pubkey,
+    pub representative: Pubkey,
+    pub scopes: u8,
+    pub expires_at: Option<i64>,
+}
+
+#[event]
+pub struct PatientRepresentativeRevoked {
+    pub patient: Pubkey,
+    pub representative: Pubkey,
+}
+
+/// Custom error codes for patient representatives
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Patients cannot appoint themselves as their own representative")]
+    SelfRepresentation,
+    #[msg("Representative must be granted at least one scope")]
+    NoRepresentativeScopes,
+    #[msg("Representative appointment must expire in the future")]
+    InvalidRepresentativeExpiry,
+}
@@ -1,8 +1,20 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use anchor_spl::token::{self, Token, TokenAccount, Mint};
 
+use crate::community_pool::CommunityOverflowPoolAccount;
+use crate::elder_council::{ElderCouncil, ElderCouncilAccount};
+use crate::endorsement_stake::{EndorsementStake, EndorsementStakeAccount, EndorsementStakeLocked, ENDORSEMENT_STAKE_LIVES};
+use crate::lives_token::LivesTokenConfigAccount;
+use crate::member_registry::{EndorserReputationAccount, UbuntuMemberProfile};
+use crate::milestone_amendments::{AmendmentRecord, MAX_AMENDMENT_HISTORY};
+use crate::patient_representatives::{acts_for_patient, PatientRepresentativeAccount, RepresentativeScope};
+use crate::verifier_registry::{VerifierEntryAccount, VerifierKind, VerifierRegistryAccount};
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub struct TreatmentPass {
+    /// Account layout version, see `TREATMENT_PASS_VERSION`
+    pub version: u8,
     /// Unique identifier for this treatment pass
     pub pass_id: u64,
     /// Patient's public key
@@ -17,18 +29,56 @@ pub struct TreatmentPass {
     pub funding_target: u64,
     /// Current amount funded
     pub current_funding: u64,
-    /// Percentage funded (0-100)
-    pub funding_percentage: u8,
+    /// Token account holding the sponsored funds, owned by the treatment pass PDA
+    pub escrow: Pubkey,
+    /// Portion of the target funded, in basis points (0-10000)
+    pub funding_basis_points: u16,
+    /// How sponsorships beyond the funding target are handled
+    pub overfunding_policy: OverfundingPolicy,
     /// List of treatment milestones
     pub milestones: Vec<TreatmentMilestone>,
-    /// List of sponsors
-    pub sponsors: Vec<Sponsor>,
+    /// Number of distinct sponsors; each sponsor's contribution lives in its own PDA
+    pub sponsor_count: u32,
+    /// Sponsored funds not earmarked to any milestone and not yet released
+    pub unearmarked_funding: u64,
     /// Timestamp when treatment pass was created
     pub created_at: i64,
     /// Expected treatment duration in days
     pub treatment_duration_days: u16,
+    /// Unix timestamp after which no further sponsorships are accepted
+    pub funding_deadline: i64,
+    /// Time after a milestone is flagged overdue before sponsors and elders may vote on it
+    pub overdue_grace_period_seconds: i64,
+    /// Share of sponsor funding, in basis points, needed to approve a milestone amendment
+    pub amendment_approval_basis_points: u16,
+    /// Number of milestone amendments proposed so far
+    pub amendment_count: u16,
+    /// Amendment currently awaiting sponsor consent
+    pub pending_amendment: Option<u16>,
+    /// Outcome of every resolved milestone amendment
+    pub amendment_history: Vec<AmendmentRecord>,
+    /// Accredited provider delivering the treatment; only they may start it
+    pub treating_provider: Option<Pubkey>,
     /// Current status of the treatment pass
     pub status: TreatmentPassStatus,
+    /// Whether governance or the elder council marked the pass fraudulent
+    pub flagged_fraudulent: bool,
+    /// Current elder council review round; advances each time the council reaches a decision
+    pub elder_review_round: u16,
+    
+    // Ubuntu Community Governance
+    /// Elder-verified community member who endorsed this pass, if any
+    pub ubuntu_community_endorsement: Option<Pubkey>,
+    /// Whether the elder council has approved the treatment
+    pub elder_council_approval: bool,
+    /// Elder whose vote carried the council's approval
+    pub approving_elder: Option<Pubkey>,
+    /// Traditional healing practice integrated into the treatment plan
+    pub traditional_healing_component: Option<TraditionalHealing>,
+    /// Patient's connection to the diaspora community
+    pub diaspora_connection: Option<DiasporaInfo>,
+    /// Alignment with Ubuntu principles as assessed by the community (0-100)
+    pub ubuntu_alignment_score: u8,
     
     // Ubuntu Health Integration
     /// Ubuntu Health verification status
@@ -45,6 +95,8 @@ pub struct TreatmentMilestone {
     pub description: String,
     /// Amount of funding released upon completion
     pub funding_amount: u64,
+    /// Amount sponsors have earmarked specifically for this milestone
+    pub pledged_amount: u64,
     /// Type of verification required
     pub verification_required: VerificationType,
     /// Current completion status
@@ -53,39 +105,199 @@ pub struct TreatmentMilestone {
     pub expected_completion: i64,
     /// Actual completion timestamp
     pub completion_timestamp: Option<i64>,
+    /// When the milestone was flagged as overdue, if it currently is
+    pub overdue_since: Option<i64>,
+    /// Overdue review round, advanced each time an overdue deadline is extended
+    pub overdue_round: u8,
+    /// Milestone that took over this milestone's funding and earmarks, once reallocated
+    pub reallocated_to: Option<u8>,
     /// Healthcare provider who verified completion
     pub verifying_provider: Option<Pubkey>,
+    /// Evidence hash submitted by the patient or care team when reporting completion
+    pub completion_evidence: Option<String>,
     /// Verification evidence (IPFS hash)
     pub verification_evidence: Option<String>,
     
     // Verification Details
     /// Cryptographic proof of milestone completion
     pub cryptographic_proof: Option<String>,
+    
+    // Ubuntu Community Validation
+    /// Elder review record behind the council decision covering this milestone
+    pub elder_council_review: Option<Pubkey>,
+    /// Whether the community confirmed completion alongside the verifier
+    pub ubuntu_community_validation: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub struct Sponsor {
-    /// Sponsor's wallet public key
+pub struct SponsorContribution {
+    /// Treatment pass account this contribution was made to
+    pub treatment_pass: Pubkey,
+    /// Sponsor's wallet public key; left unset while the sponsor is anonymous
     pub wallet: Pubkey,
     /// Total amount contributed by this sponsor
     pub amount_contributed: u64,
-    /// Specific milestones sponsored by this sponsor
-    pub sponsored_milestones: Vec<u8>,
+    /// Portions of this sponsor's contribution earmarked to specific milestones
+    pub milestone_allocations: Vec<MilestoneAllocation>,
     /// Timestamp of first sponsorship
     pub first_sponsored_at: i64,
     /// Timestamp of most recent sponsorship
     pub last_sponsored_at: i64,
-    /// Sponsor's message to the patient
+    /// Sponsor's message to the patient; cleared if the message is hidden
     pub sponsor_message: Option<String>,
+    /// Hash of the latest message, kept after hiding for auditability
+    pub sponsor_message_hash: Option<[u8; 32]>,
+    /// Whether a message was hidden by the patient or a moderator; the record accepts no new messages after that
+    pub sponsor_message_hidden: bool,
     /// Whether sponsor wants to remain anonymous
     pub anonymous: bool,
+    /// Hash of (treatment pass, wallet, salt) standing in for the wallet of an anonymous sponsor
+    pub identity_commitment: Option<[u8; 32]>,
+    /// Whether the sponsor has claimed their refund from a cancelled or expired pass
+    pub refund_claimed: bool,
+    /// Whether the sponsor has claimed their share of a slashed endorsement stake
+    pub slashed_stake_claimed: bool,
     
     // Ubuntu Health Integration
     /// Whether sponsor is an Ubuntu Health member
     pub ubuntu_health_member: bool,
+    /// Whether sponsor is a registered Ubuntu community member
+    pub ubuntu_community_member: bool,
+    /// Sponsor's standing within the Ubuntu community
+    pub community_reputation: u32,
+    /// Sponsor's own diaspora connection, if they share one
+    pub diaspora_connection: Option<DiasporaInfo>,
+    /// Traditional healing knowledge the sponsor offered to the patient
+    pub traditional_healing_knowledge: Option<String>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct MilestoneAllocation {
+    /// Milestone the funds are earmarked for
+    pub milestone_id: u8,
+    /// Amount earmarked for the milestone
+    pub amount: u64,
+    /// Whether the earmark was refunded, after the milestone failed or the pass was cancelled
+    pub refunded: bool,
+}
+
+impl MilestoneAllocation {
+    pub const SIZE: usize = 1 + // milestone_id
+        8 + // amount
+        1; // refunded
+}
+
+impl SponsorContribution {
+    /// Merges newly earmarked amounts into this sponsor's per-milestone allocations
+    pub fn add_allocations(&mut self, allocations: &[MilestoneAllocation]) -> Result<()> {
+        for allocation in allocations {
+            match self.milestone_allocations.iter_mut()
+                .find(|a| a.milestone_id == allocation.milestone_id) {
+                Some(existing) => {
+                    existing.amount = existing.amount.checked_add(allocation.amount)
+                        .ok_or(ErrorCode::Overflow)?;
+                }
+                None => self.milestone_allocations.push(MilestoneAllocation {
+                    refunded: false,
+                    ..allocation.clone()
+                }),
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct TraditionalHealing {
+    /// Traditional practice being integrated (e.g., "Herbal medicine", "Ubuntu healing circle")
+    pub practice_type: String,
+    /// Traditional healer guiding the practice, if registered
+    pub traditional_healer: Option<Pubkey>,
+    /// How the practice complements the medical treatment plan
+    pub integration_plan: String,
+}
+
+impl TraditionalHealing {
+    /// Borsh-serialized size with maximal strings
+    pub const MAX_SIZE: usize = 4 + MAX_HEALING_PRACTICE_LEN + // practice_type
+        1 + 32 + // traditional_healer
+        4 + MAX_INTEGRATION_PLAN_LEN; // integration_plan
+    
+    /// Checks that the practice fits the fixed account allocation
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.practice_type.len() <= MAX_HEALING_PRACTICE_LEN &&
+                self.integration_plan.len() <= MAX_INTEGRATION_PLAN_LEN,
+            ErrorCode::TraditionalHealingTooLong
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct DiasporaInfo {
+    /// Community of origin
+    pub origin_community: String,
+    /// Where the patient or sponsor currently lives
+    pub current_location: String,
+    /// Strength of the connection to the origin community (0-100)
+    pub connection_strength: u8,
+    /// Diaspora members vouching for the connection
+    pub network_contacts: Vec<Pubkey>,
+    /// Whether remittances can be sent through this connection
+    pub remittance_capability: bool,
+}
+
+impl DiasporaInfo {
+    /// Borsh-serialized size with maximal strings and contacts
+    pub const MAX_SIZE: usize = 4 + MAX_COMMUNITY_NAME_LEN + // origin_community
+        4 + MAX_COMMUNITY_NAME_LEN + // current_location
+        1 + // connection_strength
+        4 + MAX_NETWORK_CONTACTS * 32 + // network_contacts
+        1; // remittance_capability
+    
+    /// Checks the connection details are in range and fit the fixed account allocation
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.origin_community.len() <= MAX_COMMUNITY_NAME_LEN &&
+                self.current_location.len() <= MAX_COMMUNITY_NAME_LEN,
+            ErrorCode::CommunityNameTooLong
+        );
+        require!(self.connection_strength <= 100, ErrorCode::InvalidConnectionStrength);
+        require!(
+            self.network_contacts.len() <= MAX_NETWORK_CONTACTS,
+            ErrorCode::TooManyNetworkContacts
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ElderReview {
+    /// Elder who gave the review
+    pub reviewing_elder: Pubkey,
+    /// When the review was recorded
+    pub review_timestamp: i64,
+    /// Elder's decision on the treatment
+    pub decision: ElderDecision,
+    /// Guidance offered to the patient and care team
+    pub elder_guidance: String,
+    /// Traditional wisdom shared with the patient, if any
+    pub traditional_wisdom: Option<String>,
+    /// Community support the elder recommends
+    pub community_support_recommendations: Vec<String>,
 }
 
-// Removed complex Ubuntu governance structures to focus on core healthcare functionality
+impl ElderReview {
+    /// Borsh-serialized size with maximal strings
+    pub const MAX_SIZE: usize = 32 + // reviewing_elder
+        8 + // review_timestamp
+        1 + // decision
+        4 + MAX_ELDER_GUIDANCE_LEN + // elder_guidance
+        1 + 4 + MAX_TRADITIONAL_WISDOM_LEN + // traditional_wisdom
+        4 + MAX_SUPPORT_RECOMMENDATIONS * (4 + MAX_SUPPORT_RECOMMENDATION_LEN); // community_support_recommendations
+}
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum TreatmentPassStatus {
@@ -103,6 +315,82 @@ pub enum TreatmentPassStatus {
     TreatmentPaused,
     /// Treatment cancelled
     TreatmentCancelled,
+    /// Funding deadline passed before the target was reached
+    FundingExpired,
+    /// Funding paused while the elder council reviews the treatment
+    ElderCouncilReview,
+    /// Referred to the wider Ubuntu community for consensus
+    CommunityReview,
+}
+
+impl TreatmentPassStatus {
+    /// Whether the treatment lifecycle allows moving from this status to `next`
+    pub fn can_transition_to(&self, next: &TreatmentPassStatus) -> bool {
+        match self {
+            Self::FundingRequired => matches!(
+                next,
+                Self::PartiallyFunded | Self::FullyFunded | Self::FundingExpired |
+                    Self::TreatmentCancelled | Self::ElderCouncilReview | Self::CommunityReview
+            ),
+            Self::PartiallyFunded => matches!(
+                next,
+                Self::FullyFunded | Self::FundingExpired | Self::TreatmentCancelled |
+                    Self::ElderCouncilReview | Self::CommunityReview
+            ),
+            Self::FullyFunded => matches!(
+                next,
+                Self::TreatmentInProgress | Self::TreatmentCancelled |
+                    Self::ElderCouncilReview | Self::CommunityReview
+            ),
+            Self::TreatmentInProgress => matches!(
+                next,
+                Self::TreatmentPaused | Self::TreatmentCompleted | Self::TreatmentCancelled
+            ),
+            Self::TreatmentPaused => matches!(
+                next,
+                Self::TreatmentInProgress | Self::TreatmentCancelled
+            ),
+            // Reviews resolve back into the funding state matching the amount raised
+            Self::ElderCouncilReview => matches!(
+                next,
+                Self::FundingRequired | Self::PartiallyFunded | Self::FullyFunded |
+                    Self::CommunityReview | Self::TreatmentCancelled
+            ),
+            Self::CommunityReview => matches!(
+                next,
+                Self::FundingRequired | Self::PartiallyFunded | Self::FullyFunded |
+                    Self::ElderCouncilReview | Self::TreatmentCancelled
+            ),
+            // Completed, cancelled and expired passes can never be reopened
+            Self::TreatmentCompleted | Self::TreatmentCancelled | Self::FundingExpired => false,
+        }
+    }
+    
+    /// Whether the pass has reached a final status
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::TreatmentCompleted | Self::TreatmentCancelled | Self::FundingExpired
+        )
+    }
+    
+    /// Whether sponsors may reclaim their unreleased contributions
+    pub fn allows_sponsor_refunds(&self) -> bool {
+        matches!(
+            self,
+            TreatmentPassStatus::TreatmentCancelled | TreatmentPassStatus::FundingExpired
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum OverfundingPolicy {
+    /// Reject sponsorships that would exceed the funding target
+    Reject,
+    /// Accept only the amount still needed to reach the target
+    CapAtTarget,
+    /// Accept the amount still needed and route the surplus to the community overflow pool
+    RouteToCommunityPool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -115,6 +403,10 @@ pub enum VerificationType {
     ThirdPartyEvidence,
     /// Community witness verification
     CommunityWitness,
+    /// Verification by a member of the elder council
+    ElderCouncilVerification,
+    /// Provider verification of a plan the elder council has approved
+    IntegratedVerification,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -129,6 +421,18 @@ pub enum MilestoneStatus {
     Verified,
     /// Milestone failed or cancelled
     Failed,
+    /// Milestone abandoned and its funding moved to another milestone
+    Reallocated,
+}
+
+impl MilestoneStatus {
+    /// Whether the milestone can no longer change
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            MilestoneStatus::Verified | MilestoneStatus::Failed | MilestoneStatus::Reallocated
+        )
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -151,6 +455,7 @@ pub struct TreatmentPassCreated {
     pub pass_id: u64,
     pub patient: Pubkey,
     pub funding_target: u64,
+    pub funding_deadline: i64,
     pub treatment_category: String,
     pub ubuntu_community_endorsement: Option<Pubkey>,
 }
@@ -158,10 +463,38 @@ pub struct TreatmentPassCreated {
 #[event]
 pub struct TreatmentPassSponsored {
     pub pass_id: u64,
-    pub sponsor: Pubkey,
+    /// Sponsor wallet, or None for anonymous sponsorships
+    pub sponsor: Option<Pubkey>,
+    pub sponsor_commitment: Option<[u8; 32]>,
     pub amount: u64,
+    pub milestone_allocations: Vec<MilestoneAllocation>,
+    pub surplus_routed_to_pool: u64,
     pub total_funded: u64,
-    pub funding_percentage: u8,
+    pub funding_basis_points: u16,
+}
+
+#[event]
+pub struct SponsorIdentityRevealed {
+    pub pass_id: u64,
+    pub sponsor: Pubkey,
+    pub sponsor_commitment: [u8; 32],
+    pub amount_contributed: u64,
+    pub made_public: bool,
+}
+
+#[event]
+pub struct SponsorMessageHidden {
+    pub pass_id: u64,
+    pub sponsor_contribution: Pubkey,
+    pub message_hash: Option<[u8; 32]>,
+    pub hidden_by: Pubkey,
+}
+
+#[event]
+pub struct TreatmentPassEndorsed {
+    pub pass_id: u64,
+    pub endorser: Pubkey,
+    pub endorsement_count: u32,
 }
 
 #[event]
@@ -173,6 +506,23 @@ pub struct MilestoneCompleted {
     pub ubuntu_community_validated: bool,
 }
 
+#[event]
+pub struct MilestoneCompletionSubmitted {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub submitted_by: Pubkey,
+    pub evidence_hash: String,
+    pub submitted_at: i64,
+}
+
+#[event]
+pub struct MilestoneCompletionRejected {
+    pub pass_id: u64,
+    pub milestone_id: u8,
+    pub rejected_by: Pubkey,
+    pub reason: String,
+}
+
 #[event]
 pub struct ElderCouncilReview {
     pub pass_id: u64,
@@ -181,6 +531,39 @@ pub struct ElderCouncilReview {
     pub traditional_wisdom_shared: bool,
 }
 
+#[event]
+pub struct TreatmentStatusChanged {
+    pub pass_id: u64,
+    pub previous_status: TreatmentPassStatus,
+    pub new_status: TreatmentPassStatus,
+    pub changed_by: Pubkey,
+    pub reason: Option<String>,
+    pub changed_at: i64,
+}
+
+#[event]
+pub struct TreatingProviderAssigned {
+    pub pass_id: u64,
+    pub provider: Pubkey,
+    pub assigned_by: Pubkey,
+}
+
+#[event]
+pub struct TreatmentPassExpired {
+    pub pass_id: u64,
+    pub funding_target: u64,
+    pub current_funding: u64,
+    pub funding_deadline: i64,
+}
+
+#[event]
+pub struct SponsorRefundClaimed {
+    pub pass_id: u64,
+    pub sponsor: Option<Pubkey>,
+    pub refund_amount: u64,
+    pub claimed_at: i64,
+}
+
 #[event]
 pub struct TreatmentCompleted {
     pub pass_id: u64,
@@ -191,6 +574,22 @@ pub struct TreatmentCompleted {
     pub ubuntu_community_celebration: bool,
 }
 
+/// Maximum length of a reason given for a status change or rejected milestone
+pub const MAX_STATUS_REASON_LEN: usize = 256;
+
+/// Bounds on the grace period between flagging a milestone overdue and voting on it (1 to 90 days)
+pub const MIN_OVERDUE_GRACE_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_OVERDUE_GRACE_PERIOD_SECONDS: i64 = 90 * 24 * 60 * 60;
+
+/// Longest funding window a treatment pass may request (180 days)
+pub const MAX_FUNDING_PERIOD_SECONDS: i64 = 180 * 24 * 60 * 60;
+
+/// Basis points representing a fully funded pass
+pub const FULLY_FUNDED_BASIS_POINTS: u16 = 10_000;
+
+/// Smallest share of sponsor funding a pass may require to approve milestone amendments
+pub const MIN_AMENDMENT_APPROVAL_BASIS_POINTS: u16 = 5_000;
+
 // Account structure for storing treatment passes
 #[account]
 pub struct TreatmentPassAccount {
@@ -198,9 +597,370 @@ pub struct TreatmentPassAccount {
     pub bump: u8,
 }
 
+// Account structure for storing a single sponsor's contribution to a treatment pass
+#[account]
+pub struct SponsorContributionAccount {
+    pub contribution: SponsorContribution,
+    pub bump: u8,
+}
+
+impl TreatmentPass {
+    /// Total funding contributed to the general pool rather than earmarked to a milestone
+    pub fn unearmarked_contributions(&self) -> Result<u64> {
+        let pledged_total = self.milestones.iter()
+            .try_fold(0u64, |total, m| total.checked_add(m.pledged_amount))
+            .ok_or(ErrorCode::Overflow)?;
+        
+        Ok(self.current_funding.saturating_sub(pledged_total))
+    }
+    
+    /// Milestone currently holding funds earmarked to `milestone_id`, following reallocations;
+    /// None once the earmark has fallen back to the general pool
+    pub fn earmark_destination(&self, milestone_id: u8) -> Option<u8> {
+        let mut current = milestone_id;
+        // Funds only move to open milestones, so a chain never revisits a milestone
+        for _ in 0..=self.milestones.len() {
+            let milestone = self.milestones.iter().find(|m| m.milestone_id == current)?;
+            if milestone.completion_status != MilestoneStatus::Reallocated {
+                return Some(current);
+            }
+            current = milestone.reallocated_to?;
+        }
+        
+        None
+    }
+    
+    /// Whether funds earmarked to the milestone have already been paid out to the patient
+    pub fn earmark_released(&self, milestone_id: u8) -> bool {
+        self.earmark_destination(milestone_id).map_or(false, |destination| {
+            self.milestones.iter()
+                .any(|m| m.milestone_id == destination && m.completion_status == MilestoneStatus::Verified)
+        })
+    }
+    
+    /// Whether funds earmarked to the milestone are still held in escrow for it
+    pub fn earmark_unreleased(&self, milestone_id: u8) -> bool {
+        self.earmark_destination(milestone_id).is_some() && !self.earmark_released(milestone_id)
+    }
+    
+    /// Current overdue review round of a milestone (0 if the milestone does not exist)
+    pub fn milestone_overdue_round(&self, milestone_id: u8) -> u8 {
+        self.milestones.iter()
+            .find(|m| m.milestone_id == milestone_id)
+            .map_or(0, |m| m.overdue_round)
+    }
+    
+    /// Amount still needed to reach the funding target
+    pub fn remaining_funding(&self) -> u64 {
+        self.funding_target.saturating_sub(self.current_funding)
+    }
+    
+    /// Whether the pass is still open to sponsorship at the given time
+    pub fn is_accepting_funding(&self, now: i64) -> bool {
+        matches!(
+            self.status,
+            TreatmentPassStatus::FundingRequired | TreatmentPassStatus::PartiallyFunded
+        ) && now <= self.funding_deadline
+    }
+    
+    /// Splits an offered amount into the accepted portion and the surplus routed to the community pool
+    pub fn split_offered_funding(&self, amount: u64) -> Result<(u64, u64)> {
+        let remaining = self.remaining_funding();
+        require!(remaining > 0, ErrorCode::FundingTargetReached);
+        
+        if amount <= remaining {
+            return Ok((amount, 0));
+        }
+        
+        match self.overfunding_policy {
+            OverfundingPolicy::Reject => err!(ErrorCode::FundingTargetExceeded),
+            OverfundingPolicy::CapAtTarget => Ok((remaining, 0)),
+            OverfundingPolicy::RouteToCommunityPool => Ok((remaining, amount - remaining)),
+        }
+    }
+    
+    /// Moves the pass to `next`, rejecting any move the treatment lifecycle does not allow
+    pub fn transition_to(&mut self, next: TreatmentPassStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(&next),
+            ErrorCode::InvalidStatusTransition
+        );
+        self.status = next;
+        
+        Ok(())
+    }
+    
+    /// Funding status matching the amount raised so far
+    pub fn funding_status(&self) -> TreatmentPassStatus {
+        if self.current_funding >= self.funding_target {
+            TreatmentPassStatus::FullyFunded
+        } else if self.current_funding > 0 {
+            TreatmentPassStatus::PartiallyFunded
+        } else {
+            TreatmentPassStatus::FundingRequired
+        }
+    }
+    
+    /// Adds accepted funding and refreshes the funded ratio and status
+    pub fn record_funding(&mut self, amount: u64) -> Result<()> {
+        self.current_funding = self.current_funding.checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.refresh_funding_basis_points()?;
+        
+        // Update status based on funding level
+        let next = self.funding_status();
+        if next != self.status {
+            self.transition_to(next)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Removes funding returned to a sponsor mid-treatment from the pass totals
+    pub fn record_refund(&mut self, amount: u64) -> Result<()> {
+        self.current_funding = self.current_funding.checked_sub(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.refresh_funding_basis_points()
+    }
+    
+    fn refresh_funding_basis_points(&mut self) -> Result<()> {
+        self.funding_basis_points = (self.current_funding as u128)
+            .checked_mul(FULLY_FUNDED_BASIS_POINTS as u128)
+            .and_then(|scaled| scaled.checked_div(self.funding_target as u128))
+            .ok_or(ErrorCode::Overflow)?
+            .min(FULLY_FUNDED_BASIS_POINTS as u128) as u16;
+        
+        Ok(())
+    }
+    
+    /// Amount owed to a contributor of a refundable pass: their unreleased earmarks in full,
+    /// plus a pro-rata share of the general pool that has not been released through verified milestones
+    pub fn refund_amount(&self, contribution: &SponsorContribution) -> Result<u64> {
+        let mut unreleased_earmarks: u64 = 0;
+        for allocation in contribution.milestone_allocations.iter()
+            .filter(|a| !a.refunded && self.earmark_destination(a.milestone_id).is_some()) {
+            if !self.earmark_released(allocation.milestone_id) {
+                unreleased_earmarks = unreleased_earmarks.checked_add(allocation.amount)
+                    .ok_or(ErrorCode::Overflow)?;
+            }
+        }
+        
+        unreleased_earmarks.checked_add(self.pool_refund_share(contribution)?)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+    
+    /// Contributor's pro-rata share of the general pool still held in escrow
+    pub fn pool_refund_share(&self, contribution: &SponsorContribution) -> Result<u64> {
+        let mut earmarked: u64 = 0;
+        // Earmarks that fell back to the general pool are repaid through the pool share
+        for allocation in contribution.milestone_allocations.iter()
+            .filter(|a| !a.refunded && self.earmark_destination(a.milestone_id).is_some()) {
+            earmarked = earmarked.checked_add(allocation.amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        
+        let unearmarked_contributions = self.unearmarked_contributions()?;
+        if unearmarked_contributions == 0 {
+            return Ok(0);
+        }
+        
+        Ok((contribution.amount_contributed.saturating_sub(earmarked) as u128)
+            .checked_mul(self.unearmarked_funding as u128)
+            .and_then(|share| share.checked_div(unearmarked_contributions as u128))
+            .ok_or(ErrorCode::Overflow)? as u64)
+    }
+}
+
+impl TreatmentMilestone {
+    /// Whether the signer may confirm or reject completion of this milestone
+    pub fn is_authorized_verifier(
+        &self,
+        verifier: Pubkey,
+        verifier_entry: Option<&VerifierEntryAccount>,
+        elder_council: Option<&ElderCouncil>,
+        verifier_acts_for_patient: bool,
+    ) -> bool {
+        // Milestones that release funds are never verified by the patient or on their behalf
+        if self.funding_amount > 0 && verifier_acts_for_patient {
+            return false;
+        }
+        
+        // Elder verification is open to any sitting council member
+        if self.verification_required == VerificationType::ElderCouncilVerification {
+            return elder_council.map_or(false, |council| council.is_member(&verifier));
+        }
+        
+        // Verifier must hold an active registry entry matching the milestone's verification type
+        match verifier_entry {
+            Some(verifier_entry) => {
+                verifier_entry.entry.is_active &&
+                    verifier_entry.entry.verifier_kind.can_verify(&self.verification_required)
+            }
+            // Patients may only confirm self-reported milestones that carry no funding
+            None => {
+                self.verification_required == VerificationType::PatientReported &&
+                    self.funding_amount == 0 &&
+                    verifier_acts_for_patient
+            }
+        }
+    }
+}
+
+impl SponsorContribution {
+    /// Empty contribution record for a wallet's first sponsorship of a pass
+    pub fn new(treatment_pass: Pubkey, wallet: Pubkey, now: i64) -> Self {
+        SponsorContribution {
+            treatment_pass,
+            wallet,
+            amount_contributed: 0,
+            milestone_allocations: Vec::new(),
+            first_sponsored_at: now,
+            last_sponsored_at: now,
+            sponsor_message: None,
+            sponsor_message_hash: None,
+            sponsor_message_hidden: false,
+            anonymous: false,
+            identity_commitment: None,
+            refund_claimed: false,
+            slashed_stake_claimed: false,
+            ubuntu_health_member: false,
+            ubuntu_community_member: false, // TODO: Validate community membership
+            community_reputation: 0, // TODO: Calculate from community data
+            diaspora_connection: None, // TODO: Check for diaspora connections
+            traditional_healing_knowledge: None,
+        }
+    }
+    
+    /// Commitment an anonymous sponsor is recorded by instead of their wallet, opened only with the sponsor's salt
+    pub fn identity_commitment(treatment_pass: &Pubkey, wallet: &Pubkey, salt: &[u8; 32]) -> [u8; 32] {
+        hashv(&[treatment_pass.as_ref(), wallet.as_ref(), salt.as_ref()]).to_bytes()
+    }
+    
+    /// Last seed of a contribution record: the sponsor's wallet, or for anonymous sponsors
+    /// a hash of their commitment so the record's address carries no wallet
+    pub fn record_seed(wallet: &Pubkey, identity_commitment: Option<[u8; 32]>) -> [u8; 32] {
+        match identity_commitment {
+            Some(commitment) => hashv(&[b"anonymous_contribution", commitment.as_ref()]).to_bytes(),
+            None => wallet.to_bytes(),
+        }
+    }
+    
+    /// Record seed for a wallet acting on its own contribution; anonymous sponsors prove authorship with their salt
+    pub fn signer_record_seed(treatment_pass: &Pubkey, wallet: &Pubkey, identity_salt: Option<[u8; 32]>) -> [u8; 32] {
+        Self::record_seed(
+            wallet,
+            identity_salt.map(|salt| Self::identity_commitment(treatment_pass, wallet, &salt)),
+        )
+    }
+    
+    /// Sponsor identity as it may be shown publicly
+    pub fn public_wallet(&self) -> Option<Pubkey> {
+        if self.anonymous { None } else { Some(self.wallet) }
+    }
+}
+
+/// Maximum lengths for variable-sized treatment pass data
+pub const MAX_TITLE_LEN: usize = 100;
+pub const MAX_DESCRIPTION_LEN: usize = 1000;
+pub const MAX_CATEGORY_LEN: usize = 50;
+pub const MAX_MILESTONES: usize = 10;
+pub const MAX_MILESTONE_DESCRIPTION_LEN: usize = 200;
+pub const MAX_EVIDENCE_LEN: usize = 128;
+pub const MAX_SPONSOR_MESSAGE_LEN: usize = 280;
+pub const MAX_HEALING_PRACTICE_LEN: usize = 64;
+pub const MAX_INTEGRATION_PLAN_LEN: usize = 256;
+pub const MAX_HEALING_KNOWLEDGE_LEN: usize = 256;
+pub const MAX_COMMUNITY_NAME_LEN: usize = 64;
+pub const MAX_NETWORK_CONTACTS: usize = 5;
+pub const MAX_ELDER_GUIDANCE_LEN: usize = 500;
+pub const MAX_TRADITIONAL_WISDOM_LEN: usize = 500;
+pub const MAX_SUPPORT_RECOMMENDATIONS: usize = 5;
+pub const MAX_SUPPORT_RECOMMENDATION_LEN: usize = 100;
+
+/// Current `TreatmentPass` layout; version 1 passes predate the Ubuntu governance fields
+/// and version 2 passes predate the pinned escrow account
+pub const TREATMENT_PASS_VERSION: u8 = 3;
+
+impl TreatmentMilestone {
+    /// Borsh-serialized size of a milestone with maximal strings
+    pub const MAX_SIZE: usize = 1 + // milestone_id
+        4 + MAX_MILESTONE_DESCRIPTION_LEN + // description
+        8 + // funding_amount
+        8 + // pledged_amount
+        1 + // verification_required
+        1 + // completion_status
+        8 + // expected_completion
+        1 + 8 + // completion_timestamp
+        1 + 8 + // overdue_since
+        1 + // overdue_round
+        1 + 1 + // reallocated_to
+        1 + 32 + // verifying_provider
+        1 + 4 + MAX_EVIDENCE_LEN + // completion_evidence
+        1 + 4 + MAX_EVIDENCE_LEN + // verification_evidence
+        1 + 4 + MAX_EVIDENCE_LEN + // cryptographic_proof
+        1 + 32 + // elder_council_review
+        1; // ubuntu_community_validation
+}
+
+impl SponsorContributionAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // treatment_pass
+        32 + // wallet
+        8 + // amount_contributed
+        4 + MAX_MILESTONES * MilestoneAllocation::SIZE + // milestone_allocations
+        8 + // first_sponsored_at
+        8 + // last_sponsored_at
+        1 + 4 + MAX_SPONSOR_MESSAGE_LEN + // sponsor_message
+        1 + 32 + // sponsor_message_hash
+        1 + // sponsor_message_hidden
+        1 + // anonymous
+        1 + 32 + // identity_commitment
+        1 + // refund_claimed
+        1 + // slashed_stake_claimed
+        1 + // ubuntu_health_member
+        1 + // ubuntu_community_member
+        4 + // community_reputation
+        1 + DiasporaInfo::MAX_SIZE + // diaspora_connection
+        1 + 4 + MAX_HEALING_KNOWLEDGE_LEN + // traditional_healing_knowledge
+        1; // bump
+}
+
 impl TreatmentPassAccount {
     pub const LEN: usize = 8 + // discriminator
-        std::mem::size_of::<TreatmentPass>() + 
+        1 + // version
+        8 + // pass_id
+        32 + // patient
+        4 + MAX_TITLE_LEN + // treatment_title
+        4 + MAX_DESCRIPTION_LEN + // treatment_description
+        4 + MAX_CATEGORY_LEN + // treatment_category
+        8 + // funding_target
+        8 + // current_funding
+        32 + // escrow
+        2 + // funding_basis_points
+        1 + // overfunding_policy
+        4 + MAX_MILESTONES * TreatmentMilestone::MAX_SIZE + // milestones
+        4 + // sponsor_count
+        8 + // unearmarked_funding
+        8 + // created_at
+        2 + // treatment_duration_days
+        8 + // funding_deadline
+        8 + // overdue_grace_period_seconds
+        2 + // amendment_approval_basis_points
+        2 + // amendment_count
+        1 + 2 + // pending_amendment
+        4 + MAX_AMENDMENT_HISTORY * AmendmentRecord::SIZE + // amendment_history
+        1 + 32 + // treating_provider
+        1 + // status
+        1 + // flagged_fraudulent
+        2 + // elder_review_round
+        1 + 32 + // ubuntu_community_endorsement
+        1 + // elder_council_approval
+        1 + 32 + // approving_elder
+        1 + TraditionalHealing::MAX_SIZE + // traditional_healing_component
+        1 + DiasporaInfo::MAX_SIZE + // diaspora_connection
+        1 + // ubuntu_alignment_score
+        1 + // ubuntu_health_verified
+        1 + 8 + // verification_timestamp
         1; // bump
 }
 
@@ -210,24 +970,104 @@ impl TreatmentPassAccount {
 pub struct CreateTreatmentPass<'info> {
     #[account(
         init,
-        payer = patient,
+        payer = creator,
         space = TreatmentPassAccount::LEN,
         seeds = [b"treatment_pass", patient.key().as_ref(), pass_id.to_le_bytes().as_ref()],
         bump
     )]
     pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
     
+    /// Patient the treatment pass is for
+    /// CHECK: Only used as a key; the creator must be the patient or hold their ManageTreatment delegation
+    pub patient: UncheckedAccount<'info>,
+    
+    /// Patient or their representative, paying for the pass
     #[account(mut)]
-    pub patient: Signer<'info>,
+    pub creator: Signer<'info>,
+    
+    /// Creator's appointment as the patient's representative; omitted when the patient creates the pass
+    #[account(
+        seeds = [b"patient_representative", patient.key().as_ref(), creator.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
+    /// Mint sponsorships of this pass are paid in
+    pub funding_mint: Account<'info, Mint>,
+    
+    /// Escrow holding the pass's sponsored funds, owned by the treatment pass PDA
+    #[account(
+        init,
+        payer = creator,
+        token::mint = funding_mint,
+        token::authority = treatment_pass_account,
+        seeds = [b"treatment_pass_escrow", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
     
     /// Ubuntu community member who endorses this treatment
-    /// CHECK: Optional endorsement, validated by Ubuntu community logic
-    pub ubuntu_endorser: Option<AccountInfo<'info>>,
+    pub ubuntu_endorser: Option<Signer<'info>>,
+    
+    /// Endorser's profile in the Ubuntu member registry
+    /// CHECK: Owner, address and layout are validated by `UbuntuMemberProfile::load`
+    pub endorser_profile: Option<UncheckedAccount<'info>>,
+    
+    /// Endorser's endorsement reputation, created on their first endorsement
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = EndorserReputationAccount::LEN,
+        seeds = [b"endorser_reputation", ubuntu_endorser.as_ref().map(|e| e.key()).unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub endorser_reputation: Option<Account<'info, EndorserReputationAccount>>,
+    
+    /// $LIVES configuration, identifying the stake mint
+    #[account(
+        seeds = [b"lives_token_config"],
+        bump = lives_config.bump
+    )]
+    pub lives_config: Option<Account<'info, LivesTokenConfigAccount>>,
+    
+    pub lives_token_mint: Option<Account<'info, Mint>>,
+    
+    /// Endorser's $LIVES account funding the stake
+    #[account(mut)]
+    pub endorser_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Endorser's stake record for this pass
+    #[account(
+        init,
+        payer = creator,
+        space = EndorsementStakeAccount::LEN,
+        seeds = [b"endorsement_stake", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub endorsement_stake: Option<Account<'info, EndorsementStakeAccount>>,
+    
+    /// Vault holding the endorser's stake, owned by the stake record
+    #[account(
+        init,
+        payer = creator,
+        token::mint = lives_token_mint,
+        token::authority = endorsement_stake,
+        seeds = [b"endorsement_stake_vault", treatment_pass_account.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Option<Account<'info, TokenAccount>>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(
+    amount: u64,
+    sponsor_message: Option<String>,
+    milestone_allocations: Vec<MilestoneAllocation>,
+    identity_salt: Option<[u8; 32]>
+)]
 pub struct SponsorTreatment<'info> {
     #[account(
         mut,
@@ -236,18 +1076,53 @@ pub struct SponsorTreatment<'info> {
     )]
     pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
     
-    #[account(mut)]
+    /// Sponsor's contribution record, created on their first sponsorship of this pass;
+    /// anonymous records are addressed by the commitment to the sponsor's wallet and salt instead of the wallet
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SponsorContributionAccount::LEN,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), identity_salt).as_ref()
+        ],
+        bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    /// Owner of the funding token account; anonymous sponsors should fund from a wallet not tied to their identity
     pub sponsor: Signer<'info>,
     
-    /// Escrow account to hold sponsored funds
+    /// Pays rent for a new contribution record; a relayer or throwaway wallet for anonymous sponsors
     #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Pass's escrow, receiving the sponsored funds
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
     pub escrow_account: Account<'info, TokenAccount>,
     
     /// Sponsor's token account
     #[account(mut)]
     pub sponsor_token_account: Account<'info, TokenAccount>,
     
+    /// Community overflow pool; required only when surplus is routed to it
+    #[account(
+        mut,
+        seeds = [b"community_overflow_pool"],
+        bump = community_pool.bump
+    )]
+    pub community_pool: Option<Account<'info, CommunityOverflowPoolAccount>>,
+    
+    /// Token account holding the community overflow pool's funds
+    #[account(mut)]
+    pub community_pool_vault: Option<Account<'info, TokenAccount>>,
+    
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -262,63 +1137,508 @@ pub struct VerifyMilestone<'info> {
     /// Healthcare provider or Ubuntu elder verifying the milestone
     pub verifier: Signer<'info>,
     
+    /// Verifier's registry entry; may be omitted only when the patient confirms an unfunded self-reported milestone
+    #[account(
+        seeds = [b"verifier", verifier.key().as_ref()],
+        bump = verifier_entry.bump
+    )]
+    pub verifier_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// Elder council, when an elder verifies an elder council milestone
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Option<Account<'info, ElderCouncilAccount>>,
+    
+    /// Verifier's appointment as the patient's representative, when confirming an unfunded self-reported milestone
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), verifier.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
     /// Patient account (for releasing funds to)
     /// CHECK: Validated against treatment pass patient
     pub patient: AccountInfo<'info>,
     
     /// Escrow account holding the funds
-    #[account(mut)]
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
     pub escrow_account: Account<'info, TokenAccount>,
     
-    /// Patient's token account to receive released funds
+    /// Token account receiving released funds, owned by the patient or a representative holding ReceiveFunds
     #[account(mut)]
     pub patient_token_account: Account<'info, TokenAccount>,
     
+    /// Payee's appointment as the patient's representative; omitted when funds go to the patient
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), patient_token_account.owner.as_ref()],
+        bump = payee_representative.bump
+    )]
+    pub payee_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
     pub token_program: Program<'info, Token>,
 }
 
-// Treatment Pass Program Implementation
-pub fn create_treatment_pass(
-    ctx: Context<CreateTreatmentPass>,
-    pass_id: u64,
-    treatment_title: String,
-    treatment_description: String,
-    treatment_category: String,
-    funding_target: u64,
-    milestones: Vec<TreatmentMilestone>,
-    traditional_healing_component: Option<TraditionalHealing>,
-) -> Result<()> {
-    let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
-    let clock = Clock::get()?;
-    
-    // Validate Ubuntu community endorsement if provided
-    let ubuntu_community_endorsement = if let Some(endorser) = &ctx.accounts.ubuntu_endorser {
-        // TODO: Validate endorser is a valid Ubuntu community member
-        Some(endorser.key())
-    } else {
-        None
+#[derive(Accounts)]
+pub struct SubmitMilestoneCompletion<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Patient or member of the care team reporting completion
+    pub submitter: Signer<'info>,
+    
+    /// Submitter's registry entry; omitted when the patient reports
+    #[account(
+        seeds = [b"verifier", submitter.key().as_ref()],
+        bump = submitter_entry.bump
+    )]
+    pub submitter_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// Submitter's appointment as the patient's representative; omitted when the patient or care team reports
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), submitter.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RejectMilestoneCompletion<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Verifier rejecting the reported completion
+    pub verifier: Signer<'info>,
+    
+    /// Verifier's registry entry; may be omitted only for an unfunded self-reported milestone
+    #[account(
+        seeds = [b"verifier", verifier.key().as_ref()],
+        bump = verifier_entry.bump
+    )]
+    pub verifier_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// Elder council, when an elder verifies an elder council milestone
+    #[account(
+        seeds = [b"elder_council"],
+        bump = elder_council.bump
+    )]
+    pub elder_council: Option<Account<'info, ElderCouncilAccount>>,
+    
+    /// Verifier's appointment as the patient's representative, when confirming an unfunded self-reported milestone
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), verifier.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireTreatmentPass<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreatmentStatus<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Patient, their representative or assigned provider changing the treatment status
+    pub authority: Signer<'info>,
+    
+    /// Authority's registry entry; omitted when the patient acts
+    #[account(
+        seeds = [b"verifier", authority.key().as_ref()],
+        bump = authority_entry.bump
+    )]
+    pub authority_entry: Option<Account<'info, VerifierEntryAccount>>,
+    
+    /// Authority's appointment as the patient's representative; omitted unless a representative acts
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct AssignTreatingProvider<'info> {
+    #[account(
+        mut,
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Patient or their representative choosing the provider
+    pub authority: Signer<'info>,
+    
+    /// Authority's appointment as the patient's representative; omitted when the patient acts
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), authority.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
+    /// Registry entry of the provider being assigned
+    #[account(
+        seeds = [b"verifier", provider_entry.entry.verifier.as_ref()],
+        bump = provider_entry.bump
+    )]
+    pub provider_entry: Account<'info, VerifierEntryAccount>,
+}
+
+impl<'info> UpdateTreatmentStatus<'info> {
+    fn acts_for_patient(&self) -> Result<bool> {
+        acts_for_patient(
+            &self.authority.key(),
+            &self.treatment_pass_account.treatment_pass.patient,
+            self.patient_representative.as_deref(),
+            RepresentativeScope::ManageTreatment,
+        )
+    }
+    
+    fn is_treating_provider(&self) -> bool {
+        self.treatment_pass_account.treatment_pass.treating_provider == Some(self.authority.key()) &&
+            self.authority_entry.as_ref().map_or(false, |authority_entry| {
+                authority_entry.entry.is_active &&
+                    authority_entry.entry.verifier_kind == VerifierKind::AccreditedProvider
+            })
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(identity_salt: Option<[u8; 32]>)]
+pub struct ClaimSponsorRefund<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Sponsor's contribution record for this pass
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), identity_salt).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    pub sponsor: Signer<'info>,
+    
+    /// Escrow account holding the sponsored funds
+    #[account(
+        mut,
+        address = treatment_pass_account.treatment_pass.escrow @ ErrorCode::InvalidEscrowAccount
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    /// Sponsor's token account to receive the refund
+    #[account(
+        mut,
+        constraint = sponsor_token_account.owner == sponsor.key() @ ErrorCode::InvalidRefundAccount,
+        constraint = sponsor_token_account.mint == escrow_account.mint @ ErrorCode::InvalidRefundAccount
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(salt: [u8; 32])]
+pub struct RevealSponsorIdentity<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Anonymous sponsor's contribution record for this pass
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_contribution",
+            treatment_pass_account.key().as_ref(),
+            SponsorContribution::signer_record_seed(&treatment_pass_account.key(), &sponsor.key(), Some(salt)).as_ref()
+        ],
+        bump = sponsor_contribution.bump
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    pub sponsor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct HideSponsorMessage<'info> {
+    #[account(
+        seeds = [b"treatment_pass", treatment_pass_account.treatment_pass.patient.as_ref(), treatment_pass_account.treatment_pass.pass_id.to_le_bytes().as_ref()],
+        bump = treatment_pass_account.bump
+    )]
+    pub treatment_pass_account: Account<'info, TreatmentPassAccount>,
+    
+    /// Contribution record carrying the message; addressed directly since anonymous sponsors' wallets are unknown
+    #[account(
+        mut,
+        constraint = sponsor_contribution.contribution.treatment_pass == treatment_pass_account.key() @ ErrorCode::ContributionPassMismatch
+    )]
+    pub sponsor_contribution: Account<'info, SponsorContributionAccount>,
+    
+    /// Patient, their representative or platform moderation authority
+    pub moderator: Signer<'info>,
+    
+    /// Moderator's appointment as the patient's representative; omitted unless a representative acts
+    #[account(
+        seeds = [b"patient_representative", treatment_pass_account.treatment_pass.patient.as_ref(), moderator.key().as_ref()],
+        bump = patient_representative.bump
+    )]
+    pub patient_representative: Option<Account<'info, PatientRepresentativeAccount>>,
+    
+    /// Verifier registry, whose platform authority also moderates sponsor messages; omitted when the patient acts
+    #[account(
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump
+    )]
+    pub registry_account: Option<Account<'info, VerifierRegistryAccount>>,
+}
+
+// Treatment Pass Program Implementation
+/// Checks a new milestone plan before it is stored, so bad plans fail at intake rather than at release
+pub(crate) fn validate_milestone_schedule(milestones: &[TreatmentMilestone], funding_target: u64, now: i64) -> Result<()> {
+    require!(!milestones.is_empty(), ErrorCode::NoMilestones);
+    require!(milestones.len() <= MAX_MILESTONES, ErrorCode::TooManyMilestones);
+    
+    let mut milestone_total: u64 = 0;
+    for (index, milestone) in milestones.iter().enumerate() {
+        // Ids must be strictly increasing, which also makes them unique
+        if index > 0 {
+            let previous_id = milestones[index - 1].milestone_id;
+            require!(milestone.milestone_id != previous_id, ErrorCode::DuplicateMilestoneId);
+            require!(milestone.milestone_id > previous_id, ErrorCode::MilestoneIdsNotOrdered);
+        }
+        require!(milestone.funding_amount > 0, ErrorCode::InvalidMilestoneAmount);
+        require!(milestone.expected_completion > now, ErrorCode::MilestoneCompletionInPast);
+        require!(
+            milestone.completion_status == MilestoneStatus::NotStarted,
+            ErrorCode::MilestoneAlreadyStarted
+        );
+        require!(
+            milestone.description.len() <= MAX_MILESTONE_DESCRIPTION_LEN,
+            ErrorCode::MilestoneDescriptionTooLong
+        );
+        require!(
+            milestone.verification_evidence.as_ref().map_or(0, |e| e.len()) <= MAX_EVIDENCE_LEN &&
+                milestone.cryptographic_proof.as_ref().map_or(0, |p| p.len()) <= MAX_EVIDENCE_LEN,
+            ErrorCode::EvidenceTooLong
+        );
+        milestone_total = milestone_total.checked_add(milestone.funding_amount)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    
+    require!(milestone_total == funding_target, ErrorCode::MilestoneTotalMismatch);
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_treatment_pass(
+    ctx: Context<CreateTreatmentPass>,
+    pass_id: u64,
+    treatment_title: String,
+    treatment_description: String,
+    treatment_category: String,
+    funding_target: u64,
+    funding_deadline: i64,
+    overfunding_policy: OverfundingPolicy,
+    overdue_grace_period_seconds: i64,
+    amendment_approval_basis_points: u16,
+    mut milestones: Vec<TreatmentMilestone>,
+    traditional_healing_component: Option<TraditionalHealing>,
+) -> Result<()> {
+    require!(
+        acts_for_patient(
+            &ctx.accounts.creator.key(),
+            &ctx.accounts.patient.key(),
+            ctx.accounts.patient_representative.as_deref(),
+            RepresentativeScope::ManageTreatment,
+        )?,
+        ErrorCode::UnauthorizedPatientAction
+    );
+    
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
+    let clock = Clock::get()?;
+    
+    require!(funding_target > 0, ErrorCode::InvalidFundingTarget);
+    
+    // Funding window must be in the future and of bounded length
+    require!(
+        funding_deadline > clock.unix_timestamp &&
+            funding_deadline - clock.unix_timestamp <= MAX_FUNDING_PERIOD_SECONDS,
+        ErrorCode::InvalidFundingDeadline
+    );
+    require!(
+        (MIN_OVERDUE_GRACE_PERIOD_SECONDS..=MAX_OVERDUE_GRACE_PERIOD_SECONDS)
+            .contains(&overdue_grace_period_seconds),
+        ErrorCode::InvalidGracePeriod
+    );
+    require!(
+        (MIN_AMENDMENT_APPROVAL_BASIS_POINTS..=FULLY_FUNDED_BASIS_POINTS)
+            .contains(&amendment_approval_basis_points),
+        ErrorCode::InvalidAmendmentApprovalShare
+    );
+    
+    // Variable-length fields must fit the fixed account allocation
+    require!(treatment_title.len() <= MAX_TITLE_LEN, ErrorCode::TreatmentTitleTooLong);
+    require!(treatment_description.len() <= MAX_DESCRIPTION_LEN, ErrorCode::TreatmentDescriptionTooLong);
+    require!(treatment_category.len() <= MAX_CATEGORY_LEN, ErrorCode::TreatmentCategoryTooLong);
+    validate_milestone_schedule(&milestones, funding_target, clock.unix_timestamp)?;
+    if let Some(traditional_healing) = &traditional_healing_component {
+        traditional_healing.validate()?;
+    }
+    
+    // Pledges, overdue tracking and community validation are accumulated on-chain
+    for milestone in milestones.iter_mut() {
+        milestone.pledged_amount = 0;
+        milestone.overdue_since = None;
+        milestone.overdue_round = 0;
+        milestone.reallocated_to = None;
+        milestone.elder_council_review = None;
+        milestone.ubuntu_community_validation = false;
+    }
+    
+    // Validate Ubuntu community endorsement if provided
+    let ubuntu_community_endorsement = match (
+        &ctx.accounts.ubuntu_endorser,
+        &ctx.accounts.endorser_profile,
+        &mut ctx.accounts.endorser_reputation,
+    ) {
+        (Some(endorser), Some(endorser_profile), Some(endorser_reputation)) => {
+            require_keys_neq!(endorser.key(), ctx.accounts.patient.key(), ErrorCode::SelfEndorsement);
+            require_keys_neq!(endorser.key(), ctx.accounts.creator.key(), ErrorCode::SelfEndorsement);
+            
+            let profile = UbuntuMemberProfile::load(endorser_profile, &endorser.key())?;
+            require!(profile.is_active, ErrorCode::EndorserInactive);
+            require!(profile.elder_verified, ErrorCode::EndorserNotElderVerified);
+            
+            endorser_reputation.endorser = endorser.key();
+            endorser_reputation.endorsement_count = endorser_reputation.endorsement_count.checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+            endorser_reputation.last_endorsed_at = clock.unix_timestamp;
+            endorser_reputation.bump = ctx.bumps.endorser_reputation;
+            
+            // Endorsers back their word with a $LIVES stake held until the pass is settled
+            let lives_config = ctx.accounts.lives_config.as_ref().ok_or(ErrorCode::IncompleteEndorsement)?;
+            let lives_token_mint = ctx.accounts.lives_token_mint.as_ref().ok_or(ErrorCode::IncompleteEndorsement)?;
+            let endorser_token_account = ctx.accounts.endorser_token_account.as_ref()
+                .ok_or(ErrorCode::IncompleteEndorsement)?;
+            let endorsement_stake = ctx.accounts.endorsement_stake.as_mut().ok_or(ErrorCode::IncompleteEndorsement)?;
+            let stake_vault = ctx.accounts.stake_vault.as_ref().ok_or(ErrorCode::IncompleteEndorsement)?;
+            let token_program = &ctx.accounts.token_program;
+            require_keys_eq!(lives_token_mint.key(), lives_config.config.token_mint, ErrorCode::InvalidStakeMint);
+            
+            let stake_amount = 10u64.checked_pow(lives_token_mint.decimals as u32)
+                .and_then(|unit| unit.checked_mul(ENDORSEMENT_STAKE_LIVES))
+                .ok_or(ErrorCode::Overflow)?;
+            
+            let cpi_accounts = token::Transfer {
+                from: endorser_token_account.to_account_info(),
+                to: stake_vault.to_account_info(),
+                authority: endorser.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, stake_amount)?;
+            
+            endorsement_stake.bump = ctx.bumps.endorsement_stake;
+            endorsement_stake.stake = EndorsementStake::new(
+                treatment_pass_key,
+                endorser.key(),
+                stake_amount,
+                clock.unix_timestamp,
+            );
+            
+            emit!(EndorsementStakeLocked {
+                pass_id,
+                endorser: endorser.key(),
+                amount: stake_amount,
+            });
+            
+            emit!(TreatmentPassEndorsed {
+                pass_id,
+                endorser: endorser.key(),
+                endorsement_count: endorser_reputation.endorsement_count,
+            });
+            
+            Some(endorser.key())
+        }
+        (None, None, None) => {
+            require!(
+                ctx.accounts.endorsement_stake.is_none() && ctx.accounts.stake_vault.is_none(),
+                ErrorCode::IncompleteEndorsement
+            );
+            None
+        }
+        _ => return err!(ErrorCode::IncompleteEndorsement),
     };
     
     treatment_pass_account.treatment_pass = TreatmentPass {
+        version: TREATMENT_PASS_VERSION,
         pass_id,
         patient: ctx.accounts.patient.key(),
         treatment_title,
         treatment_description,
-        treatment_category,
+        treatment_category: treatment_category.clone(),
         funding_target,
         current_funding: 0,
-        funding_percentage: 0,
+        escrow: ctx.accounts.escrow_account.key(),
+        funding_basis_points: 0,
+        overfunding_policy,
         milestones,
-        sponsors: Vec::new(),
+        sponsor_count: 0,
+        unearmarked_funding: 0,
         created_at: clock.unix_timestamp,
         treatment_duration_days: 0, // To be set based on treatment type
+        funding_deadline,
+        overdue_grace_period_seconds,
+        amendment_approval_basis_points,
+        amendment_count: 0,
+        pending_amendment: None,
+        amendment_history: Vec::new(),
+        treating_provider: None,
         status: TreatmentPassStatus::FundingRequired,
+        flagged_fraudulent: false,
+        elder_review_round: 0,
         ubuntu_community_endorsement,
         elder_council_approval: false,
         approving_elder: None,
         traditional_healing_component,
         diaspora_connection: None,
         ubuntu_alignment_score: 0, // To be calculated by Ubuntu community
+        ubuntu_health_verified: false,
+        verification_timestamp: None,
     };
     
     treatment_pass_account.bump = ctx.bumps.treatment_pass_account;
@@ -328,6 +1648,7 @@ pub fn create_treatment_pass(
         pass_id,
         patient: ctx.accounts.patient.key(),
         funding_target,
+        funding_deadline,
         treatment_category,
         ubuntu_community_endorsement,
     });
@@ -339,12 +1660,39 @@ pub fn sponsor_treatment(
     ctx: Context<SponsorTreatment>,
     amount: u64,
     sponsor_message: Option<String>,
-    sponsored_milestones: Vec<u8>,
+    milestone_allocations: Vec<MilestoneAllocation>,
+    identity_salt: Option<[u8; 32]>,
 ) -> Result<()> {
-    let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
-    let treatment_pass = &mut treatment_pass_account.treatment_pass;
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let sponsor_key = ctx.accounts.sponsor.key();
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let sponsor_contribution = &mut ctx.accounts.sponsor_contribution;
     let clock = Clock::get()?;
     
+    require!(
+        clock.unix_timestamp <= treatment_pass.funding_deadline,
+        ErrorCode::FundingDeadlinePassed
+    );
+    require!(
+        sponsor_message.as_ref().map_or(0, |m| m.len()) <= MAX_SPONSOR_MESSAGE_LEN,
+        ErrorCode::SponsorMessageTooLong
+    );
+    require!(
+        matches!(
+            treatment_pass.status,
+            TreatmentPassStatus::FundingRequired | TreatmentPassStatus::PartiallyFunded
+        ),
+        ErrorCode::TreatmentPassNotFundable
+    );
+    require!(amount > 0, ErrorCode::InvalidSponsorshipAmount);
+    
+    // Derived from the signer, so only the wallet holding the salt can add to an anonymous record
+    let identity_commitment = identity_salt
+        .map(|salt| SponsorContribution::identity_commitment(&treatment_pass_key, &sponsor_key, &salt));
+    
+    // Apply the pass's overfunding policy to the offered amount
+    let (amount, surplus) = treatment_pass.split_offered_funding(amount)?;
+    
     // Transfer tokens from sponsor to escrow
     let cpi_accounts = token::Transfer {
         from: ctx.accounts.sponsor_token_account.to_account_info(),
@@ -355,49 +1703,120 @@ pub fn sponsor_treatment(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
     
-    // Update treatment pass funding
-    treatment_pass.current_funding = treatment_pass.current_funding.checked_add(amount)
+    // Route any surplus to the community overflow pool
+    if surplus > 0 {
+        let community_pool = ctx.accounts.community_pool.as_mut()
+            .ok_or(ErrorCode::CommunityPoolRequired)?;
+        let community_pool_vault = ctx.accounts.community_pool_vault.as_ref()
+            .ok_or(ErrorCode::CommunityPoolRequired)?;
+        require!(
+            community_pool_vault.key() == community_pool.pool.vault &&
+                community_pool_vault.mint == ctx.accounts.escrow_account.mint,
+            ErrorCode::InvalidCommunityPoolVault
+        );
+        
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.sponsor_token_account.to_account_info(),
+            to: community_pool_vault.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, surplus)?;
+        
+        community_pool.pool.total_received = community_pool.pool.total_received.checked_add(surplus)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    
+    // Earmark the requested portions of this contribution to their milestones
+    let mut earmarked_total: u64 = 0;
+    for (index, allocation) in milestone_allocations.iter().enumerate() {
+        require!(allocation.amount > 0, ErrorCode::InvalidMilestoneAllocation);
+        require!(
+            !milestone_allocations[..index].iter().any(|a| a.milestone_id == allocation.milestone_id),
+            ErrorCode::DuplicateMilestoneAllocation
+        );
+        
+        let milestone = treatment_pass.milestones.iter_mut()
+            .find(|m| m.milestone_id == allocation.milestone_id)
+            .ok_or(ErrorCode::MilestoneNotFound)?;
+        require!(
+            !milestone.completion_status.is_closed(),
+            ErrorCode::InvalidMilestoneStatus
+        );
+        
+        milestone.pledged_amount = milestone.pledged_amount.checked_add(allocation.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            milestone.pledged_amount <= milestone.funding_amount,
+            ErrorCode::MilestoneOverPledged
+        );
+        
+        earmarked_total = earmarked_total.checked_add(allocation.amount)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    require!(earmarked_total <= amount, ErrorCode::AllocationExceedsContribution);
+    
+    // Anything not earmarked goes to the general pool
+    treatment_pass.unearmarked_funding = treatment_pass.unearmarked_funding
+        .checked_add(amount - earmarked_total)
         .ok_or(ErrorCode::Overflow)?;
     
-    treatment_pass.funding_percentage = ((treatment_pass.current_funding as u128 * 100) / treatment_pass.funding_target as u128) as u8;
+    // Update treatment pass funding
+    treatment_pass.record_funding(amount)?;
     
-    // Add or update sponsor
-    if let Some(existing_sponsor) = treatment_pass.sponsors.iter_mut()
-        .find(|s| s.wallet == ctx.accounts.sponsor.key()) {
-        existing_sponsor.amount_contributed = existing_sponsor.amount_contributed.checked_add(amount)
+    // Create the sponsor's contribution record on their first sponsorship
+    if sponsor_contribution.contribution.treatment_pass != treatment_pass_key {
+        treatment_pass.sponsor_count = treatment_pass.sponsor_count.checked_add(1)
             .ok_or(ErrorCode::Overflow)?;
-        existing_sponsor.last_sponsored_at = clock.unix_timestamp;
-        existing_sponsor.sponsored_milestones.extend(sponsored_milestones);
-    } else {
-        treatment_pass.sponsors.push(Sponsor {
-            wallet: ctx.accounts.sponsor.key(),
-            amount_contributed: amount,
-            sponsored_milestones,
-            first_sponsored_at: clock.unix_timestamp,
-            last_sponsored_at: clock.unix_timestamp,
-            sponsor_message,
-            anonymous: false, // Can be configurable
-            ubuntu_community_member: false, // TODO: Validate community membership
-            community_reputation: 0, // TODO: Calculate from community data
-            diaspora_connection: None, // TODO: Check for diaspora connections
-            traditional_healing_knowledge: None,
-        });
+        
+        sponsor_contribution.bump = ctx.bumps.sponsor_contribution;
+        sponsor_contribution.contribution = SponsorContribution::new(
+            treatment_pass_key,
+            sponsor_key,
+            clock.unix_timestamp,
+        );
+        
+        // Anonymous sponsors are recorded by their commitment rather than their wallet
+        if identity_commitment.is_some() {
+            sponsor_contribution.contribution.wallet = Pubkey::default();
+            sponsor_contribution.contribution.anonymous = true;
+            sponsor_contribution.contribution.identity_commitment = identity_commitment;
+        }
     }
     
-    // Update status based on funding level
-    if treatment_pass.current_funding >= treatment_pass.funding_target {
-        treatment_pass.status = TreatmentPassStatus::FullyFunded;
-    } else {
-        treatment_pass.status = TreatmentPassStatus::PartiallyFunded;
+    // Later sponsorships keep the identity chosen on the first one; an anonymous record whose sponsor
+    // has since made their wallet public is still addressed by its commitment
+    let recorded = &sponsor_contribution.contribution;
+    require!(
+        recorded.identity_commitment == identity_commitment ||
+            (identity_commitment.is_some() && !recorded.anonymous && recorded.wallet == sponsor_key),
+        ErrorCode::AnonymityMismatch
+    );
+    
+    // Record the contribution and its milestone split
+    let contribution = &mut sponsor_contribution.contribution;
+    contribution.amount_contributed = contribution.amount_contributed.checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    contribution.last_sponsored_at = clock.unix_timestamp;
+    if let Some(message) = sponsor_message {
+        // Moderation is sticky, otherwise a top-up could repost the hidden message
+        require!(!contribution.sponsor_message_hidden, ErrorCode::SponsorMessagesBlocked);
+        contribution.sponsor_message_hash = Some(hashv(&[message.as_bytes()]).to_bytes());
+        contribution.sponsor_message = Some(message);
     }
+    contribution.add_allocations(&milestone_allocations)?;
     
     // Emit event
     emit!(TreatmentPassSponsored {
         pass_id: treatment_pass.pass_id,
-        sponsor: ctx.accounts.sponsor.key(),
+        sponsor: contribution.public_wallet(),
+        sponsor_commitment: contribution.identity_commitment,
         amount,
+        milestone_allocations,
+        surplus_routed_to_pool: surplus,
         total_funded: treatment_pass.current_funding,
-        funding_percentage: treatment_pass.funding_percentage,
+        funding_basis_points: treatment_pass.funding_basis_points,
     });
     
     Ok(())
@@ -409,14 +1828,56 @@ pub fn verify_milestone(
     verification_evidence: Option<String>,
     ubuntu_community_validation: bool,
 ) -> Result<()> {
-    let treatment_pass_account = &mut ctx.accounts.treatment_pass_account;
-    let treatment_pass = &mut treatment_pass_account.treatment_pass;
     let clock = Clock::get()?;
+    let verifier_acts_for_patient = acts_for_patient(
+        &ctx.accounts.verifier.key(),
+        &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageMilestones,
+    )?;
+    let elder_council_approval = ctx.accounts.treatment_pass_account.treatment_pass.elder_council_approval;
+    
+    require!(
+        acts_for_patient(
+            &ctx.accounts.patient_token_account.owner,
+            &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+            ctx.accounts.payee_representative.as_deref(),
+            RepresentativeScope::ReceiveFunds,
+        )?,
+        ErrorCode::InvalidPayoutAccount
+    );
+    require!(
+        ctx.accounts.treatment_pass_account.treatment_pass.status == TreatmentPassStatus::TreatmentInProgress,
+        ErrorCode::TreatmentNotInProgress
+    );
+    require!(
+        verification_evidence.as_ref().map_or(0, |e| e.len()) <= MAX_EVIDENCE_LEN,
+        ErrorCode::EvidenceTooLong
+    );
     
     // Find and update the milestone
-    let milestone = treatment_pass.milestones.iter_mut()
+    let milestone = ctx.accounts.treatment_pass_account.treatment_pass.milestones.iter_mut()
         .find(|m| m.milestone_id == milestone_id)
         .ok_or(ErrorCode::MilestoneNotFound)?;
+    // Only milestones reported complete by the patient or care team can be verified
+    require!(
+        milestone.completion_status == MilestoneStatus::AwaitingVerification,
+        ErrorCode::InvalidMilestoneStatus
+    );
+    require!(
+        milestone.is_authorized_verifier(
+            ctx.accounts.verifier.key(),
+            ctx.accounts.verifier_entry.as_deref(),
+            ctx.accounts.elder_council.as_ref().map(|elder_council| &elder_council.council),
+            verifier_acts_for_patient,
+        ),
+        ErrorCode::UnauthorizedVerifier
+    );
+    // Integrated traditional and medical plans only pay out once the elder council has approved them
+    require!(
+        milestone.verification_required != VerificationType::IntegratedVerification || elder_council_approval,
+        ErrorCode::ElderCouncilApprovalRequired
+    );
     
     // Verify the milestone
     milestone.completion_status = MilestoneStatus::Verified;
@@ -428,21 +1889,42 @@ pub fn verify_milestone(
     // Release funds to patient
     let funding_to_release = milestone.funding_amount;
     
+    // Earmarked pledges cover their own milestone; any shortfall comes from the general pool
+    let pool_share = funding_to_release.saturating_sub(milestone.pledged_amount);
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    require!(
+        treatment_pass.unearmarked_funding >= pool_share,
+        ErrorCode::InsufficientFunding
+    );
+    treatment_pass.unearmarked_funding -= pool_share;
+    
+    let treatment_pass_account = &ctx.accounts.treatment_pass_account;
+    let pass_id_bytes = treatment_pass_account.treatment_pass.pass_id.to_le_bytes();
+    let seeds = &[
+        b"treatment_pass".as_ref(),
+        treatment_pass_account.treatment_pass.patient.as_ref(),
+        pass_id_bytes.as_ref(),
+        &[treatment_pass_account.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
     let cpi_accounts = token::Transfer {
         from: ctx.accounts.escrow_account.to_account_info(),
         to: ctx.accounts.patient_token_account.to_account_info(),
-        authority: ctx.accounts.escrow_account.to_account_info(), // Escrow authority
+        authority: treatment_pass_account.to_account_info(),
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
     token::transfer(cpi_ctx, funding_to_release)?;
     
-    // Check if all milestones are completed
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    // Treatment completes once every milestone is verified or closed out as overdue
     let all_completed = treatment_pass.milestones.iter()
-        .all(|m| m.completion_status == MilestoneStatus::Verified);
+        .all(|m| m.completion_status.is_closed());
     
     if all_completed {
-        treatment_pass.status = TreatmentPassStatus::TreatmentCompleted;
+        treatment_pass.transition_to(TreatmentPassStatus::TreatmentCompleted)?;
         
         emit!(TreatmentCompleted {
             pass_id: treatment_pass.pass_id,
@@ -466,6 +1948,367 @@ pub fn verify_milestone(
     Ok(())
 }
 
+/// Patient or care team reports a milestone complete, attaching an evidence hash for the verifier
+pub fn submit_milestone_completion(
+    ctx: Context<SubmitMilestoneCompletion>,
+    milestone_id: u8,
+    evidence_hash: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let submitter = ctx.accounts.submitter.key();
+    let submitter_acts_for_patient = acts_for_patient(
+        &submitter,
+        &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageMilestones,
+    )?;
+    
+    // Patient, their representative or an accredited provider on the care team
+    let care_team_member = ctx.accounts.submitter_entry.as_ref().map_or(false, |submitter_entry| {
+        submitter_entry.entry.is_active &&
+            submitter_entry.entry.verifier_kind == VerifierKind::AccreditedProvider
+    });
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    require!(
+        submitter_acts_for_patient || care_team_member,
+        ErrorCode::UnauthorizedMilestoneSubmitter
+    );
+    require!(
+        treatment_pass.status == TreatmentPassStatus::TreatmentInProgress,
+        ErrorCode::TreatmentNotInProgress
+    );
+    require!(!evidence_hash.is_empty(), ErrorCode::MissingCompletionEvidence);
+    require!(evidence_hash.len() <= MAX_EVIDENCE_LEN, ErrorCode::EvidenceTooLong);
+    
+    let milestone = treatment_pass.milestones.iter_mut()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    require!(
+        matches!(
+            milestone.completion_status,
+            MilestoneStatus::NotStarted | MilestoneStatus::InProgress
+        ),
+        ErrorCode::InvalidMilestoneStatus
+    );
+    
+    milestone.completion_status = MilestoneStatus::AwaitingVerification;
+    milestone.completion_evidence = Some(evidence_hash.clone());
+    
+    emit!(MilestoneCompletionSubmitted {
+        pass_id: treatment_pass.pass_id,
+        milestone_id,
+        submitted_by: submitter,
+        evidence_hash,
+        submitted_at: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Verifier sends a reported milestone back to the care team with a reason
+pub fn reject_milestone_completion(
+    ctx: Context<RejectMilestoneCompletion>,
+    milestone_id: u8,
+    reason: String,
+) -> Result<()> {
+    require!(!reason.is_empty(), ErrorCode::MissingRejectionReason);
+    require!(reason.len() <= MAX_STATUS_REASON_LEN, ErrorCode::StatusReasonTooLong);
+    
+    let verifier = ctx.accounts.verifier.key();
+    let verifier_entry = ctx.accounts.verifier_entry.as_deref();
+    let elder_council = ctx.accounts.elder_council.as_ref().map(|elder_council| &elder_council.council);
+    let verifier_acts_for_patient = acts_for_patient(
+        &verifier,
+        &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageMilestones,
+    )?;
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    
+    let milestone = treatment_pass.milestones.iter_mut()
+        .find(|m| m.milestone_id == milestone_id)
+        .ok_or(ErrorCode::MilestoneNotFound)?;
+    require!(
+        milestone.completion_status == MilestoneStatus::AwaitingVerification,
+        ErrorCode::InvalidMilestoneStatus
+    );
+    require!(
+        milestone.is_authorized_verifier(verifier, verifier_entry, elder_council, verifier_acts_for_patient),
+        ErrorCode::UnauthorizedVerifier
+    );
+    
+    milestone.completion_status = MilestoneStatus::InProgress;
+    
+    emit!(MilestoneCompletionRejected {
+        pass_id: treatment_pass.pass_id,
+        milestone_id,
+        rejected_by: verifier,
+        reason,
+    });
+    
+    Ok(())
+}
+
+/// Permissionless crank that expires an underfunded pass once its deadline has passed
+pub fn expire_treatment_pass(ctx: Context<ExpireTreatmentPass>) -> Result<()> {
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let clock = Clock::get()?;
+    
+    require!(
+        matches!(
+            treatment_pass.status,
+            TreatmentPassStatus::FundingRequired | TreatmentPassStatus::PartiallyFunded
+        ),
+        ErrorCode::TreatmentPassNotExpirable
+    );
+    require!(
+        clock.unix_timestamp > treatment_pass.funding_deadline,
+        ErrorCode::FundingDeadlineNotReached
+    );
+    require!(
+        treatment_pass.current_funding < treatment_pass.funding_target,
+        ErrorCode::TreatmentPassNotExpirable
+    );
+    
+    treatment_pass.transition_to(TreatmentPassStatus::FundingExpired)?;
+    
+    emit!(TreatmentPassExpired {
+        pass_id: treatment_pass.pass_id,
+        funding_target: treatment_pass.funding_target,
+        current_funding: treatment_pass.current_funding,
+        funding_deadline: treatment_pass.funding_deadline,
+    });
+    
+    Ok(())
+}
+
+/// Patient or their representative assigns the accredited provider who will deliver the treatment
+pub fn assign_treating_provider(ctx: Context<AssignTreatingProvider>) -> Result<()> {
+    require!(
+        acts_for_patient(
+            &ctx.accounts.authority.key(),
+            &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+            ctx.accounts.patient_representative.as_deref(),
+            RepresentativeScope::ManageTreatment,
+        )?,
+        ErrorCode::UnauthorizedPatientAction
+    );
+    
+    let provider_entry = &ctx.accounts.provider_entry.entry;
+    require!(
+        provider_entry.is_active && provider_entry.verifier_kind == VerifierKind::AccreditedProvider,
+        ErrorCode::ProviderNotAccredited
+    );
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    require!(
+        !matches!(
+            treatment_pass.status,
+            TreatmentPassStatus::TreatmentCompleted |
+                TreatmentPassStatus::TreatmentCancelled |
+                TreatmentPassStatus::FundingExpired
+        ),
+        ErrorCode::InvalidStatusTransition
+    );
+    treatment_pass.treating_provider = Some(provider_entry.verifier);
+    
+    emit!(TreatingProviderAssigned {
+        pass_id: treatment_pass.pass_id,
+        provider: provider_entry.verifier,
+        assigned_by: ctx.accounts.authority.key(),
+    });
+    
+    Ok(())
+}
+
+/// Assigned provider starts treatment on a fully funded pass
+pub fn start_treatment(ctx: Context<UpdateTreatmentStatus>) -> Result<()> {
+    require!(ctx.accounts.is_treating_provider(), ErrorCode::UnauthorizedStatusChange);
+    
+    change_treatment_status(ctx, TreatmentPassStatus::TreatmentInProgress, None)
+}
+
+/// Patient, their representative or assigned provider pauses treatment for medical reasons
+pub fn pause_treatment(ctx: Context<UpdateTreatmentStatus>, reason: String) -> Result<()> {
+    require!(
+        ctx.accounts.acts_for_patient()? || ctx.accounts.is_treating_provider(),
+        ErrorCode::UnauthorizedStatusChange
+    );
+    
+    change_treatment_status(ctx, TreatmentPassStatus::TreatmentPaused, Some(reason))
+}
+
+/// Patient, their representative or assigned provider resumes paused treatment
+pub fn resume_treatment(ctx: Context<UpdateTreatmentStatus>) -> Result<()> {
+    require!(
+        ctx.accounts.acts_for_patient()? || ctx.accounts.is_treating_provider(),
+        ErrorCode::UnauthorizedStatusChange
+    );
+    
+    change_treatment_status(ctx, TreatmentPassStatus::TreatmentInProgress, None)
+}
+
+/// Patient or their representative cancels treatment, opening sponsor refunds; the elder council cancels through a quorum review
+pub fn cancel_treatment(ctx: Context<UpdateTreatmentStatus>, reason: String) -> Result<()> {
+    require!(ctx.accounts.acts_for_patient()?, ErrorCode::UnauthorizedStatusChange);
+    
+    change_treatment_status(ctx, TreatmentPassStatus::TreatmentCancelled, Some(reason))
+}
+
+/// Patient or their representative refers a pass still raising funds, or one under community review, to the elder council
+pub fn request_elder_council_review(ctx: Context<UpdateTreatmentStatus>, reason: String) -> Result<()> {
+    require!(ctx.accounts.acts_for_patient()?, ErrorCode::UnauthorizedStatusChange);
+    
+    change_treatment_status(ctx, TreatmentPassStatus::ElderCouncilReview, Some(reason))
+}
+
+fn change_treatment_status(
+    ctx: Context<UpdateTreatmentStatus>,
+    next: TreatmentPassStatus,
+    reason: Option<String>,
+) -> Result<()> {
+    require!(
+        reason.as_ref().map_or(0, |r| r.len()) <= MAX_STATUS_REASON_LEN,
+        ErrorCode::StatusReasonTooLong
+    );
+    
+    let treatment_pass = &mut ctx.accounts.treatment_pass_account.treatment_pass;
+    let previous_status = treatment_pass.status.clone();
+    treatment_pass.transition_to(next.clone())?;
+    
+    emit!(TreatmentStatusChanged {
+        pass_id: treatment_pass.pass_id,
+        previous_status,
+        new_status: next,
+        changed_by: ctx.accounts.authority.key(),
+        reason,
+        changed_at: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Anonymous sponsor proves authorship of their contribution (e.g. for a tax receipt),
+/// optionally making their wallet public on the contribution record
+pub fn reveal_sponsor_identity(
+    ctx: Context<RevealSponsorIdentity>,
+    salt: [u8; 32],
+    make_public: bool,
+) -> Result<()> {
+    let treatment_pass_key = ctx.accounts.treatment_pass_account.key();
+    let sponsor_key = ctx.accounts.sponsor.key();
+    let contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+    
+    let commitment = contribution.identity_commitment.ok_or(ErrorCode::SponsorNotAnonymous)?;
+    require!(
+        SponsorContribution::identity_commitment(&treatment_pass_key, &sponsor_key, &salt) == commitment,
+        ErrorCode::InvalidIdentityProof
+    );
+    
+    if make_public {
+        contribution.wallet = sponsor_key;
+        contribution.anonymous = false;
+        contribution.identity_commitment = None;
+    }
+    
+    emit!(SponsorIdentityRevealed {
+        pass_id: ctx.accounts.treatment_pass_account.treatment_pass.pass_id,
+        sponsor: sponsor_key,
+        sponsor_commitment: commitment,
+        amount_contributed: contribution.amount_contributed,
+        made_public: make_public,
+    });
+    
+    Ok(())
+}
+
+/// Masks a sponsor message while keeping its hash on the contribution record
+pub fn hide_sponsor_message(ctx: Context<HideSponsorMessage>) -> Result<()> {
+    let moderator = ctx.accounts.moderator.key();
+    let is_patient = acts_for_patient(
+        &moderator,
+        &ctx.accounts.treatment_pass_account.treatment_pass.patient,
+        ctx.accounts.patient_representative.as_deref(),
+        RepresentativeScope::ManageTreatment,
+    )?;
+    let is_platform_moderator = ctx.accounts.registry_account.as_ref()
+        .map_or(false, |registry_account| registry_account.registry.platform_authority == moderator);
+    require!(is_patient || is_platform_moderator, ErrorCode::UnauthorizedMessageModerator);
+    
+    let sponsor_contribution_key = ctx.accounts.sponsor_contribution.key();
+    let contribution = &mut ctx.accounts.sponsor_contribution.contribution;
+    require!(contribution.sponsor_message.is_some(), ErrorCode::NoSponsorMessage);
+    
+    contribution.sponsor_message = None;
+    contribution.sponsor_message_hidden = true;
+    
+    emit!(SponsorMessageHidden {
+        pass_id: ctx.accounts.treatment_pass_account.treatment_pass.pass_id,
+        sponsor_contribution: sponsor_contribution_key,
+        message_hash: contribution.sponsor_message_hash,
+        hidden_by: moderator,
+    });
+    
+    Ok(())
+}
+
+pub fn claim_sponsor_refund(ctx: Context<ClaimSponsorRefund>) -> Result<()> {
+    let treatment_pass = &ctx.accounts.treatment_pass_account.treatment_pass;
+    let clock = Clock::get()?;
+    
+    require!(
+        treatment_pass.status.allows_sponsor_refunds(),
+        ErrorCode::RefundNotAvailable
+    );
+    
+    let sponsor = &mut ctx.accounts.sponsor_contribution.contribution;
+    
+    require!(!sponsor.refund_claimed, ErrorCode::RefundAlreadyClaimed);
+    
+    let refund_amount = treatment_pass.refund_amount(sponsor)?;
+    
+    // Unreleased earmarks are settled by this refund, so they cannot also be claimed as failed milestone refunds
+    sponsor.refund_claimed = true;
+    for allocation in sponsor.milestone_allocations.iter_mut() {
+        if treatment_pass.earmark_unreleased(allocation.milestone_id) {
+            allocation.refunded = true;
+        }
+    }
+    let refunded_sponsor = sponsor.public_wallet();
+    let pass_id = treatment_pass.pass_id;
+    
+    if refund_amount > 0 {
+        let treatment_pass_account = &ctx.accounts.treatment_pass_account;
+        let pass_id_bytes = pass_id.to_le_bytes();
+        let seeds = &[
+            b"treatment_pass".as_ref(),
+            treatment_pass_account.treatment_pass.patient.as_ref(),
+            pass_id_bytes.as_ref(),
+            &[treatment_pass_account.bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        // Transfer refund from escrow back to sponsor
+        let cpi_accounts = token::Transfer {
+            from: ctx.accounts.escrow_account.to_account_info(),
+            to: ctx.accounts.sponsor_token_account.to_account_info(),
+            authority: treatment_pass_account.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, refund_amount)?;
+    }
+    
+    emit!(SponsorRefundClaimed {
+        pass_id,
+        sponsor: refunded_sponsor,
+        refund_amount,
+        claimed_at: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Overflow occurred")]
@@ -480,4 +2323,134 @@ pub enum ErrorCode {
     UnauthorizedVerifier,
     #[msg("Treatment pass already completed")]
     TreatmentPassCompleted,
+    #[msg("Escrow account is not controlled by this treatment pass")]
+    InvalidEscrowAccount,
+    #[msg("Refund token account does not belong to the sponsor")]
+    InvalidRefundAccount,
+    #[msg("Payout token account does not belong to the patient or a representative authorized to receive funds")]
+    InvalidPayoutAccount,
+    #[msg("Refunds are only available for cancelled or expired treatment passes")]
+    RefundNotAvailable,
+    #[msg("Sponsor refund already claimed")]
+    RefundAlreadyClaimed,
+    #[msg("Funding deadline must be in the future and within the maximum funding period")]
+    InvalidFundingDeadline,
+    #[msg("Funding deadline has passed")]
+    FundingDeadlinePassed,
+    #[msg("Funding deadline has not been reached")]
+    FundingDeadlineNotReached,
+    #[msg("Treatment pass cannot be expired in its current state")]
+    TreatmentPassNotExpirable,
+    #[msg("Treatment title exceeds maximum length")]
+    TreatmentTitleTooLong,
+    #[msg("Treatment description exceeds maximum length")]
+    TreatmentDescriptionTooLong,
+    #[msg("Treatment category exceeds maximum length")]
+    TreatmentCategoryTooLong,
+    #[msg("Too many milestones")]
+    TooManyMilestones,
+    #[msg("Treatment pass requires at least one milestone")]
+    NoMilestones,
+    #[msg("Milestone id appears more than once")]
+    DuplicateMilestoneId,
+    #[msg("Milestone ids must be in ascending order")]
+    MilestoneIdsNotOrdered,
+    #[msg("Milestone funding amount must be greater than zero")]
+    InvalidMilestoneAmount,
+    #[msg("Milestone funding amounts must add up to the funding target")]
+    MilestoneTotalMismatch,
+    #[msg("Milestone expected completion must be in the future")]
+    MilestoneCompletionInPast,
+    #[msg("New milestones must start as not started")]
+    MilestoneAlreadyStarted,
+    #[msg("Milestone description exceeds maximum length")]
+    MilestoneDescriptionTooLong,
+    #[msg("Verification evidence exceeds maximum length")]
+    EvidenceTooLong,
+    #[msg("Sponsor message exceeds maximum length")]
+    SponsorMessageTooLong,
+    #[msg("Milestone allocation amount must be greater than zero")]
+    InvalidMilestoneAllocation,
+    #[msg("Milestone appears more than once in the allocation")]
+    DuplicateMilestoneAllocation,
+    #[msg("Milestone allocations exceed the sponsored amount")]
+    AllocationExceedsContribution,
+    #[msg("Pledges would exceed the milestone's funding amount")]
+    MilestoneOverPledged,
+    #[msg("Treatment pass status does not allow this transition")]
+    InvalidStatusTransition,
+    #[msg("Signer is not allowed to change this treatment's status")]
+    UnauthorizedStatusChange,
+    #[msg("Status change reason exceeds maximum length")]
+    StatusReasonTooLong,
+    #[msg("Treatment pass is not accepting sponsorships")]
+    TreatmentPassNotFundable,
+    #[msg("Treatment is not in progress")]
+    TreatmentNotInProgress,
+    #[msg("Only the patient, their representative or care team may report milestone completion")]
+    UnauthorizedMilestoneSubmitter,
+    #[msg("Milestone completion requires an evidence hash")]
+    MissingCompletionEvidence,
+    #[msg("Rejecting a milestone requires a reason")]
+    MissingRejectionReason,
+    #[msg("Overdue grace period is outside the allowed range")]
+    InvalidGracePeriod,
+    #[msg("Amendment approval share is outside the allowed range")]
+    InvalidAmendmentApprovalShare,
+    #[msg("Funding target must be greater than zero")]
+    InvalidFundingTarget,
+    #[msg("Sponsorship amount must be greater than zero")]
+    InvalidSponsorshipAmount,
+    #[msg("Treatment pass has already reached its funding target")]
+    FundingTargetReached,
+    #[msg("Sponsorship would exceed the treatment pass funding target")]
+    FundingTargetExceeded,
+    #[msg("Sponsorship must use the same identity as the sponsor's earlier contributions")]
+    AnonymityMismatch,
+    #[msg("Contribution was not made anonymously")]
+    SponsorNotAnonymous,
+    #[msg("Salt does not open the identity commitment")]
+    InvalidIdentityProof,
+    #[msg("Only the patient, their representative or the platform moderator may hide sponsor messages")]
+    UnauthorizedMessageModerator,
+    #[msg("Contribution has no visible sponsor message")]
+    NoSponsorMessage,
+    #[msg("A message on this contribution was hidden, so it accepts no new messages")]
+    SponsorMessagesBlocked,
+    #[msg("Contribution record does not belong to this treatment pass")]
+    ContributionPassMismatch,
+    #[msg("Endorsement requires the endorser, their member profile and their reputation account")]
+    IncompleteEndorsement,
+    #[msg("Patients cannot endorse their own treatment pass")]
+    SelfEndorsement,
+    #[msg("Endorser is not an active Ubuntu community member")]
+    EndorserInactive,
+    #[msg("Endorser has not been verified by the elder council")]
+    EndorserNotElderVerified,
+    #[msg("Endorsement stake must be paid in $LIVES")]
+    InvalidStakeMint,
+    #[msg("Community overflow pool accounts are required to route surplus funding")]
+    CommunityPoolRequired,
+    #[msg("Token account is not the community overflow pool vault")]
+    InvalidCommunityPoolVault,
+    #[msg("Traditional healing details exceed maximum length")]
+    TraditionalHealingTooLong,
+    #[msg("Traditional healing knowledge exceeds maximum length")]
+    HealingKnowledgeTooLong,
+    #[msg("Community name exceeds maximum length")]
+    CommunityNameTooLong,
+    #[msg("Diaspora connection strength must be between 0 and 100")]
+    InvalidConnectionStrength,
+    #[msg("Too many diaspora network contacts")]
+    TooManyNetworkContacts,
+    #[msg("Milestone requires elder council approval of the treatment")]
+    ElderCouncilApprovalRequired,
+    #[msg("Signer is neither the patient nor an appointed representative")]
+    UnauthorizedPatientAction,
+    #[msg("Signer is not the sponsor of this contribution")]
+    UnauthorizedSponsor,
+    #[msg("Traditional healing knowledge must not be empty")]
+    MissingHealingKnowledge,
+    #[msg("Provider is not an active accredited provider")]
+    ProviderNotAccredited,
 }
@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+
+use crate::treatment_pass_nft::VerificationType;
+
+/// On-chain registry of accredited milestone verifiers
+/// Managed by the platform authority and consulted before milestone funds are released
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifierRegistry {
+    /// Authority allowed to register and deactivate verifiers
+    pub platform_authority: Pubkey,
+    /// Total verifiers ever registered
+    pub total_verifiers: u32,
+    /// Registry creation timestamp
+    pub created_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifierEntry {
+    /// Verifier's wallet public key
+    pub verifier: Pubkey,
+    /// Kind of accreditation held by the verifier
+    pub verifier_kind: VerifierKind,
+    /// Accreditation reference (licence number, certification IPFS hash, etc.)
+    pub credential: String,
+    /// Whether the verifier may currently verify milestones
+    pub is_active: bool,
+    /// Registration timestamp
+    pub registered_at: i64,
+    /// Authority that registered this verifier
+    pub registered_by: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum VerifierKind {
+    /// Accredited hospital, clinic or physician
+    AccreditedProvider,
+    /// Accredited laboratory or imaging centre
+    AccreditedLaboratory,
+    /// Recognised community witness
+    CommunityWitness,
+}
+
+impl VerifierKind {
+    /// Whether this kind of verifier may confirm a milestone of the given verification type
+    pub fn can_verify(&self, verification_type: &VerificationType) -> bool {
+        match verification_type {
+            VerificationType::MedicalProvider => *self == VerifierKind::AccreditedProvider,
+            VerificationType::ThirdPartyEvidence => *self == VerifierKind::AccreditedLaboratory,
+            VerificationType::CommunityWitness => *self == VerifierKind::CommunityWitness,
+            // Integrated plans are confirmed by a provider once the elder council has approved them
+            VerificationType::IntegratedVerification => *self == VerifierKind::AccreditedProvider,
+            // Elder verification goes through council membership, not the verifier registry
+            VerificationType::ElderCouncilVerification => false,
+            // Patient reports may be confirmed by any registered verifier
+            VerificationType::PatientReported => true,
+        }
+    }
+}
+
+/// Maximum length of a verifier credential reference
+pub const MAX_CREDENTIAL_LEN: usize = 128;
+
+#[account]
+pub struct VerifierRegistryAccount {
+    pub registry: VerifierRegistry,
+    pub bump: u8,
+}
+
+impl VerifierRegistryAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // platform_authority
+        4 + // total_verifiers
+        8 + // created_at
+        1; // bump
+}
+
+#[account]
+pub struct VerifierEntryAccount {
+    pub entry: VerifierEntry,
+    pub bump: u8,
+}
+
+impl VerifierEntryAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // verifier
+        1 + // verifier_kind
+        4 + MAX_CREDENTIAL_LEN + // credential
+        1 + // is_active
+        8 + // registered_at
+        32 + // registered_by
+        1; // bump
+}
+
+/// Instructions for verifier registry operations
+#[derive(Accounts)]
+pub struct InitializeVerifierRegistry<'info> {
+    #[account(
+        init,
+        payer = platform_authority,
+        space = VerifierRegistryAccount::LEN,
+        seeds = [b"verifier_registry"],
+        bump
+    )]
+    pub registry_account: Account<'info, VerifierRegistryAccount>,
+    
+    #[account(mut)]
+    pub platform_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(verifier: Pubkey)]
+pub struct RegisterVerifier<'info> {
+    #[account(
+        mut,
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump,
+        constraint = registry_account.registry.platform_authority == platform_authority.key() @ ErrorCode::UnauthorizedRegistryAuthority
+    )]
+    pub registry_account: Account<'info, VerifierRegistryAccount>,
+    
+    #[account(
+        init,
+        payer = platform_authority,
+        space = VerifierEntryAccount::LEN,
+        seeds = [b"verifier", verifier.as_ref()],
+        bump
+    )]
+    pub verifier_entry: Account<'info, VerifierEntryAccount>,
+    
+    #[account(mut)]
+    pub platform_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVerifierStatus<'info> {
+    #[account(
+        seeds = [b"verifier_registry"],
+        bump = registry_account.bump,
+        constraint = registry_account.registry.platform_authority == platform_authority.key() @ ErrorCode::UnauthorizedRegistryAuthority
+    )]
+    pub registry_account: Account<'info, VerifierRegistryAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier_entry.entry.verifier.as_ref()],
+        bump = verifier_entry.bump
+    )]
+    pub verifier_entry: Account<'info, VerifierEntryAccount>,
+    
+    pub platform_authority: Signer<'info>,
+}
+
+/// Verifier registry instruction implementations
+pub fn initialize_verifier_registry(ctx: Context<InitializeVerifierRegistry>) -> Result<()> {
+    let registry_account = &mut ctx.accounts.registry_account;
+    registry_account.bump = ctx.bumps.registry_account;
+    
+    registry_account.registry = VerifierRegistry {
+        platform_authority: ctx.accounts.platform_authority.key(),
+        total_verifiers: 0,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+    
+    emit!(VerifierRegistryInitialized {
+        platform_authority: ctx.accounts.platform_authority.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn register_verifier(
+    ctx: Context<RegisterVerifier>,
+    verifier: Pubkey,
+    verifier_kind: VerifierKind,
+    credential: String,
+) -> Result<()> {
+    require!(
+        credential.len() <= MAX_CREDENTIAL_LEN,
+        ErrorCode::CredentialTooLong
+    );
+    
+    let registry_account = &mut ctx.accounts.registry_account;
+    registry_account.registry.total_verifiers = registry_account.registry.total_verifiers
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+    
+    let verifier_entry = &mut ctx.accounts.verifier_entry;
+    verifier_entry.bump = ctx.bumps.verifier_entry;
+    verifier_entry.entry = VerifierEntry {
+        verifier,
+        verifier_kind: verifier_kind.clone(),
+        credential,
+        is_active: true,
+        registered_at: Clock::get()?.unix_timestamp,
+        registered_by: ctx.accounts.platform_authority.key(),
+    };
+    
+    emit!(VerifierRegistered {
+        verifier,
+        verifier_kind,
+        registered_by: ctx.accounts.platform_authority.key(),
+    });
+    
+    Ok(())
+}
+
+pub fn set_verifier_status(ctx: Context<SetVerifierStatus>, is_active: bool) -> Result<()> {
+    let verifier_entry = &mut ctx.accounts.verifier_entry;
+    verifier_entry.entry.is_active = is_active;
+    
+    emit!(VerifierStatusUpdated {
+        verifier: verifier_entry.entry.verifier,
+        is_active,
+        updated_by: ctx.accounts.platform_authority.key(),
+    });
+    
+    Ok(())
+}
+
+/// Events for verifier registry operations
+#[event]
+pub struct VerifierRegistryInitialized {
+    pub platform_authority: Pubkey,
+}
+
+#[event]
+pub struct VerifierRegistered {
+    pub verifier: Pubkey,
+    pub verifier_kind: VerifierKind,
+    pub registered_by: Pubkey,
+}
+
+#[event]
+pub struct VerifierStatusUpdated {
+    pub verifier: Pubkey,
+    pub is_active: bool,
+    pub updated_by: Pubkey,
+}
+
+/// Custom error codes for verifier registry operations
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Overflow occurred")]
+    Overflow,
+    #[msg("Signer is not the verifier registry authority")]
+    UnauthorizedRegistryAuthority,
+    #[msg("Verifier credential exceeds maximum length")]
+    CredentialTooLong,
+}
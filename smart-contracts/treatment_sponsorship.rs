@@ -3,6 +3,7 @@
 // Built on Solana using Anchor Framework
 
 use anchor_lang::prelude::*;
+use patient_representatives::RepresentativeScope;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 declare_id!("UbuntuHealthTreatmentSponsorship11111111111");
@@ -158,10 +159,15 @@ pub mod treatment_sponsorship {
         research_consent: bool,
     ) -> Result<()> {
         let treatment_request = &mut ctx.accounts.treatment_request;
-        let patient = &ctx.accounts.patient;
         
+        // Patient or a caregiver holding their LogRecovery delegation
         require!(
-            treatment_request.patient == patient.key(),
+            acts_for_patient(
+                &ctx.accounts.patient.key(),
+                &treatment_request.patient,
+                ctx.accounts.patient_representative.as_deref(),
+                RepresentativeScope::LogRecovery,
+            )?,
             TreatmentError::UnauthorizedPatient
         );
         
@@ -180,6 +186,22 @@ pub mod treatment_sponsorship {
     }
 }
 
+// Whether `signer` is the patient or holds a current core-program appointment from them covering `scope`
+fn acts_for_patient(
+    signer: &Pubkey,
+    patient: &Pubkey,
+    representative: Option<&AccountInfo>,
+    scope: RepresentativeScope,
+) -> Result<bool> {
+    patient_representatives::acts_for_patient(
+        signer,
+        patient,
+        representative,
+        scope,
+        TreatmentError::InvalidPatientRepresentative,
+    )
+}
+
 // Account structures
 #[derive(Accounts)]
 pub struct CreateTreatmentRequest<'info> {
@@ -244,6 +266,9 @@ pub struct ReportOutcome<'info> {
     pub treatment_request: Account<'info, TreatmentRequest>,
     
     pub patient: Signer<'info>,
+    
+    /// CHECK: Signer's appointment as the patient's representative, validated by `patient_representatives::PatientRepresentative::load`
+    pub patient_representative: Option<UncheckedAccount<'info>>,
 }
 
 // Data structures
@@ -345,4 +370,7 @@ pub enum TreatmentError {
     
     #[msg("Unauthorized patient")]
     UnauthorizedPatient,
+    
+    #[msg("Account is not a patient representative appointment from the Ubuntu Health core program")]
+    InvalidPatientRepresentative,
 }
\ No newline at end of file
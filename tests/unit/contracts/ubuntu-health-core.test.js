@@ -7,7 +7,462 @@
 
 const anchor = require('@coral-xyz/anchor');
 const { expect } = require('chai');
-const { Connection, Keypair, PublicKey } = require('@solana/web3.js');
+const { Connection, Keypair, PublicKey, Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY, SYSVAR_RENT_PUBKEY } = require('@solana/web3.js');
+const { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo, getAccount } = require('@solana/spl-token');
+const nacl = require('tweetnacl');
+const { createHash } = require('crypto');
+
+const DAY_SECONDS = 24 * 60 * 60;
+const ATTESTATION_DOMAIN = Buffer.from('UBUNTU_HEALTH_MILESTONE_ATTESTATION');
+const HEALTHCARE_PROVIDER_DISCRIMINANT = 0;
+const MEMBER_REGISTRY_PROGRAM_ID = new PublicKey('UbuntuMemberRegistry11111111111111111111111');
+
+async function airdrop(connection, publicKey, sol) {
+  const signature = await connection.requestAirdrop(publicKey, sol * anchor.web3.LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(signature);
+}
+
+async function chainTime(connection) {
+  return connection.getBlockTime(await connection.getSlot());
+}
+
+async function waitForChainTime(connection, timestamp) {
+  while ((await chainTime(connection)) <= timestamp) {
+    await new Promise(resolve => setTimeout(resolve, 1000));
+  }
+}
+
+function milestone(milestoneId, fundingAmount, expectedCompletion) {
+  return {
+    milestoneId,
+    description: `Treatment milestone ${milestoneId}`,
+    fundingAmount: new anchor.BN(fundingAmount),
+    pledgedAmount: new anchor.BN(0),
+    verificationRequired: { medicalProvider: {} },
+    completionStatus: { notStarted: {} },
+    expectedCompletion: new anchor.BN(expectedCompletion),
+    completionTimestamp: null,
+    overdueSince: null,
+    overdueRound: 0,
+    reallocatedTo: null,
+    verifyingProvider: null,
+    completionEvidence: null,
+    verificationEvidence: null,
+    cryptographicProof: null,
+    elderCouncilReview: null,
+    ubuntuCommunityValidation: false,
+  };
+}
+
+async function treatmentPassAddress(program, patient, passId) {
+  const [treatmentPassPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('treatment_pass'), patient.toBuffer(), new anchor.BN(passId).toArrayLike(Buffer, 'le', 8)],
+    program.programId
+  );
+  return treatmentPassPDA;
+}
+
+async function passEscrowAddress(program, treatmentPassPDA) {
+  const [escrowPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('treatment_pass_escrow'), treatmentPassPDA.toBuffer()],
+    program.programId
+  );
+  return escrowPDA;
+}
+
+async function representativeAddress(program, patient, representative) {
+  const [representativePDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('patient_representative'), patient.toBuffer(), representative.toBuffer()],
+    program.programId
+  );
+  return representativePDA;
+}
+
+async function createTreatmentPass(program, {
+  patient,
+  creator,
+  passId,
+  amounts,
+  fundingMint,
+  fundingPeriod = 30 * DAY_SECONDS,
+  title = 'Community-funded hip replacement',
+  category = 'orthopaedics',
+  overfundingPolicy = { reject: {} },
+  milestones = null,
+  fundingTarget = null,
+  patientRepresentative = null,
+  endorserKeypair = null,
+  endorserAccounts = {},
+  traditionalHealing = null,
+}) {
+  const connection = program.provider.connection;
+  const now = await chainTime(connection);
+  const treatmentPassPDA = await treatmentPassAddress(program, patient, passId);
+  const schedule = milestones || amounts.map((amount, index) => milestone(index + 1, amount, now + (60 + index) * DAY_SECONDS));
+  const target = fundingTarget === null
+    ? schedule.reduce((total, m) => total + m.fundingAmount.toNumber(), 0)
+    : fundingTarget;
+
+  await program.methods
+    .createTreatmentPass(
+      new anchor.BN(passId),
+      title,
+      'Surgery and rehabilitation supported by the Ubuntu community',
+      category,
+      new anchor.BN(target),
+      new anchor.BN(now + fundingPeriod),
+      overfundingPolicy,
+      new anchor.BN(7 * DAY_SECONDS),
+      6000,
+      schedule,
+      traditionalHealing
+    )
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      patient,
+      creator: creator.publicKey,
+      patientRepresentative,
+      fundingMint,
+      escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+      ubuntuEndorser: endorserKeypair && endorserKeypair.publicKey,
+      endorserProfile: null,
+      endorserReputation: null,
+      livesConfig: null,
+      livesTokenMint: null,
+      endorserTokenAccount: null,
+      endorsementStake: null,
+      stakeVault: null,
+      ...endorserAccounts,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers(endorserKeypair ? [creator, endorserKeypair] : [creator])
+    .rpc();
+
+  return treatmentPassPDA;
+}
+
+function sha256(...parts) {
+  return createHash('sha256').update(Buffer.concat(parts)).digest();
+}
+
+function identityCommitment(treatmentPassPDA, sponsor, identitySalt) {
+  return sha256(treatmentPassPDA.toBuffer(), sponsor.toBuffer(), identitySalt);
+}
+
+async function sponsorContributionAddress(program, treatmentPassPDA, sponsor, identitySalt = null) {
+  // Anonymous records are addressed by a hash of the sponsor's commitment rather than their wallet
+  const recordSeed = identitySalt
+    ? sha256(Buffer.from('anonymous_contribution'), identityCommitment(treatmentPassPDA, sponsor, identitySalt))
+    : sponsor.toBuffer();
+  const [contributionPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('sponsor_contribution'), treatmentPassPDA.toBuffer(), recordSeed],
+    program.programId
+  );
+  return contributionPDA;
+}
+
+async function sponsorTreatment(program, {
+  treatmentPassPDA,
+  sponsorKeypair,
+  sponsorTokenAccount,
+  amount,
+  milestoneAllocations = [],
+  communityPool = null,
+  communityPoolVault = null,
+  identitySalt = null,
+  sponsorContribution = null,
+  sponsorMessage = null,
+}) {
+  await program.methods
+    .sponsorTreatment(new anchor.BN(amount), sponsorMessage, milestoneAllocations, identitySalt && Array.from(identitySalt))
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      sponsorContribution: sponsorContribution ||
+        await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey, identitySalt),
+      sponsor: sponsorKeypair.publicKey,
+      payer: sponsorKeypair.publicKey,
+      escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+      sponsorTokenAccount,
+      communityPool,
+      communityPoolVault,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([sponsorKeypair])
+    .rpc();
+}
+
+async function claimSponsorRefund(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount }) {
+  await program.methods
+    .claimSponsorRefund(null)
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey),
+      sponsor: sponsorKeypair.publicKey,
+      escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+      sponsorTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([sponsorKeypair])
+    .rpc();
+}
+
+async function fundedTokenAccount(connection, payer, fundingMint, owner, amount) {
+  const tokenAccount = await createAccount(connection, payer, fundingMint, owner);
+  if (amount > 0) {
+    await mintTo(connection, payer, fundingMint, tokenAccount, payer, amount);
+  }
+  return tokenAccount;
+}
+
+async function verifierEntryAddress(program, verifier) {
+  const [verifierEntryPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('verifier'), verifier.toBuffer()],
+    program.programId
+  );
+  return verifierEntryPDA;
+}
+
+async function verifierRegistry(program) {
+  const [registryPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('verifier_registry')],
+    program.programId
+  );
+
+  // The registry is a singleton; only the first suite to run creates it
+  if (!(await program.account.verifierRegistryAccount.fetchNullable(registryPDA))) {
+    await program.methods
+      .initializeVerifierRegistry()
+      .accounts({
+        registryAccount: registryPDA,
+        platformAuthority: program.provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return registryPDA;
+}
+
+async function registerVerifier(program, verifier, verifierKind, credential) {
+  const provider = program.provider;
+  const registryPDA = await verifierRegistry(program);
+  const verifierEntryPDA = await verifierEntryAddress(program, verifier);
+  await program.methods
+    .registerVerifier(verifier, verifierKind, credential)
+    .accounts({
+      registryAccount: registryPDA,
+      verifierEntry: verifierEntryPDA,
+      platformAuthority: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .rpc();
+  return verifierEntryPDA;
+}
+
+async function startTreatment(program, { treatmentPassPDA, patientKeypair, providerKeypair }) {
+  const providerEntry = await verifierEntryAddress(program, providerKeypair.publicKey);
+
+  await program.methods
+    .assignTreatingProvider()
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      authority: patientKeypair.publicKey,
+      patientRepresentative: null,
+      providerEntry,
+    })
+    .signers([patientKeypair])
+    .rpc();
+
+  await program.methods
+    .startTreatment()
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      authority: providerKeypair.publicKey,
+      authorityEntry: providerEntry,
+      patientRepresentative: null,
+    })
+    .signers([providerKeypair])
+    .rpc();
+}
+
+async function submitMilestoneCompletion(program, { treatmentPassPDA, patientKeypair, milestoneId }) {
+  await program.methods
+    .submitMilestoneCompletion(milestoneId, `evidence-hash-${milestoneId}`)
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      submitter: patientKeypair.publicKey,
+      submitterEntry: null,
+      patientRepresentative: null,
+    })
+    .signers([patientKeypair])
+    .rpc();
+}
+
+async function verifyMilestone(program, { treatmentPassPDA, verifierKeypair, verifierEntry, patientTokenAccount, milestoneId }) {
+  const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+
+  await program.methods
+    .verifyMilestone(milestoneId, null, true)
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      verifier: verifierKeypair.publicKey,
+      verifierEntry,
+      elderCouncil: null,
+      patientRepresentative: null,
+      patient: treatmentPass.patient,
+      escrowAccount: treatmentPass.escrow,
+      patientTokenAccount,
+      payeeRepresentative: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([verifierKeypair])
+    .rpc();
+}
+
+async function createMilestoneEscrow(program, {
+  patientKeypair,
+  passId,
+  fundingMint,
+  requirements,
+  emergencyReleasers = [],
+  emergencyDelaySeconds = 3 * DAY_SECONDS,
+  authorityKeypair = patientKeypair,
+  releaseAmounts = null,
+}) {
+  const treatmentPassPDA = await treatmentPassAddress(program, patientKeypair.publicKey, passId);
+  const [escrowPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('milestone_escrow'), treatmentPassPDA.toBuffer()],
+    program.programId
+  );
+  const [escrowVaultPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('escrow_vault'), treatmentPassPDA.toBuffer()],
+    program.programId
+  );
+  const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+
+  await program.methods
+    .createMilestoneEscrow(
+      new anchor.BN(passId),
+      treatmentPass.milestones.map((m, index) => ({
+        milestoneId: m.milestoneId,
+        releaseAmount: releaseAmounts ? new anchor.BN(releaseAmounts[index]) : m.fundingAmount,
+        isReleased: false,
+        releaseTimestamp: null,
+        requiredVerifications: requirements,
+        receivedVerifications: [],
+      })),
+      true,
+      emergencyReleasers,
+      new anchor.BN(emergencyDelaySeconds)
+    )
+    .accounts({
+      escrowAccount: escrowPDA,
+      escrowVault: escrowVaultPDA,
+      treatmentPassAccount: treatmentPassPDA,
+      patient: patientKeypair.publicKey,
+      authority: authorityKeypair.publicKey,
+      patientRepresentative: null,
+      fundingMint,
+      payer: authorityKeypair.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([authorityKeypair])
+    .rpc();
+
+  return { escrowPDA, escrowVaultPDA, treatmentPassPDA };
+}
+
+async function fundEscrow(program, { passId, escrowPDA, escrowVaultPDA, treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount }) {
+  const [escrowDepositPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('escrow_deposit'), escrowPDA.toBuffer(), sponsorKeypair.publicKey.toBuffer()],
+    program.programId
+  );
+
+  await program.methods
+    .fundEscrow(new anchor.BN(passId), new anchor.BN(amount))
+    .accounts({
+      escrowAccount: escrowPDA,
+      escrowVault: escrowVaultPDA,
+      treatmentPassAccount: treatmentPassPDA,
+      escrowDeposit: escrowDepositPDA,
+      sponsorTokenAccount,
+      sponsor: sponsorKeypair.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([sponsorKeypair])
+    .rpc();
+
+  return escrowDepositPDA;
+}
+
+async function elderCouncil(program) {
+  const [elderCouncilPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('elder_council')],
+    program.programId
+  );
+
+  // Like the verifier registry, the council is a singleton governed by the platform authority
+  if (!(await program.account.elderCouncilAccount.fetchNullable(elderCouncilPDA))) {
+    await program.methods
+      .initializeElderCouncil(2, 6667)
+      .accounts({
+        elderCouncil: elderCouncilPDA,
+        registryAccount: await verifierRegistry(program),
+        authority: program.provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  }
+  return elderCouncilPDA;
+}
+
+async function elderCouncilReview(program, { treatmentPassPDA, elderKeypair, decision, guidance = 'Reviewed with the family' }) {
+  const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+  const round = Buffer.alloc(2);
+  round.writeUInt16LE(treatmentPass.elderReviewRound);
+  const [reviewTallyPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('elder_review_tally'), treatmentPassPDA.toBuffer(), round],
+    program.programId
+  );
+  const [reviewRecordPDA] = await PublicKey.findProgramAddress(
+    [Buffer.from('elder_review'), reviewTallyPDA.toBuffer(), elderKeypair.publicKey.toBuffer()],
+    program.programId
+  );
+
+  await program.methods
+    .elderCouncilReview(treatmentPass.passId, decision, guidance, null, [])
+    .accounts({
+      treatmentPassAccount: treatmentPassPDA,
+      elderCouncil: await elderCouncil(program),
+      reviewTally: reviewTallyPDA,
+      reviewRecord: reviewRecordPDA,
+      elder: elderKeypair.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([elderKeypair])
+    .rpc();
+}
+
+async function appointRepresentative(program, { patientKeypair, representativeKeypair, role, scopes, expiresAt = null }) {
+  const representativePDA = await representativeAddress(
+    program, patientKeypair.publicKey, representativeKeypair.publicKey
+  );
+  await program.methods
+    .appointPatientRepresentative(representativeKeypair.publicKey, role, scopes, expiresAt && new anchor.BN(expiresAt))
+    .accounts({
+      representativeAccount: representativePDA,
+      patient: patientKeypair.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    })
+    .signers([patientKeypair])
+    .rpc();
+  return representativePDA;
+}
+
 
 describe('Ubuntu Health Core Contract', () => {
   let program;
@@ -260,6 +715,3331 @@ describe('Ubuntu Health Core Contract', () => {
     });
   });
 
+  describe('Sponsor Refunds', () => {
+    const passId = 2001;
+    let refundPatientKeypair;
+    let earmarkingSponsorKeypair;
+    let poolSponsorKeypair;
+    let fundingMint;
+    let treatmentPassPDA;
+    let passEscrowAccount;
+    let earmarkingTokenAccount;
+    let poolTokenAccount;
+
+    async function sponsorContributionAddress(sponsor) {
+      const [contributionPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('sponsor_contribution'), treatmentPassPDA.toBuffer(), sponsor.toBuffer()],
+        program.programId
+      );
+      return contributionPDA;
+    }
+
+    async function sponsor(sponsorKeypair, sponsorTokenAccount, amount, milestoneAllocations, escrowAccount = passEscrowAccount) {
+      await program.methods
+        .sponsorTreatment(new anchor.BN(amount), 'Ubuntu: I am because we are', milestoneAllocations, null)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          sponsorContribution: await sponsorContributionAddress(sponsorKeypair.publicKey),
+          sponsor: sponsorKeypair.publicKey,
+          payer: sponsorKeypair.publicKey,
+          escrowAccount,
+          sponsorTokenAccount,
+          communityPool: null,
+          communityPoolVault: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([sponsorKeypair])
+        .rpc();
+    }
+
+    async function claimRefund(sponsorKeypair, sponsorTokenAccount) {
+      return program.methods
+        .claimSponsorRefund(null)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          sponsorContribution: await sponsorContributionAddress(sponsorKeypair.publicKey),
+          sponsor: sponsorKeypair.publicKey,
+          escrowAccount: passEscrowAccount,
+          sponsorTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([sponsorKeypair])
+        .rpc();
+    }
+
+    before(async () => {
+      refundPatientKeypair = Keypair.generate();
+      earmarkingSponsorKeypair = Keypair.generate();
+      poolSponsorKeypair = Keypair.generate();
+      await airdrop(connection, refundPatientKeypair.publicKey, 2);
+      await airdrop(connection, earmarkingSponsorKeypair.publicKey, 1);
+      await airdrop(connection, poolSponsorKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: refundPatientKeypair.publicKey,
+        creator: refundPatientKeypair,
+        passId,
+        amounts: [500, 500],
+        fundingMint,
+      });
+      passEscrowAccount = await passEscrowAddress(program, treatmentPassPDA);
+
+      earmarkingTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, earmarkingSponsorKeypair.publicKey
+      );
+      poolTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, poolSponsorKeypair.publicKey
+      );
+      await mintTo(connection, provider.wallet.payer, fundingMint, earmarkingTokenAccount, provider.wallet.payer, 600);
+      await mintTo(connection, provider.wallet.payer, fundingMint, poolTokenAccount, provider.wallet.payer, 300);
+    });
+
+    it('should only accept sponsorships into the escrow created for the pass', async () => {
+      // Owned by the pass PDA like the real escrow, but not the one recorded on the pass
+      const decoyEscrowAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, treatmentPassPDA, Keypair.generate()
+      );
+
+      try {
+        await sponsor(poolSponsorKeypair, poolTokenAccount, 100, [], decoyEscrowAccount);
+        expect.fail('Should have rejected an escrow other than the pass escrow');
+      } catch (error) {
+        expect(error.message).to.include('Escrow account is not controlled by this treatment pass');
+      }
+    });
+
+    it('should refund unreleased earmarks in full and the general pool pro rata after cancellation', async () => {
+      // 400 earmarked for the first milestone and 200 to the general pool, then 300 more to the pool
+      await sponsor(earmarkingSponsorKeypair, earmarkingTokenAccount, 600, [
+        { milestoneId: 1, amount: new anchor.BN(400), refunded: false },
+      ]);
+      await sponsor(poolSponsorKeypair, poolTokenAccount, 300, []);
+
+      try {
+        await claimRefund(poolSponsorKeypair, poolTokenAccount);
+        expect.fail('Should have rejected a refund while the pass is still raising funds');
+      } catch (error) {
+        expect(error.message).to.include('Refunds are only available for cancelled or expired treatment passes');
+      }
+
+      await program.methods
+        .cancelTreatment('Patient relocated for care abroad')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: refundPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([refundPatientKeypair])
+        .rpc();
+
+      await claimRefund(earmarkingSponsorKeypair, earmarkingTokenAccount);
+      await claimRefund(poolSponsorKeypair, poolTokenAccount);
+
+      expect(Number((await getAccount(connection, earmarkingTokenAccount)).amount)).to.equal(600);
+      expect(Number((await getAccount(connection, poolTokenAccount)).amount)).to.equal(300);
+      expect(Number((await getAccount(connection, passEscrowAccount)).amount)).to.equal(0);
+
+      const contribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(earmarkingSponsorKeypair.publicKey)
+      )).contribution;
+      expect(contribution.refundClaimed).to.be.true;
+      expect(contribution.milestoneAllocations[0].refunded).to.be.true;
+    });
+
+    it('should reject a second refund claim from the same sponsor', async () => {
+      try {
+        await claimRefund(poolSponsorKeypair, poolTokenAccount);
+        expect.fail('Should have rejected a repeated refund claim');
+      } catch (error) {
+        expect(error.message).to.include('Sponsor refund already claimed');
+      }
+    });
+  });
+
+
+  describe('Funding Deadline and Expiry', () => {
+    const passId = 2007;
+    let expiringPatientKeypair;
+    let sponsorKeypair;
+    let fundingMint;
+    let treatmentPassPDA;
+    let sponsorTokenAccount;
+
+    async function expire() {
+      return program.methods
+        .expireTreatmentPass()
+        .accounts({ treatmentPassAccount: treatmentPassPDA })
+        .rpc();
+    }
+
+    before(async () => {
+      expiringPatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      await airdrop(connection, expiringPatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 300
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: expiringPatientKeypair.publicKey,
+        creator: expiringPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+        fundingPeriod: 15,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 200 });
+    });
+
+    it('should reject a funding deadline beyond the maximum funding period', async () => {
+      try {
+        await createTreatmentPass(program, {
+          patient: expiringPatientKeypair.publicKey,
+          creator: expiringPatientKeypair,
+          passId: passId + 1,
+          amounts: [500],
+          fundingMint,
+          fundingPeriod: 181 * DAY_SECONDS,
+        });
+        expect.fail('Should have rejected a funding period longer than 180 days');
+      } catch (error) {
+        expect(error.message).to.include('Funding deadline must be in the future and within the maximum funding period');
+      }
+    });
+
+    it('should not expire a pass before its funding deadline', async () => {
+      try {
+        await expire();
+        expect.fail('Should have rejected expiry before the deadline');
+      } catch (error) {
+        expect(error.message).to.include('Funding deadline has not been reached');
+      }
+    });
+
+    it('should close an underfunded pass to sponsorship once its deadline passes and refund its sponsors', async () => {
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      await waitForChainTime(connection, treatmentPass.fundingDeadline.toNumber());
+
+      try {
+        await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 100 });
+        expect.fail('Should have rejected a sponsorship after the deadline');
+      } catch (error) {
+        expect(error.message).to.include('Funding deadline has passed');
+      }
+
+      await expire();
+      const expiredPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(expiredPass.status).to.have.property('fundingExpired');
+
+      await claimSponsorRefund(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount });
+      expect(Number((await getAccount(connection, sponsorTokenAccount)).amount)).to.equal(300);
+      const passEscrowAccount = await passEscrowAddress(program, treatmentPassPDA);
+      expect(Number((await getAccount(connection, passEscrowAccount)).amount)).to.equal(0);
+    });
+  });
+
+  describe('Milestone Verifier Registry', () => {
+    const passId = 2009;
+    let verifiedPatientKeypair;
+    let sponsorKeypair;
+    let treatingProviderKeypair;
+    let laboratoryKeypair;
+    let fundingMint;
+    let treatmentPassPDA;
+    let patientTokenAccount;
+    let treatingProviderEntry;
+    let laboratoryEntry;
+
+    async function expectUnauthorized(verifierKeypair, verifierEntry) {
+      try {
+        await verifyMilestone(program, { treatmentPassPDA, verifierKeypair, verifierEntry, patientTokenAccount, milestoneId: 1 });
+        expect.fail('Should have rejected the verifier');
+      } catch (error) {
+        expect(error.message).to.include('Unauthorized verifier');
+      }
+    }
+
+    before(async () => {
+      verifiedPatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      treatingProviderKeypair = Keypair.generate();
+      laboratoryKeypair = Keypair.generate();
+      await airdrop(connection, verifiedPatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      const sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 500
+      );
+      patientTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, verifiedPatientKeypair.publicKey, 0
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: verifiedPatientKeypair.publicKey,
+        creator: verifiedPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 500 });
+
+      treatingProviderEntry = await registerVerifier(
+        program, treatingProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0012345'
+      );
+      laboratoryEntry = await registerVerifier(
+        program, laboratoryKeypair.publicKey, { accreditedLaboratory: {} }, 'SANAS-M0421'
+      );
+      await startTreatment(program, {
+        treatmentPassPDA,
+        patientKeypair: verifiedPatientKeypair,
+        providerKeypair: treatingProviderKeypair,
+      });
+      await submitMilestoneCompletion(program, { treatmentPassPDA, patientKeypair: verifiedPatientKeypair, milestoneId: 1 });
+    });
+
+    it('should reject verification by a wallet outside the verifier registry', async () => {
+      await expectUnauthorized(Keypair.generate(), null);
+    });
+
+    it('should reject a registered verifier whose kind does not match the milestone', async () => {
+      await expectUnauthorized(laboratoryKeypair, laboratoryEntry);
+    });
+
+    it('should reject a provider whose registry entry has been deactivated', async () => {
+      const [registryPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('verifier_registry')],
+        program.programId
+      );
+      const suspendedProviderKeypair = Keypair.generate();
+      const suspendedProviderEntry = await registerVerifier(
+        program, suspendedProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0054321'
+      );
+      await program.methods
+        .setVerifierStatus(false)
+        .accounts({
+          registryAccount: registryPDA,
+          verifierEntry: suspendedProviderEntry,
+          platformAuthority: provider.wallet.publicKey,
+        })
+        .rpc();
+
+      await expectUnauthorized(suspendedProviderKeypair, suspendedProviderEntry);
+    });
+
+    it('should release the milestone funding once a registered provider verifies it', async () => {
+      await verifyMilestone(program, {
+        treatmentPassPDA,
+        verifierKeypair: treatingProviderKeypair,
+        verifierEntry: treatingProviderEntry,
+        patientTokenAccount,
+        milestoneId: 1,
+      });
+
+      expect(Number((await getAccount(connection, patientTokenAccount)).amount)).to.equal(500);
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.status).to.have.property('treatmentCompleted');
+      expect(treatmentPass.milestones[0].completionStatus).to.have.property('verified');
+      expect(treatmentPass.milestones[0].verifyingProvider.toString()).to.equal(
+        treatingProviderKeypair.publicKey.toString()
+      );
+    });
+  });
+
+  describe('Milestone Escrow Quorum Release', () => {
+    const passId = 2002;
+    let escrowPatientKeypair;
+    let verifierKeypairs;
+    let fundingMint;
+    let escrowPDA;
+    let escrowVaultPDA;
+    let treatmentPassPDA;
+    let patientTokenAccount;
+
+    async function submitAttestation(verifierKeypair, verifierEntry, {
+      attestedAt = null,
+      signingKeypair = verifierKeypair,
+      signedEvidenceHash = 'QmSurgeryDischargeSummary',
+      withPrecompile = true,
+    } = {}) {
+      attestedAt = attestedAt === null ? (await chainTime(connection)) - 5 : attestedAt;
+      const evidenceHash = 'QmSurgeryDischargeSummary';
+      const message = Buffer.concat([
+        ATTESTATION_DOMAIN,
+        escrowPDA.toBuffer(),
+        Buffer.from([1, HEALTHCARE_PROVIDER_DISCRIMINANT]),
+        new anchor.BN(attestedAt).toArrayLike(Buffer, 'le', 8),
+        Buffer.from([1]),
+        new anchor.BN(signedEvidenceHash.length).toArrayLike(Buffer, 'le', 4),
+        Buffer.from(signedEvidenceHash),
+      ]);
+      const signature = nacl.sign.detached(message, signingKeypair.secretKey);
+
+      return program.methods
+        .submitEscrowVerification(
+          new anchor.BN(passId),
+          1,
+          verifierKeypair.publicKey,
+          { healthcareProvider: {} },
+          evidenceHash,
+          new anchor.BN(attestedAt),
+          Array.from(signature)
+        )
+        .accounts({
+          escrowAccount: escrowPDA,
+          submitter: provider.wallet.publicKey,
+          verifierEntry,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .preInstructions(withPrecompile ? [
+          Ed25519Program.createInstructionWithPublicKey({
+            publicKey: signingKeypair.publicKey.toBytes(),
+            message,
+            signature,
+          }),
+        ] : [])
+        .rpc();
+    }
+
+    async function expectRejectedAttestation(options, expectedError) {
+      try {
+        await submitAttestation(
+          verifierKeypairs[0],
+          await verifierEntryAddress(program, verifierKeypairs[0].publicKey),
+          options
+        );
+        expect.fail('Should have rejected the attestation');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    async function releaseMilestone() {
+      return program.methods
+        .releaseMilestoneFunds(new anchor.BN(passId), 1)
+        .accounts({
+          escrowAccount: escrowPDA,
+          escrowVault: escrowVaultPDA,
+          patientTokenAccount,
+          treatmentPassAccount: treatmentPassPDA,
+          executor: provider.wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    }
+
+    before(async () => {
+      escrowPatientKeypair = Keypair.generate();
+      verifierKeypairs = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      await airdrop(connection, escrowPatientKeypair.publicKey, 2);
+
+      // Only the first two providers are accredited; the third stays unregistered
+      for (const [index, verifierKeypair] of verifierKeypairs.slice(0, 2).entries()) {
+        await registerVerifier(program, verifierKeypair.publicKey, { accreditedProvider: {} }, `HPCSA-MP-00${index + 1}`);
+      }
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      await createTreatmentPass(program, {
+        patient: escrowPatientKeypair.publicKey,
+        creator: escrowPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      ({ escrowPDA, escrowVaultPDA, treatmentPassPDA } = await createMilestoneEscrow(program, {
+        patientKeypair: escrowPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: null,
+          isMandatory: true,
+          eligibleVerifiers: verifierKeypairs.map(v => v.publicKey),
+          threshold: 2,
+        }],
+      }));
+
+      const sponsorTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, provider.wallet.publicKey
+      );
+      await mintTo(connection, provider.wallet.payer, fundingMint, sponsorTokenAccount, provider.wallet.payer, 500);
+      await fundEscrow(program, {
+        passId,
+        escrowPDA,
+        escrowVaultPDA,
+        treatmentPassPDA,
+        sponsorKeypair: provider.wallet.payer,
+        sponsorTokenAccount,
+        amount: 500,
+      });
+
+      patientTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, escrowPatientKeypair.publicKey
+      );
+    });
+
+    it('should reject attestations from eligible verifiers missing from the registry', async () => {
+      try {
+        await submitAttestation(verifierKeypairs[2], null);
+        expect.fail('Should have rejected an unregistered verifier');
+      } catch (error) {
+        expect(error.message).to.include('Verifier is not an active registry verifier accredited for this verification type');
+      }
+    });
+
+    it('should reject an attestation without a preceding Ed25519 verification', async () => {
+      await expectRejectedAttestation({ withPrecompile: false }, 'Invalid verification signature');
+    });
+
+    it('should reject an attestation signed by a key other than the named verifier', async () => {
+      await expectRejectedAttestation({ signingKeypair: verifierKeypairs[1] }, 'Invalid verification signature');
+    });
+
+    it('should reject a signature over different evidence than the submitted attestation', async () => {
+      await expectRejectedAttestation({ signedEvidenceHash: 'QmAnotherPatientsRecord' }, 'Invalid verification signature');
+    });
+
+    it('should reject attestations dated in the future or older than seven days', async () => {
+      const now = await chainTime(connection);
+      await expectRejectedAttestation(
+        { attestedAt: now + 10 * 60 },
+        'Verification attestation is too old or timestamped in the future'
+      );
+      await expectRejectedAttestation(
+        { attestedAt: now - 8 * DAY_SECONDS },
+        'Verification attestation is too old or timestamped in the future'
+      );
+    });
+
+    it('should hold funds until the verification quorum is met', async () => {
+      await submitAttestation(verifierKeypairs[0], await verifierEntryAddress(program, verifierKeypairs[0].publicKey));
+
+      try {
+        await releaseMilestone();
+        expect.fail('Should have held funds with one of two required verifications');
+      } catch (error) {
+        expect(error.message).to.include('Milestone verification incomplete');
+      }
+
+      try {
+        await submitAttestation(verifierKeypairs[0], await verifierEntryAddress(program, verifierKeypairs[0].publicKey));
+        expect.fail('Should have rejected a second attestation from the same verifier');
+      } catch (error) {
+        expect(error.message).to.include('Verifier has already verified this milestone');
+      }
+    });
+
+    it('should release milestone funds to the patient once the quorum is met', async () => {
+      await submitAttestation(verifierKeypairs[1], await verifierEntryAddress(program, verifierKeypairs[1].publicKey));
+      await releaseMilestone();
+
+      expect(Number((await getAccount(connection, patientTokenAccount)).amount)).to.equal(500);
+      const escrow = (await program.account.milestoneEscrowAccount.fetch(escrowPDA)).escrow;
+      expect(escrow.releasedAmount.toNumber()).to.equal(500);
+      expect(escrow.milestoneReleases[0].isReleased).to.be.true;
+    });
+  });
+
+  describe('Milestone Escrow Emergency Release', () => {
+    const passId = 2003;
+    let emergencyPatientKeypair;
+    let releaserKeypair;
+    let puppetReleaserKeypair;
+    let unlistedVerifierKeypair;
+    let fundingMint;
+    let escrowPDA;
+    let escrowVaultPDA;
+    let treatmentPassPDA;
+    let patientTokenAccount;
+
+    async function initiateEmergencyRelease(signer, releaserEntry) {
+      return program.methods
+        .initiateEmergencyRelease(new anchor.BN(passId), 'Emergency surgery scheduled before verification')
+        .accounts({
+          escrowAccount: escrowPDA,
+          releaser: signer.publicKey,
+          releaserEntry,
+          elderCouncil: null,
+          releaserRepresentative: await representativeAddress(
+            program, emergencyPatientKeypair.publicKey, signer.publicKey
+          ),
+        })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      emergencyPatientKeypair = Keypair.generate();
+      releaserKeypair = Keypair.generate();
+      puppetReleaserKeypair = Keypair.generate();
+      unlistedVerifierKeypair = Keypair.generate();
+      await airdrop(connection, emergencyPatientKeypair.publicKey, 2);
+      await airdrop(connection, releaserKeypair.publicKey, 1);
+      await airdrop(connection, puppetReleaserKeypair.publicKey, 1);
+      await airdrop(connection, unlistedVerifierKeypair.publicKey, 1);
+      await registerVerifier(program, releaserKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-010');
+      await registerVerifier(program, unlistedVerifierKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-011');
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      await createTreatmentPass(program, {
+        patient: emergencyPatientKeypair.publicKey,
+        creator: emergencyPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      ({ escrowPDA, escrowVaultPDA, treatmentPassPDA } = await createMilestoneEscrow(program, {
+        patientKeypair: emergencyPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: providerKeypair.publicKey,
+          isMandatory: true,
+          eligibleVerifiers: [],
+          threshold: 1,
+        }],
+        emergencyReleasers: [releaserKeypair.publicKey, puppetReleaserKeypair.publicKey],
+      }));
+      patientTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, emergencyPatientKeypair.publicKey
+      );
+    });
+
+    it('should reject an escrow that lists the patient as an emergency releaser', async () => {
+      const selfListingPatientKeypair = Keypair.generate();
+      await airdrop(connection, selfListingPatientKeypair.publicKey, 2);
+      await createTreatmentPass(program, {
+        patient: selfListingPatientKeypair.publicKey,
+        creator: selfListingPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+
+      try {
+        await createMilestoneEscrow(program, {
+          patientKeypair: selfListingPatientKeypair,
+          passId,
+          fundingMint,
+          requirements: [{
+            verificationType: { healthcareProvider: {} },
+            requiredVerifier: providerKeypair.publicKey,
+            isMandatory: true,
+            eligibleVerifiers: [],
+            threshold: 1,
+          }],
+          emergencyReleasers: [selfListingPatientKeypair.publicKey],
+        });
+        expect.fail('Should have rejected the patient as their own emergency releaser');
+      } catch (error) {
+        expect(error.message).to.include("Emergency releasers cannot be the patient, their representative or the escrow's creator");
+      }
+    });
+
+    it('should reject duplicate emergency releasers and delays shorter than 72 hours', async () => {
+      const misconfiguredPatientKeypair = Keypair.generate();
+      await airdrop(connection, misconfiguredPatientKeypair.publicKey, 2);
+      await createTreatmentPass(program, {
+        patient: misconfiguredPatientKeypair.publicKey,
+        creator: misconfiguredPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      const escrowOptions = {
+        patientKeypair: misconfiguredPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: providerKeypair.publicKey,
+          isMandatory: true,
+          eligibleVerifiers: [],
+          threshold: 1,
+        }],
+      };
+
+      try {
+        await createMilestoneEscrow(program, {
+          ...escrowOptions,
+          emergencyReleasers: [releaserKeypair.publicKey, releaserKeypair.publicKey],
+        });
+        expect.fail('Should have rejected a releaser listed twice');
+      } catch (error) {
+        expect(error.message).to.include('Emergency releaser is listed more than once');
+      }
+
+      try {
+        await createMilestoneEscrow(program, {
+          ...escrowOptions,
+          emergencyReleasers: [releaserKeypair.publicKey],
+          emergencyDelaySeconds: DAY_SECONDS,
+        });
+        expect.fail('Should have rejected a one-day emergency delay');
+      } catch (error) {
+        expect(error.message).to.include('Emergency release delay is too short');
+      }
+    });
+
+    it('should only let listed, accredited releasers initiate an emergency release', async () => {
+      try {
+        await initiateEmergencyRelease(puppetReleaserKeypair, null);
+        expect.fail('Should have rejected a listed releaser who is neither a verifier nor an elder');
+      } catch (error) {
+        expect(error.message).to.include('Emergency releaser is neither an active registry verifier nor a council elder');
+      }
+
+      try {
+        await initiateEmergencyRelease(
+          unlistedVerifierKeypair, await verifierEntryAddress(program, unlistedVerifierKeypair.publicKey)
+        );
+        expect.fail('Should have rejected a verifier who is not listed on this escrow');
+      } catch (error) {
+        expect(error.message).to.include('Emergency release not authorized');
+      }
+
+      await initiateEmergencyRelease(releaserKeypair, await verifierEntryAddress(program, releaserKeypair.publicKey));
+
+      const conditions = (await program.account.milestoneEscrowAccount.fetch(escrowPDA)).escrow.emergencyConditions;
+      expect(conditions.emergencyInitiated).to.not.be.null;
+      expect(conditions.emergencyInitiatedBy.toString()).to.equal(releaserKeypair.publicKey.toString());
+    });
+
+    it('should hold an emergency release until its delay has elapsed', async () => {
+      try {
+        await program.methods
+          .executeEmergencyRelease(new anchor.BN(passId))
+          .accounts({
+            escrowAccount: escrowPDA,
+            escrowVault: escrowVaultPDA,
+            patientTokenAccount,
+            treatmentPassAccount: treatmentPassPDA,
+            authority: releaserKeypair.publicKey,
+            patientRepresentative: null,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([releaserKeypair])
+          .rpc();
+        expect.fail('Should have held the release during the emergency delay');
+      } catch (error) {
+        expect(error.message).to.include('Emergency release delay has not elapsed');
+      }
+    });
+
+    it('should let the patient cancel a pending emergency release', async () => {
+      await program.methods
+        .cancelEmergencyRelease(new anchor.BN(passId))
+        .accounts({
+          escrowAccount: escrowPDA,
+          authority: emergencyPatientKeypair.publicKey,
+          patientRepresentative: null,
+        })
+        .signers([emergencyPatientKeypair])
+        .rpc();
+
+      const escrow = (await program.account.milestoneEscrowAccount.fetch(escrowPDA)).escrow;
+      expect(escrow.emergencyConditions.emergencyInitiated).to.be.null;
+      expect(escrow.isActive).to.be.true;
+    });
+  });
+
+  describe('Milestone Escrow Deposit Refunds', () => {
+    const passId = 2006;
+    let closingPatientKeypair;
+    let depositorKeypair;
+    let fundingMint;
+    let escrowPDA;
+    let escrowVaultPDA;
+    let treatmentPassPDA;
+    let escrowDepositPDA;
+    let depositorTokenAccount;
+
+    async function claimEscrowRefund() {
+      return program.methods
+        .claimEscrowRefund(new anchor.BN(passId))
+        .accounts({
+          escrowAccount: escrowPDA,
+          escrowVault: escrowVaultPDA,
+          treatmentPassAccount: treatmentPassPDA,
+          escrowDeposit: escrowDepositPDA,
+          depositor: depositorKeypair.publicKey,
+          depositorTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([depositorKeypair])
+        .rpc();
+    }
+
+    before(async () => {
+      closingPatientKeypair = Keypair.generate();
+      depositorKeypair = Keypair.generate();
+      await airdrop(connection, closingPatientKeypair.publicKey, 2);
+      await airdrop(connection, depositorKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      await createTreatmentPass(program, {
+        patient: closingPatientKeypair.publicKey,
+        creator: closingPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      ({ escrowPDA, escrowVaultPDA, treatmentPassPDA } = await createMilestoneEscrow(program, {
+        patientKeypair: closingPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: providerKeypair.publicKey,
+          isMandatory: true,
+          eligibleVerifiers: [],
+          threshold: 1,
+        }],
+      }));
+
+      depositorTokenAccount = await createAccount(
+        connection, provider.wallet.payer, fundingMint, depositorKeypair.publicKey
+      );
+      await mintTo(connection, provider.wallet.payer, fundingMint, depositorTokenAccount, provider.wallet.payer, 400);
+      escrowDepositPDA = await fundEscrow(program, {
+        passId,
+        escrowPDA,
+        escrowVaultPDA,
+        treatmentPassPDA,
+        sponsorKeypair: depositorKeypair,
+        sponsorTokenAccount: depositorTokenAccount,
+        amount: 300,
+      });
+    });
+
+    it('should only let the patient create an escrow mirroring the pass milestones', async () => {
+      const unboundPatientKeypair = Keypair.generate();
+      const strangerKeypair = Keypair.generate();
+      await airdrop(connection, unboundPatientKeypair.publicKey, 2);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+      await createTreatmentPass(program, {
+        patient: unboundPatientKeypair.publicKey,
+        creator: unboundPatientKeypair,
+        passId,
+        amounts: [500],
+        fundingMint,
+      });
+      const escrowOptions = {
+        patientKeypair: unboundPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: providerKeypair.publicKey,
+          isMandatory: true,
+          eligibleVerifiers: [],
+          threshold: 1,
+        }],
+      };
+
+      try {
+        await createMilestoneEscrow(program, { ...escrowOptions, authorityKeypair: strangerKeypair });
+        expect.fail('Should have rejected an escrow created by someone other than the patient');
+      } catch (error) {
+        expect(error.message).to.include('Only the patient or their representative can create the escrow');
+      }
+
+      try {
+        await createMilestoneEscrow(program, { ...escrowOptions, releaseAmounts: [900] });
+        expect.fail('Should have rejected releases that differ from the pass milestones');
+      } catch (error) {
+        expect(error.message).to.include('Milestone releases do not match the treatment pass milestones');
+      }
+    });
+
+    it('should keep escrow deposits locked while the pass is open', async () => {
+      try {
+        await claimEscrowRefund();
+        expect.fail('Should have rejected an escrow refund on an open pass');
+      } catch (error) {
+        expect(error.message).to.include('Escrow refunds are only available for cancelled or expired treatment passes');
+      }
+    });
+
+    it('should refund escrow deposits and refuse new ones once the pass is cancelled', async () => {
+      await program.methods
+        .cancelTreatment('Treatment no longer needed')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: closingPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([closingPatientKeypair])
+        .rpc();
+
+      try {
+        await fundEscrow(program, {
+          passId,
+          escrowPDA,
+          escrowVaultPDA,
+          treatmentPassPDA,
+          sponsorKeypair: depositorKeypair,
+          sponsorTokenAccount: depositorTokenAccount,
+          amount: 100,
+        });
+        expect.fail('Should have rejected a deposit into a cancelled pass');
+      } catch (error) {
+        expect(error.message).to.include('Treatment pass is cancelled or expired');
+      }
+
+      await claimEscrowRefund();
+
+      expect(Number((await getAccount(connection, depositorTokenAccount)).amount)).to.equal(400);
+      expect(Number((await getAccount(connection, escrowVaultPDA)).amount)).to.equal(0);
+      const escrow = (await program.account.milestoneEscrowAccount.fetch(escrowPDA)).escrow;
+      expect(escrow.refundedAmount.toNumber()).to.equal(300);
+
+      try {
+        await claimEscrowRefund();
+        expect.fail('Should have rejected a second escrow refund');
+      } catch (error) {
+        expect(error.message).to.include('Escrow deposit already refunded');
+      }
+    });
+  });
+
+  describe('Treatment Pass Sizing', () => {
+    const passId = 2010;
+    let sizingPatientKeypair;
+    let fundingMint;
+
+    async function expectRejectedPass(options, expectedError) {
+      try {
+        await createTreatmentPass(program, {
+          patient: sizingPatientKeypair.publicKey,
+          creator: sizingPatientKeypair,
+          passId,
+          amounts: [500],
+          fundingMint,
+          ...options,
+        });
+        expect.fail('Should have rejected the treatment pass');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      sizingPatientKeypair = Keypair.generate();
+      await airdrop(connection, sizingPatientKeypair.publicKey, 2);
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+    });
+
+    it('should reject titles and categories longer than the pass allocation', async () => {
+      await expectRejectedPass({ title: 'T'.repeat(101) }, 'Treatment title exceeds maximum length');
+      await expectRejectedPass({ category: 'c'.repeat(51) }, 'Treatment category exceeds maximum length');
+    });
+
+    it('should store a pass with the longest allowed title and category and several milestones', async () => {
+      const title = 'T'.repeat(100);
+      const category = 'c'.repeat(50);
+      const treatmentPassPDA = await createTreatmentPass(program, {
+        patient: sizingPatientKeypair.publicKey,
+        creator: sizingPatientKeypair,
+        passId,
+        amounts: [100, 200, 300, 400],
+        fundingMint,
+        title,
+        category,
+      });
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.treatmentTitle).to.equal(title);
+      expect(treatmentPass.treatmentCategory).to.equal(category);
+      expect(treatmentPass.milestones).to.have.lengthOf(4);
+      expect(treatmentPass.fundingTarget.toNumber()).to.equal(1000);
+    });
+  });
+
+  describe('Per-Sponsor Contribution Records', () => {
+    const passId = 2011;
+    let recordsPatientKeypair;
+    let returningSponsorKeypair;
+    let secondSponsorKeypair;
+    let treatmentPassPDA;
+    let returningTokenAccount;
+    let secondTokenAccount;
+
+    before(async () => {
+      recordsPatientKeypair = Keypair.generate();
+      returningSponsorKeypair = Keypair.generate();
+      secondSponsorKeypair = Keypair.generate();
+      await airdrop(connection, recordsPatientKeypair.publicKey, 2);
+      await airdrop(connection, returningSponsorKeypair.publicKey, 1);
+      await airdrop(connection, secondSponsorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      returningTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, returningSponsorKeypair.publicKey, 300
+      );
+      secondTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, secondSponsorKeypair.publicKey, 300
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: recordsPatientKeypair.publicKey,
+        creator: recordsPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+    });
+
+    it('should keep one contribution record per sponsor and only aggregate counters on the pass', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA, sponsorKeypair: returningSponsorKeypair, sponsorTokenAccount: returningTokenAccount, amount: 200,
+      });
+      await sponsorTreatment(program, {
+        treatmentPassPDA, sponsorKeypair: secondSponsorKeypair, sponsorTokenAccount: secondTokenAccount, amount: 300,
+      });
+      await sponsorTreatment(program, {
+        treatmentPassPDA, sponsorKeypair: returningSponsorKeypair, sponsorTokenAccount: returningTokenAccount, amount: 100,
+      });
+
+      const contribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, treatmentPassPDA, returningSponsorKeypair.publicKey)
+      )).contribution;
+      expect(contribution.wallet.toString()).to.equal(returningSponsorKeypair.publicKey.toString());
+      expect(contribution.treatmentPass.toString()).to.equal(treatmentPassPDA.toString());
+      expect(contribution.amountContributed.toNumber()).to.equal(300);
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.sponsorCount).to.equal(2);
+      expect(treatmentPass.currentFunding.toNumber()).to.equal(600);
+    });
+
+    it("should let an indexer list a pass's sponsors and a sponsor's portfolio by querying records", async () => {
+      // Records start with the pass key, followed by the sponsor wallet, after the 8-byte discriminator
+      const passRecords = await program.account.sponsorContributionAccount.all([
+        { memcmp: { offset: 8, bytes: treatmentPassPDA.toBase58() } },
+      ]);
+      expect(passRecords).to.have.lengthOf(2);
+
+      const portfolio = await program.account.sponsorContributionAccount.all([
+        { memcmp: { offset: 40, bytes: secondSponsorKeypair.publicKey.toBase58() } },
+      ]);
+      expect(portfolio).to.have.lengthOf(1);
+      expect(portfolio[0].account.contribution.amountContributed.toNumber()).to.equal(300);
+    });
+  });
+
+  describe('Milestone Earmarked Sponsorships', () => {
+    const passId = 2012;
+    let earmarkPatientKeypair;
+    let earmarkingSponsorKeypair;
+    let poolSponsorKeypair;
+    let earmarkProviderKeypair;
+    let treatmentPassPDA;
+    let earmarkingTokenAccount;
+    let poolTokenAccount;
+    let patientTokenAccount;
+
+    function earmark(milestoneId, amount) {
+      return { milestoneId, amount: new anchor.BN(amount), refunded: false };
+    }
+
+    async function expectRejectedEarmark(amount, milestoneAllocations, expectedError) {
+      try {
+        await sponsorTreatment(program, {
+          treatmentPassPDA,
+          sponsorKeypair: earmarkingSponsorKeypair,
+          sponsorTokenAccount: earmarkingTokenAccount,
+          amount,
+          milestoneAllocations,
+        });
+        expect.fail('Should have rejected the milestone allocation');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      earmarkPatientKeypair = Keypair.generate();
+      earmarkingSponsorKeypair = Keypair.generate();
+      poolSponsorKeypair = Keypair.generate();
+      earmarkProviderKeypair = Keypair.generate();
+      await airdrop(connection, earmarkPatientKeypair.publicKey, 2);
+      await airdrop(connection, earmarkingSponsorKeypair.publicKey, 1);
+      await airdrop(connection, poolSponsorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      earmarkingTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, earmarkingSponsorKeypair.publicKey, 600
+      );
+      poolTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, poolSponsorKeypair.publicKey, 400
+      );
+      patientTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, earmarkPatientKeypair.publicKey, 0
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: earmarkPatientKeypair.publicKey,
+        creator: earmarkPatientKeypair,
+        passId,
+        amounts: [400, 600],
+        fundingMint,
+      });
+      await registerVerifier(program, earmarkProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0020202');
+    });
+
+    it('should reject earmarks for unknown milestones, beyond the milestone amount or beyond the contribution', async () => {
+      await expectRejectedEarmark(100, [earmark(9, 100)], 'Milestone not found');
+      await expectRejectedEarmark(500, [earmark(1, 500)], "Pledges would exceed the milestone's funding amount");
+      await expectRejectedEarmark(100, [earmark(1, 60), earmark(2, 60)], 'Milestone allocations exceed the sponsored amount');
+      await expectRejectedEarmark(100, [earmark(1, 50), earmark(1, 50)], 'Milestone appears more than once in the allocation');
+    });
+
+    it('should record a split across milestones and keep the remainder in the general pool', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: earmarkingSponsorKeypair,
+        sponsorTokenAccount: earmarkingTokenAccount,
+        amount: 600,
+        milestoneAllocations: [earmark(1, 300), earmark(2, 200)],
+      });
+      await sponsorTreatment(program, {
+        treatmentPassPDA, sponsorKeypair: poolSponsorKeypair, sponsorTokenAccount: poolTokenAccount, amount: 400,
+      });
+
+      const contribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, treatmentPassPDA, earmarkingSponsorKeypair.publicKey)
+      )).contribution;
+      expect(contribution.milestoneAllocations.map(a => [a.milestoneId, a.amount.toNumber()])).to.deep.equal([[1, 300], [2, 200]]);
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.milestones.map(m => m.pledgedAmount.toNumber())).to.deep.equal([300, 200]);
+      expect(treatmentPass.unearmarkedFunding.toNumber()).to.equal(500);
+    });
+
+    it("should draw a verified milestone's shortfall from the general pool and leave other earmarks in escrow", async () => {
+      await startTreatment(program, {
+        treatmentPassPDA,
+        patientKeypair: earmarkPatientKeypair,
+        providerKeypair: earmarkProviderKeypair,
+      });
+      await submitMilestoneCompletion(program, { treatmentPassPDA, patientKeypair: earmarkPatientKeypair, milestoneId: 1 });
+      await verifyMilestone(program, {
+        treatmentPassPDA,
+        verifierKeypair: earmarkProviderKeypair,
+        verifierEntry: await verifierEntryAddress(program, earmarkProviderKeypair.publicKey),
+        patientTokenAccount,
+        milestoneId: 1,
+      });
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(Number((await getAccount(connection, patientTokenAccount)).amount)).to.equal(400);
+      expect(Number((await getAccount(connection, treatmentPass.escrow)).amount)).to.equal(600);
+      // Milestone 1 used its 300 earmark and 100 from the pool; milestone 2's 200 earmark is untouched
+      expect(treatmentPass.unearmarkedFunding.toNumber()).to.equal(400);
+      expect(treatmentPass.milestones[1].pledgedAmount.toNumber()).to.equal(200);
+    });
+  });
+
+  describe('Overfunding and Community Overflow Pool', () => {
+    const passId = 2013;
+    let overflowPatientKeypair;
+    let sponsorKeypair;
+    let strangerKeypair;
+    let poolProviderKeypair;
+    let fundingMint;
+    let communityPoolPDA;
+    let communityPoolVault;
+    let sponsorTokenAccount;
+    let patientTokenAccount;
+    let grantedPassPDA;
+
+    async function createPass(passOffset, amounts, overfundingPolicy = { reject: {} }) {
+      return createTreatmentPass(program, {
+        patient: overflowPatientKeypair.publicKey,
+        creator: overflowPatientKeypair,
+        passId: passId + passOffset,
+        amounts,
+        fundingMint,
+        overfundingPolicy,
+      });
+    }
+
+    async function grant(treatmentPassPDA, amount, authorityKeypair = provider.wallet.payer) {
+      return program.methods
+        .grantOverflowFunds(new anchor.BN(amount))
+        .accounts({
+          communityPool: communityPoolPDA,
+          communityPoolVault,
+          treatmentPassAccount: treatmentPassPDA,
+          poolContribution: await sponsorContributionAddress(program, treatmentPassPDA, communityPoolPDA),
+          escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+          authority: authorityKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+
+    async function reclaim(treatmentPassPDA) {
+      return program.methods
+        .reclaimOverflowGrant()
+        .accounts({
+          communityPool: communityPoolPDA,
+          communityPoolVault,
+          treatmentPassAccount: treatmentPassPDA,
+          poolContribution: await sponsorContributionAddress(program, treatmentPassPDA, communityPoolPDA),
+          escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+    }
+
+    async function tokenBalance(tokenAccount) {
+      return Number((await getAccount(connection, tokenAccount)).amount);
+    }
+
+    before(async () => {
+      overflowPatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      strangerKeypair = Keypair.generate();
+      poolProviderKeypair = Keypair.generate();
+      await airdrop(connection, overflowPatientKeypair.publicKey, 5);
+      await airdrop(connection, sponsorKeypair.publicKey, 2);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+      // Also creates the verifier registry whose authority may open the pool
+      await registerVerifier(program, poolProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0030303');
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 2000
+      );
+      patientTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, overflowPatientKeypair.publicKey, 0
+      );
+      [communityPoolPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('community_overflow_pool')],
+        program.programId
+      );
+      communityPoolVault = await createAccount(
+        connection, provider.wallet.payer, fundingMint, communityPoolPDA, Keypair.generate()
+      );
+    });
+
+    it('should only let the platform authority open the community overflow pool', async () => {
+      const [registryPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('verifier_registry')],
+        program.programId
+      );
+      const initializePool = authorityKeypair => program.methods
+        .initializeCommunityOverflowPool()
+        .accounts({
+          communityPool: communityPoolPDA,
+          communityPoolVault,
+          registryAccount: registryPDA,
+          authority: authorityKeypair.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+
+      try {
+        await initializePool(strangerKeypair);
+        expect.fail('Should have rejected a pool opened by someone other than the platform authority');
+      } catch (error) {
+        expect(error.message).to.include('Signer is not the platform authority');
+      }
+
+      await initializePool(provider.wallet.payer);
+      const pool = (await program.account.communityOverflowPoolAccount.fetch(communityPoolPDA)).pool;
+      expect(pool.authority.toString()).to.equal(provider.wallet.publicKey.toString());
+      expect(pool.vault.toString()).to.equal(communityPoolVault.toString());
+    });
+
+    it('should reject or cap sponsorships past the funding target according to the pass policy', async () => {
+      const rejectingPassPDA = await createPass(0, [500]);
+      try {
+        await sponsorTreatment(program, { treatmentPassPDA: rejectingPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 600 });
+        expect.fail('Should have rejected a sponsorship past the target');
+      } catch (error) {
+        expect(error.message).to.include('Sponsorship would exceed the treatment pass funding target');
+      }
+
+      const cappingPassPDA = await createPass(1, [500], { capAtTarget: {} });
+      await sponsorTreatment(program, { treatmentPassPDA: cappingPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 600 });
+      expect(await tokenBalance(sponsorTokenAccount)).to.equal(1500);
+      const cappedPass = (await program.account.treatmentPassAccount.fetch(cappingPassPDA)).treatmentPass;
+      expect(cappedPass.currentFunding.toNumber()).to.equal(500);
+      expect(cappedPass.status).to.have.property('fullyFunded');
+
+      try {
+        await sponsorTreatment(program, { treatmentPassPDA: cappingPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 100 });
+        expect.fail('Should have rejected a sponsorship of a fully funded pass');
+      } catch (error) {
+        expect(error.message).to.include('Treatment pass has already reached its funding target');
+      }
+    });
+
+    it('should route surplus to the community overflow pool when the pass asks for it', async () => {
+      const routingPassPDA = await createPass(2, [500], { routeToCommunityPool: {} });
+      try {
+        await sponsorTreatment(program, { treatmentPassPDA: routingPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 700 });
+        expect.fail('Should have required the pool accounts to route the surplus');
+      } catch (error) {
+        expect(error.message).to.include('Community overflow pool accounts are required to route surplus funding');
+      }
+
+      await sponsorTreatment(program, {
+        treatmentPassPDA: routingPassPDA,
+        sponsorKeypair,
+        sponsorTokenAccount,
+        amount: 700,
+        communityPool: communityPoolPDA,
+        communityPoolVault,
+      });
+
+      expect(await tokenBalance(sponsorTokenAccount)).to.equal(800);
+      expect(await tokenBalance(communityPoolVault)).to.equal(200);
+      const pool = (await program.account.communityOverflowPoolAccount.fetch(communityPoolPDA)).pool;
+      expect(pool.totalReceived.toNumber()).to.equal(200);
+    });
+
+    it('should only let the pool authority grant surplus, and never past the receiving target', async () => {
+      grantedPassPDA = await createPass(3, [150]);
+      try {
+        await grant(grantedPassPDA, 100, strangerKeypair);
+        expect.fail('Should have rejected a grant by someone other than the pool authority');
+      } catch (error) {
+        expect(error.message).to.include('Signer is not the community overflow pool authority');
+      }
+
+      await grant(grantedPassPDA, 200);
+
+      expect(await tokenBalance(communityPoolVault)).to.equal(50);
+      const grantedPass = (await program.account.treatmentPassAccount.fetch(grantedPassPDA)).treatmentPass;
+      expect(grantedPass.currentFunding.toNumber()).to.equal(150);
+      expect(grantedPass.unearmarkedFunding.toNumber()).to.equal(150);
+      const poolContribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, grantedPassPDA, communityPoolPDA)
+      )).contribution;
+      expect(poolContribution.amountContributed.toNumber()).to.equal(150);
+    });
+
+    it('should return an unreleased grant to the pool once the receiving pass is cancelled', async () => {
+      await program.methods
+        .cancelTreatment('Treatment covered by medical aid')
+        .accounts({
+          treatmentPassAccount: grantedPassPDA,
+          authority: overflowPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([overflowPatientKeypair])
+        .rpc();
+
+      await reclaim(grantedPassPDA);
+      expect(await tokenBalance(communityPoolVault)).to.equal(200);
+      const pool = (await program.account.communityOverflowPoolAccount.fetch(communityPoolPDA)).pool;
+      expect(pool.totalReceived.toNumber()).to.equal(350);
+
+      try {
+        await reclaim(grantedPassPDA);
+        expect.fail('Should have rejected a second reclaim');
+      } catch (error) {
+        expect(error.message).to.include('Grant already reclaimed');
+      }
+    });
+
+    it('should let the pool reclaim from a completed pass only what the pass did not spend', async () => {
+      const completedPassPDA = await createPass(4, [100]);
+      await grant(completedPassPDA, 100);
+
+      try {
+        await reclaim(completedPassPDA);
+        expect.fail('Should have rejected a reclaim from a pass still in progress');
+      } catch (error) {
+        expect(error.message).to.include('Treatment pass is neither refundable nor completed');
+      }
+
+      await startTreatment(program, {
+        treatmentPassPDA: completedPassPDA,
+        patientKeypair: overflowPatientKeypair,
+        providerKeypair: poolProviderKeypair,
+      });
+      await submitMilestoneCompletion(program, {
+        treatmentPassPDA: completedPassPDA, patientKeypair: overflowPatientKeypair, milestoneId: 1,
+      });
+      await verifyMilestone(program, {
+        treatmentPassPDA: completedPassPDA,
+        verifierKeypair: poolProviderKeypair,
+        verifierEntry: await verifierEntryAddress(program, poolProviderKeypair.publicKey),
+        patientTokenAccount,
+        milestoneId: 1,
+      });
+
+      // The whole grant paid for the verified milestone, so nothing returns to the pool
+      await reclaim(completedPassPDA);
+      expect(await tokenBalance(communityPoolVault)).to.equal(100);
+      expect(await tokenBalance(patientTokenAccount)).to.equal(100);
+      const poolContribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, completedPassPDA, communityPoolPDA)
+      )).contribution;
+      expect(poolContribution.refundClaimed).to.be.true;
+    });
+  });
+
+  describe('Treatment Lifecycle', () => {
+    const passId = 2018;
+    let lifecyclePatientKeypair;
+    let sponsorKeypair;
+    let assignedProviderKeypair;
+    let otherProviderKeypair;
+    let laboratoryKeypair;
+    let treatmentPassPDA;
+    let sponsorTokenAccount;
+    let assignedProviderEntry;
+
+    async function changeStatus(instruction, args, authorityKeypair, authorityEntry = null) {
+      return program.methods[instruction](...args)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: authorityKeypair.publicKey,
+          authorityEntry,
+          patientRepresentative: null,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+
+    async function assignProvider(providerEntry) {
+      return program.methods
+        .assignTreatingProvider()
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: lifecyclePatientKeypair.publicKey,
+          patientRepresentative: null,
+          providerEntry,
+        })
+        .signers([lifecyclePatientKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail('Should have rejected the lifecycle change');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    async function status() {
+      return (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass.status;
+    }
+
+    before(async () => {
+      lifecyclePatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      assignedProviderKeypair = Keypair.generate();
+      otherProviderKeypair = Keypair.generate();
+      laboratoryKeypair = Keypair.generate();
+      await airdrop(connection, lifecyclePatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 400
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: lifecyclePatientKeypair.publicKey,
+        creator: lifecyclePatientKeypair,
+        passId,
+        amounts: [400],
+        fundingMint,
+      });
+      assignedProviderEntry = await registerVerifier(
+        program, assignedProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0040404'
+      );
+      await registerVerifier(program, otherProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0040405');
+      await registerVerifier(program, laboratoryKeypair.publicKey, { accreditedLaboratory: {} }, 'SANAS-M0404');
+    });
+
+    it('should only let the patient assign an accredited provider', async () => {
+      await expectRejected(
+        async () => assignProvider(await verifierEntryAddress(program, laboratoryKeypair.publicKey)),
+        'Provider is not an active accredited provider'
+      );
+      await expectRejected(
+        () => program.methods
+          .assignTreatingProvider()
+          .accounts({
+            treatmentPassAccount: treatmentPassPDA,
+            authority: sponsorKeypair.publicKey,
+            patientRepresentative: null,
+            providerEntry: assignedProviderEntry,
+          })
+          .signers([sponsorKeypair])
+          .rpc(),
+        'Signer is neither the patient nor an appointed representative'
+      );
+
+      await assignProvider(assignedProviderEntry);
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.treatingProvider.toString()).to.equal(assignedProviderKeypair.publicKey.toString());
+    });
+
+    it('should only let the assigned provider start treatment, and only once the pass is fully funded', async () => {
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 200 });
+      await expectRejected(
+        () => changeStatus('startTreatment', [], assignedProviderKeypair, assignedProviderEntry),
+        'Treatment pass status does not allow this transition'
+      );
+
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 200 });
+      expect(await status()).to.have.property('fullyFunded');
+      await expectRejected(
+        async () => changeStatus(
+          'startTreatment', [], otherProviderKeypair, await verifierEntryAddress(program, otherProviderKeypair.publicKey)
+        ),
+        "Signer is not allowed to change this treatment's status"
+      );
+
+      await changeStatus('startTreatment', [], assignedProviderKeypair, assignedProviderEntry);
+      expect(await status()).to.have.property('treatmentInProgress');
+    });
+
+    it('should hold milestone reports while treatment is paused', async () => {
+      await changeStatus('pauseTreatment', ['Patient admitted for an unrelated infection'], lifecyclePatientKeypair);
+      expect(await status()).to.have.property('treatmentPaused');
+
+      await expectRejected(
+        () => submitMilestoneCompletion(program, { treatmentPassPDA, patientKeypair: lifecyclePatientKeypair, milestoneId: 1 }),
+        'Treatment is not in progress'
+      );
+
+      await changeStatus('resumeTreatment', [], assignedProviderKeypair, assignedProviderEntry);
+      expect(await status()).to.have.property('treatmentInProgress');
+    });
+
+    it('should only let the patient cancel, and never reopen a cancelled pass', async () => {
+      await expectRejected(
+        () => changeStatus('cancelTreatment', ['Provider cancelling on the patient'], assignedProviderKeypair, assignedProviderEntry),
+        "Signer is not allowed to change this treatment's status"
+      );
+
+      await changeStatus('cancelTreatment', ['Patient chose treatment closer to home'], lifecyclePatientKeypair);
+      expect(await status()).to.have.property('treatmentCancelled');
+
+      await expectRejected(
+        () => changeStatus('resumeTreatment', [], lifecyclePatientKeypair),
+        'Treatment pass status does not allow this transition'
+      );
+    });
+  });
+
+  describe('Milestone Progress Reporting', () => {
+    const passId = 2019;
+    let reportingPatientKeypair;
+    let reportingProviderKeypair;
+    let treatmentPassPDA;
+    let patientTokenAccount;
+    let reportingProviderEntry;
+
+    async function report(submitterKeypair, evidenceHash, submitterEntry = null) {
+      return program.methods
+        .submitMilestoneCompletion(1, evidenceHash)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          submitter: submitterKeypair.publicKey,
+          submitterEntry,
+          patientRepresentative: null,
+        })
+        .signers([submitterKeypair])
+        .rpc();
+    }
+
+    async function rejectReport(reason) {
+      return program.methods
+        .rejectMilestoneCompletion(1, reason)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          verifier: reportingProviderKeypair.publicKey,
+          verifierEntry: reportingProviderEntry,
+          elderCouncil: null,
+          patientRepresentative: null,
+        })
+        .signers([reportingProviderKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail('Should have rejected the milestone report');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    async function firstMilestone() {
+      return (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass.milestones[0];
+    }
+
+    before(async () => {
+      reportingPatientKeypair = Keypair.generate();
+      reportingProviderKeypair = Keypair.generate();
+      const sponsorKeypair = Keypair.generate();
+      await airdrop(connection, reportingPatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      const sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 600
+      );
+      patientTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, reportingPatientKeypair.publicKey, 0
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: reportingPatientKeypair.publicKey,
+        creator: reportingPatientKeypair,
+        passId,
+        amounts: [300, 300],
+        fundingMint,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 600 });
+      reportingProviderEntry = await registerVerifier(
+        program, reportingProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0050505'
+      );
+      await startTreatment(program, {
+        treatmentPassPDA,
+        patientKeypair: reportingPatientKeypair,
+        providerKeypair: reportingProviderKeypair,
+      });
+    });
+
+    it('should not verify a milestone nobody has reported complete', async () => {
+      await expectRejected(
+        () => verifyMilestone(program, {
+          treatmentPassPDA,
+          verifierKeypair: reportingProviderKeypair,
+          verifierEntry: reportingProviderEntry,
+          patientTokenAccount,
+          milestoneId: 1,
+        }),
+        'Invalid milestone status'
+      );
+    });
+
+    it('should only accept evidenced reports from the patient or care team', async () => {
+      await expectRejected(() => report(Keypair.generate(), 'QmWoundCheck'), 'Only the patient, their representative or care team may report milestone completion');
+      await expectRejected(() => report(reportingPatientKeypair, ''), 'Milestone completion requires an evidence hash');
+
+      await report(reportingPatientKeypair, 'QmWoundCheck');
+      const milestone = await firstMilestone();
+      expect(milestone.completionStatus).to.have.property('awaitingVerification');
+      expect(milestone.completionEvidence).to.equal('QmWoundCheck');
+    });
+
+    it('should send a rejected report back to the care team with a reason', async () => {
+      await expectRejected(() => rejectReport(''), 'Rejecting a milestone requires a reason');
+
+      await rejectReport('Discharge summary is missing the surgeon signature');
+      expect((await firstMilestone()).completionStatus).to.have.property('inProgress');
+
+      // An accredited provider on the care team may report in the patient's place
+      await report(reportingProviderKeypair, 'QmSignedDischargeSummary', reportingProviderEntry);
+      expect((await firstMilestone()).completionStatus).to.have.property('awaitingVerification');
+    });
+
+    it('should not let the patient verify their own funded milestone', async () => {
+      await expectRejected(
+        () => verifyMilestone(program, {
+          treatmentPassPDA,
+          verifierKeypair: reportingPatientKeypair,
+          verifierEntry: null,
+          patientTokenAccount,
+          milestoneId: 1,
+        }),
+        'Unauthorized verifier'
+      );
+    });
+  });
+
+  describe('Overdue Milestones', () => {
+    const passId = 2020;
+    let overduePatientKeypair;
+    let sponsorKeypair;
+    let overdueProviderKeypair;
+    let treatmentPassPDA;
+    let sponsorTokenAccount;
+
+    async function overdueReviewAddress(milestoneId, round = 0) {
+      const [overdueReviewPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('overdue_review'), treatmentPassPDA.toBuffer(), Buffer.from([milestoneId, round])],
+        program.programId
+      );
+      return overdueReviewPDA;
+    }
+
+    async function flag(milestoneId) {
+      return program.methods
+        .flagOverdueMilestone(milestoneId)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          overdueReview: await overdueReviewAddress(milestoneId),
+          cranker: provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    async function vote(voterKeypair, voterContribution) {
+      const overdueReviewPDA = await overdueReviewAddress(1);
+      const [voteRecordPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('overdue_vote'), overdueReviewPDA.toBuffer(), voterKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+
+      return program.methods
+        .voteOverdueResolution(1, { markFailed: {} }, null)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          overdueReview: overdueReviewPDA,
+          voteRecord: voteRecordPDA,
+          voter: voterKeypair.publicKey,
+          voterContribution,
+          elderCouncil: null,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([voterKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail('Should have rejected the overdue milestone action');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      overduePatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      overdueProviderKeypair = Keypair.generate();
+      await airdrop(connection, overduePatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 600
+      );
+      const now = await chainTime(connection);
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: overduePatientKeypair.publicKey,
+        creator: overduePatientKeypair,
+        passId,
+        fundingMint,
+        milestones: [milestone(1, 300, now + 30), milestone(2, 300, now + 60 * DAY_SECONDS)],
+      });
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair,
+        sponsorTokenAccount,
+        amount: 600,
+        milestoneAllocations: [{ milestoneId: 1, amount: new anchor.BN(100), refunded: false }],
+      });
+      await registerVerifier(program, overdueProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0060606');
+      await startTreatment(program, {
+        treatmentPassPDA,
+        patientKeypair: overduePatientKeypair,
+        providerKeypair: overdueProviderKeypair,
+      });
+    });
+
+    it('should not flag a milestone before its expected completion', async () => {
+      await expectRejected(() => flag(2), 'Milestone is not overdue');
+    });
+
+    it('should flag a late milestone and hold voting until the grace period has passed', async () => {
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      await waitForChainTime(connection, treatmentPass.milestones[0].expectedCompletion.toNumber());
+
+      await flag(1);
+      const flaggedPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(flaggedPass.milestones[0].overdueSince).to.not.be.null;
+      const review = (await program.account.overdueMilestoneReviewAccount.fetch(await overdueReviewAddress(1))).review;
+      expect(review.resolved).to.be.false;
+      expect(review.votingOpensAt.toNumber()).to.equal(review.flaggedAt.toNumber() + 7 * DAY_SECONDS);
+
+      await expectRejected(
+        async () => vote(sponsorKeypair, await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey)),
+        'Overdue grace period has not elapsed'
+      );
+    });
+
+    it('should only count votes from sponsors of the pass and council elders', async () => {
+      const outsiderKeypair = Keypair.generate();
+      await airdrop(connection, outsiderKeypair.publicKey, 1);
+
+      await expectRejected(
+        () => vote(outsiderKeypair, null),
+        'Only sponsors of this pass and elder council members may vote'
+      );
+    });
+
+    it('should only refund earmarks of milestones the vote has marked failed', async () => {
+      await expectRejected(
+        async () => program.methods
+          .claimFailedMilestoneRefund(1, null)
+          .accounts({
+            treatmentPassAccount: treatmentPassPDA,
+            sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey),
+            sponsor: sponsorKeypair.publicKey,
+            escrowAccount: await passEscrowAddress(program, treatmentPassPDA),
+            sponsorTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([sponsorKeypair])
+          .rpc(),
+        'Milestone has not been marked failed'
+      );
+    });
+  });
+
+  describe('Milestone Plan Amendments', () => {
+    const passId = 2021;
+    let amendingPatientKeypair;
+    let majorSponsorKeypair;
+    let minorSponsorKeypair;
+    let fundingMint;
+    let treatmentPassPDA;
+    let milestoneEscrowPDA;
+
+    async function amendmentAddress(amendmentId) {
+      const [amendmentPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('milestone_amendment'), treatmentPassPDA.toBuffer(), new anchor.BN(amendmentId).toArrayLike(Buffer, 'le', 2)],
+        program.programId
+      );
+      return amendmentPDA;
+    }
+
+    async function schedule(amounts) {
+      const now = await chainTime(connection);
+      return amounts.map(([milestoneId, amount], index) => milestone(milestoneId, amount, now + (90 + index) * DAY_SECONDS));
+    }
+
+    async function propose(amounts, proposerKeypair = amendingPatientKeypair) {
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      return program.methods
+        .proposeMilestoneAmendment(await schedule(amounts), 'Surgeon recommends a longer rehabilitation phase')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          amendmentAccount: await amendmentAddress(treatmentPass.amendmentCount),
+          proposer: proposerKeypair.publicKey,
+          patientRepresentative: null,
+          milestoneEscrow: milestoneEscrowPDA,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([proposerKeypair])
+        .rpc();
+    }
+
+    async function vote(amendmentId, sponsorKeypair, approve) {
+      const amendmentPDA = await amendmentAddress(amendmentId);
+      const [voteRecordPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('amendment_vote'), amendmentPDA.toBuffer(), sponsorKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+
+      return program.methods
+        .voteOnAmendment(amendmentId, approve, null)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          amendmentAccount: amendmentPDA,
+          voteRecord: voteRecordPDA,
+          sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey),
+          sponsor: sponsorKeypair.publicKey,
+          milestoneEscrow: milestoneEscrowPDA,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([sponsorKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail('Should have rejected the amendment');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    async function amendment(amendmentId) {
+      return (await program.account.milestoneAmendmentAccount.fetch(await amendmentAddress(amendmentId))).amendment;
+    }
+
+    async function milestoneAmounts() {
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      return treatmentPass.milestones.map(m => m.fundingAmount.toNumber());
+    }
+
+    before(async () => {
+      amendingPatientKeypair = Keypair.generate();
+      majorSponsorKeypair = Keypair.generate();
+      minorSponsorKeypair = Keypair.generate();
+      await airdrop(connection, amendingPatientKeypair.publicKey, 3);
+      await airdrop(connection, majorSponsorKeypair.publicKey, 1);
+      await airdrop(connection, minorSponsorKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      const majorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, majorSponsorKeypair.publicKey, 700
+      );
+      const minorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, minorSponsorKeypair.publicKey, 300
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: amendingPatientKeypair.publicKey,
+        creator: amendingPatientKeypair,
+        passId,
+        amounts: [400, 600],
+        fundingMint,
+      });
+      [milestoneEscrowPDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('milestone_escrow'), treatmentPassPDA.toBuffer()],
+        program.programId
+      );
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: majorSponsorKeypair,
+        sponsorTokenAccount: majorTokenAccount,
+        amount: 700,
+        milestoneAllocations: [{ milestoneId: 1, amount: new anchor.BN(300), refunded: false }],
+      });
+      await sponsorTreatment(program, {
+        treatmentPassPDA, sponsorKeypair: minorSponsorKeypair, sponsorTokenAccount: minorTokenAccount, amount: 300,
+      });
+    });
+
+    it('should only accept amendments from the patient that keep a valid, fully funded schedule', async () => {
+      await expectRejected(
+        () => propose([[1, 500], [2, 500]], minorSponsorKeypair),
+        'Only the patient or their representative may propose a milestone amendment'
+      );
+      await expectRejected(() => propose([[1, 500], [2, 600]]), 'Amended milestones must keep the same total funding');
+      await expectRejected(() => propose([[2, 400], [1, 600]]), 'Milestone ids must be in ascending order');
+      await expectRejected(
+        () => propose([[1, 200], [2, 800]]),
+        'Milestone funding cannot drop below the amount sponsors have earmarked to it'
+      );
+    });
+
+    it('should hold a pending amendment through its timelock and let the patient withdraw it', async () => {
+      await propose([[1, 500], [2, 500]]);
+      await expectRejected(() => propose([[1, 450], [2, 550]]), 'Another amendment is already pending');
+      await expectRejected(
+        async () => program.methods
+          .finalizeMilestoneAmendment(0)
+          .accounts({
+            treatmentPassAccount: treatmentPassPDA,
+            amendmentAccount: await amendmentAddress(0),
+            milestoneEscrow: milestoneEscrowPDA,
+          })
+          .rpc(),
+        'Amendment timelock has not elapsed'
+      );
+
+      await program.methods
+        .withdrawMilestoneAmendment(0)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          amendmentAccount: await amendmentAddress(0),
+          proposer: amendingPatientKeypair.publicKey,
+          patientRepresentative: null,
+        })
+        .signers([amendingPatientKeypair])
+        .rpc();
+
+      expect((await amendment(0)).status).to.have.property('withdrawn');
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.pendingAmendment).to.be.null;
+      expect(treatmentPass.amendmentHistory).to.have.lengthOf(1);
+    });
+
+    it('should apply an amendment as soon as enough sponsor weight approves it', async () => {
+      await propose([[1, 450], [2, 550]]);
+
+      // 300 of 1000 is short of the 60% approval share
+      await vote(1, minorSponsorKeypair, true);
+      expect((await amendment(1)).status).to.have.property('pending');
+
+      await vote(1, majorSponsorKeypair, true);
+      expect((await amendment(1)).status).to.have.property('applied');
+      expect(await milestoneAmounts()).to.deep.equal([450, 550]);
+    });
+
+    it('should outdate pending amendments and refuse new ones once a milestone escrow is bound', async () => {
+      await propose([[1, 500], [2, 500]]);
+      await createMilestoneEscrow(program, {
+        patientKeypair: amendingPatientKeypair,
+        passId,
+        fundingMint,
+        requirements: [{
+          verificationType: { healthcareProvider: {} },
+          requiredVerifier: providerKeypair.publicKey,
+          isMandatory: true,
+          eligibleVerifiers: [],
+          threshold: 1,
+        }],
+      });
+
+      await vote(2, majorSponsorKeypair, true);
+      expect((await amendment(2)).status).to.have.property('outdated');
+      expect(await milestoneAmounts()).to.deep.equal([450, 550]);
+
+      await expectRejected(
+        () => propose([[1, 500], [2, 500]]),
+        'Milestones cannot be amended once a milestone escrow is bound to the pass'
+      );
+    });
+  });
+
+  describe('Milestone Schedule Validation', () => {
+    const passId = 2022;
+    let schedulingPatientKeypair;
+    let fundingMint;
+
+    async function expectRejectedSchedule(milestones, expectedError, fundingTarget = null) {
+      try {
+        await createTreatmentPass(program, {
+          patient: schedulingPatientKeypair.publicKey,
+          creator: schedulingPatientKeypair,
+          passId,
+          fundingMint,
+          milestones,
+          fundingTarget,
+        });
+        expect.fail('Should have rejected the milestone schedule');
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      schedulingPatientKeypair = Keypair.generate();
+      await airdrop(connection, schedulingPatientKeypair.publicKey, 2);
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+    });
+
+    it('should require at least one milestone and milestones that add up to the funding target', async () => {
+      const later = (await chainTime(connection)) + 60 * DAY_SECONDS;
+      await expectRejectedSchedule([], 'Treatment pass requires at least one milestone', 500);
+      await expectRejectedSchedule(
+        [milestone(1, 300, later), milestone(2, 300, later)],
+        'Milestone funding amounts must add up to the funding target',
+        500
+      );
+      await expectRejectedSchedule(
+        [milestone(1, 500, later), milestone(2, 0, later)],
+        'Milestone funding amount must be greater than zero'
+      );
+    });
+
+    it('should require unique, ascending milestone ids', async () => {
+      const later = (await chainTime(connection)) + 60 * DAY_SECONDS;
+      await expectRejectedSchedule(
+        [milestone(1, 250, later), milestone(1, 250, later)],
+        'Milestone id appears more than once'
+      );
+      await expectRejectedSchedule(
+        [milestone(2, 250, later), milestone(1, 250, later)],
+        'Milestone ids must be in ascending order'
+      );
+    });
+
+    it('should require future, not yet started milestones with bounded descriptions', async () => {
+      const now = await chainTime(connection);
+      await expectRejectedSchedule(
+        [milestone(1, 500, now - DAY_SECONDS)],
+        'Milestone expected completion must be in the future'
+      );
+      await expectRejectedSchedule(
+        [{ ...milestone(1, 500, now + 60 * DAY_SECONDS), completionStatus: { verified: {} } }],
+        'New milestones must start as not started'
+      );
+      await expectRejectedSchedule(
+        [{ ...milestone(1, 500, now + 60 * DAY_SECONDS), description: 'd'.repeat(201) }],
+        'Milestone description exceeds maximum length'
+      );
+    });
+
+    it('should reset pledges a creator tries to pre-fill', async () => {
+      const now = await chainTime(connection);
+      const treatmentPassPDA = await createTreatmentPass(program, {
+        patient: schedulingPatientKeypair.publicKey,
+        creator: schedulingPatientKeypair,
+        passId,
+        fundingMint,
+        milestones: [{ ...milestone(1, 500, now + 60 * DAY_SECONDS), pledgedAmount: new anchor.BN(500) }],
+      });
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.milestones[0].pledgedAmount.toNumber()).to.equal(0);
+      expect(treatmentPass.status).to.have.property('fundingRequired');
+    });
+  });
+
+  describe('Anonymous Sponsorships', () => {
+    const passId = 2023;
+    const identitySalt = Buffer.alloc(32, 7);
+    let anonymousPatientKeypair;
+    let anonymousSponsorKeypair;
+    let impostorKeypair;
+    let treatmentPassPDA;
+    let anonymousTokenAccount;
+    let impostorTokenAccount;
+    let anonymousRecordPDA;
+
+    async function anonymousRecord() {
+      return (await program.account.sponsorContributionAccount.fetch(anonymousRecordPDA)).contribution;
+    }
+
+    async function reveal(salt, makePublic) {
+      return program.methods
+        .revealSponsorIdentity(Array.from(salt), makePublic)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          sponsorContribution: anonymousRecordPDA,
+          sponsor: anonymousSponsorKeypair.publicKey,
+        })
+        .signers([anonymousSponsorKeypair])
+        .rpc();
+    }
+
+    before(async () => {
+      anonymousPatientKeypair = Keypair.generate();
+      anonymousSponsorKeypair = Keypair.generate();
+      impostorKeypair = Keypair.generate();
+      await airdrop(connection, anonymousPatientKeypair.publicKey, 2);
+      await airdrop(connection, anonymousSponsorKeypair.publicKey, 1);
+      await airdrop(connection, impostorKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      anonymousTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, anonymousSponsorKeypair.publicKey, 400
+      );
+      impostorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, impostorKeypair.publicKey, 100
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: anonymousPatientKeypair.publicKey,
+        creator: anonymousPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+      anonymousRecordPDA = await sponsorContributionAddress(
+        program, treatmentPassPDA, anonymousSponsorKeypair.publicKey, identitySalt
+      );
+    });
+
+    it('should record an anonymous sponsorship under its commitment instead of the wallet', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: anonymousSponsorKeypair,
+        sponsorTokenAccount: anonymousTokenAccount,
+        amount: 200,
+        identitySalt,
+      });
+
+      const contribution = await anonymousRecord();
+      expect(contribution.anonymous).to.be.true;
+      expect(contribution.wallet.toString()).to.equal(PublicKey.default.toString());
+      expect(Buffer.from(contribution.identityCommitment)).to.deep.equal(
+        identityCommitment(treatmentPassPDA, anonymousSponsorKeypair.publicKey, identitySalt)
+      );
+      const walletRecord = await program.account.sponsorContributionAccount.fetchNullable(
+        await sponsorContributionAddress(program, treatmentPassPDA, anonymousSponsorKeypair.publicKey)
+      );
+      expect(walletRecord).to.be.null;
+    });
+
+    it('should only let the holder of the salt top up the anonymous record', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: anonymousSponsorKeypair,
+        sponsorTokenAccount: anonymousTokenAccount,
+        amount: 100,
+        identitySalt,
+      });
+      expect((await anonymousRecord()).amountContributed.toNumber()).to.equal(300);
+
+      try {
+        await sponsorTreatment(program, {
+          treatmentPassPDA,
+          sponsorKeypair: impostorKeypair,
+          sponsorTokenAccount: impostorTokenAccount,
+          amount: 100,
+          identitySalt,
+          sponsorContribution: anonymousRecordPDA,
+        });
+        expect.fail('Should have rejected a top-up of someone else\'s anonymous record');
+      } catch (error) {
+        expect(error.message).to.include('A seeds constraint was violated');
+      }
+
+      expect((await anonymousRecord()).amountContributed.toNumber()).to.equal(300);
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.sponsorCount).to.equal(1);
+    });
+
+    it('should keep the record reachable by its salt after the sponsor makes it public', async () => {
+      try {
+        await reveal(Buffer.alloc(32, 8), true);
+        expect.fail('Should have rejected a reveal with the wrong salt');
+      } catch (error) {
+        expect(error.message).to.include('A seeds constraint was violated');
+      }
+
+      await reveal(identitySalt, true);
+      let contribution = await anonymousRecord();
+      expect(contribution.anonymous).to.be.false;
+      expect(contribution.wallet.toString()).to.equal(anonymousSponsorKeypair.publicKey.toString());
+
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: anonymousSponsorKeypair,
+        sponsorTokenAccount: anonymousTokenAccount,
+        amount: 50,
+        identitySalt,
+      });
+      contribution = await anonymousRecord();
+      expect(contribution.amountContributed.toNumber()).to.equal(350);
+    });
+  });
+
+  describe('Sponsor Message Moderation', () => {
+    const passId = 2024;
+    const hurtfulMessage = 'You brought this on yourself';
+    let moderatedPatientKeypair;
+    let messagingSponsorKeypair;
+    let secondSponsorKeypair;
+    let strangerKeypair;
+    let treatmentPassPDA;
+    let messagingTokenAccount;
+    let secondTokenAccount;
+    let messagingRecordPDA;
+    let secondRecordPDA;
+    let registryPDA;
+
+    async function hideMessage(sponsorContribution, moderatorKeypair, registryAccount = null) {
+      return program.methods
+        .hideSponsorMessage()
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          sponsorContribution,
+          moderator: moderatorKeypair.publicKey,
+          patientRepresentative: null,
+          registryAccount,
+        })
+        .signers([moderatorKeypair])
+        .rpc();
+    }
+
+    async function contributionAt(sponsorContribution) {
+      return (await program.account.sponsorContributionAccount.fetch(sponsorContribution)).contribution;
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      moderatedPatientKeypair = Keypair.generate();
+      messagingSponsorKeypair = Keypair.generate();
+      secondSponsorKeypair = Keypair.generate();
+      strangerKeypair = Keypair.generate();
+      await airdrop(connection, moderatedPatientKeypair.publicKey, 2);
+      await airdrop(connection, messagingSponsorKeypair.publicKey, 1);
+      await airdrop(connection, secondSponsorKeypair.publicKey, 1);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      messagingTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, messagingSponsorKeypair.publicKey, 300
+      );
+      secondTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, secondSponsorKeypair.publicKey, 100
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: moderatedPatientKeypair.publicKey,
+        creator: moderatedPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+      messagingRecordPDA = await sponsorContributionAddress(
+        program, treatmentPassPDA, messagingSponsorKeypair.publicKey
+      );
+      secondRecordPDA = await sponsorContributionAddress(
+        program, treatmentPassPDA, secondSponsorKeypair.publicKey
+      );
+
+      // The platform authority moderates through the verifier registry singleton
+      registryPDA = await verifierRegistry(program);
+    });
+
+    it('should reject a sponsor message longer than the maximum length', async () => {
+      await expectRejected(() => sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: messagingSponsorKeypair,
+        sponsorTokenAccount: messagingTokenAccount,
+        amount: 100,
+        sponsorMessage: 'a'.repeat(281),
+      }), 'Sponsor message exceeds maximum length');
+    });
+
+    it('should store the message and its hash on the sponsor\'s own record', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: messagingSponsorKeypair,
+        sponsorTokenAccount: messagingTokenAccount,
+        amount: 100,
+        sponsorMessage: hurtfulMessage,
+      });
+
+      const contribution = await contributionAt(messagingRecordPDA);
+      expect(contribution.sponsorMessage).to.equal(hurtfulMessage);
+      expect(Buffer.from(contribution.sponsorMessageHash)).to.deep.equal(sha256(Buffer.from(hurtfulMessage)));
+      expect(contribution.sponsorMessageHidden).to.be.false;
+    });
+
+    it('should not let a stranger hide a sponsor message', async () => {
+      await expectRejected(
+        () => hideMessage(messagingRecordPDA, strangerKeypair),
+        'Only the patient, their representative or the platform moderator may hide sponsor messages'
+      );
+    });
+
+    it('should let the patient hide a message while keeping its hash', async () => {
+      await hideMessage(messagingRecordPDA, moderatedPatientKeypair);
+
+      const contribution = await contributionAt(messagingRecordPDA);
+      expect(contribution.sponsorMessage).to.be.null;
+      expect(contribution.sponsorMessageHidden).to.be.true;
+      expect(Buffer.from(contribution.sponsorMessageHash)).to.deep.equal(sha256(Buffer.from(hurtfulMessage)));
+
+      await expectRejected(
+        () => hideMessage(messagingRecordPDA, moderatedPatientKeypair),
+        'Contribution has no visible sponsor message'
+      );
+    });
+
+    it('should refuse new messages on a hidden record but still accept top-ups', async () => {
+      await expectRejected(() => sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: messagingSponsorKeypair,
+        sponsorTokenAccount: messagingTokenAccount,
+        amount: 50,
+        sponsorMessage: hurtfulMessage,
+      }), 'A message on this contribution was hidden, so it accepts no new messages');
+
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: messagingSponsorKeypair,
+        sponsorTokenAccount: messagingTokenAccount,
+        amount: 50,
+      });
+      const contribution = await contributionAt(messagingRecordPDA);
+      expect(contribution.amountContributed.toNumber()).to.equal(150);
+      expect(contribution.sponsorMessage).to.be.null;
+    });
+
+    it('should let the platform authority hide a message through the verifier registry', async () => {
+      await sponsorTreatment(program, {
+        treatmentPassPDA,
+        sponsorKeypair: secondSponsorKeypair,
+        sponsorTokenAccount: secondTokenAccount,
+        amount: 100,
+        sponsorMessage: 'Nobody should pay for this',
+      });
+
+      await hideMessage(secondRecordPDA, provider.wallet.payer, registryPDA);
+
+      const contribution = await contributionAt(secondRecordPDA);
+      expect(contribution.sponsorMessage).to.be.null;
+      expect(contribution.sponsorMessageHidden).to.be.true;
+    });
+  });
+
+  describe('Treatment Pass Endorsements', () => {
+    const passId = 2025;
+    let endorsedPatientKeypair;
+    let endorserKeypair;
+    let fundingMint;
+    let treatmentPassPDA;
+
+    async function endorserAccounts(endorser) {
+      const [endorserProfile] = await PublicKey.findProgramAddress(
+        [Buffer.from('member_profile'), endorser.toBuffer()],
+        MEMBER_REGISTRY_PROGRAM_ID
+      );
+      const [endorserReputation] = await PublicKey.findProgramAddress(
+        [Buffer.from('endorser_reputation'), endorser.toBuffer()],
+        program.programId
+      );
+      return { endorserProfile, endorserReputation };
+    }
+
+    async function expectEndorsementRejected(endorser, accounts, expectedError) {
+      try {
+        await createTreatmentPass(program, {
+          patient: endorsedPatientKeypair.publicKey,
+          creator: endorsedPatientKeypair,
+          passId,
+          amounts: [1000],
+          fundingMint,
+          endorserKeypair: endorser,
+          endorserAccounts: accounts,
+        });
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+      expect(await program.account.treatmentPassAccount.fetchNullable(treatmentPassPDA)).to.be.null;
+    }
+
+    before(async () => {
+      endorsedPatientKeypair = Keypair.generate();
+      endorserKeypair = Keypair.generate();
+      await airdrop(connection, endorsedPatientKeypair.publicKey, 2);
+      await airdrop(connection, endorserKeypair.publicKey, 1);
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      treatmentPassPDA = await treatmentPassAddress(program, endorsedPatientKeypair.publicKey, passId);
+    });
+
+    it('should reject an endorser without a member profile and reputation account', async () => {
+      await expectEndorsementRejected(
+        endorserKeypair,
+        {},
+        'Endorsement requires the endorser, their member profile and their reputation account'
+      );
+    });
+
+    it('should reject a patient endorsing their own treatment pass', async () => {
+      await expectEndorsementRejected(
+        endorsedPatientKeypair,
+        await endorserAccounts(endorsedPatientKeypair.publicKey),
+        'Patients cannot endorse their own treatment pass'
+      );
+    });
+
+    it('should reject an endorser who is not registered in the member registry', async () => {
+      await expectEndorsementRejected(
+        endorserKeypair,
+        await endorserAccounts(endorserKeypair.publicKey),
+        'Account is not a member profile from the Ubuntu member registry'
+      );
+    });
+
+    it('should reject a member profile that is not the endorser\'s registry account', async () => {
+      const { endorserReputation } = await endorserAccounts(endorserKeypair.publicKey);
+      await expectEndorsementRejected(
+        endorserKeypair,
+        { endorserProfile: endorserKeypair.publicKey, endorserReputation },
+        'Account is not a member profile from the Ubuntu member registry'
+      );
+    });
+
+    it('should create an unendorsed pass without touching endorsement accounts', async () => {
+      await createTreatmentPass(program, {
+        patient: endorsedPatientKeypair.publicKey,
+        creator: endorsedPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.ubuntuCommunityEndorsement).to.be.null;
+      const { endorserReputation } = await endorserAccounts(endorserKeypair.publicKey);
+      expect(await program.account.endorserReputationAccount.fetchNullable(endorserReputation)).to.be.null;
+    });
+  });
+
+  describe('Fraud Reports', () => {
+    const passId = 2026;
+    const completedPassId = 2027;
+    let fraudulentPatientKeypair;
+    let completedPatientKeypair;
+    let sponsorKeypair;
+    let treatingProviderKeypair;
+    let strangerKeypair;
+    let treatmentPassPDA;
+    let completedPassPDA;
+    let sponsorTokenAccount;
+    let registryPDA;
+
+    async function markFraudulent(passPDA, authorityKeypair, reason) {
+      return program.methods
+        .markTreatmentFraudulent(reason)
+        .accounts({
+          treatmentPassAccount: passPDA,
+          authority: authorityKeypair.publicKey,
+          registryAccount: registryPDA,
+          elderCouncil: null,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      fraudulentPatientKeypair = Keypair.generate();
+      completedPatientKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      treatingProviderKeypair = Keypair.generate();
+      strangerKeypair = Keypair.generate();
+      await airdrop(connection, fraudulentPatientKeypair.publicKey, 2);
+      await airdrop(connection, completedPatientKeypair.publicKey, 2);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 700
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: fraudulentPatientKeypair.publicKey,
+        creator: fraudulentPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 400 });
+
+      // A second pass is taken through to completion
+      completedPassPDA = await createTreatmentPass(program, {
+        patient: completedPatientKeypair.publicKey,
+        creator: completedPatientKeypair,
+        passId: completedPassId,
+        amounts: [300],
+        fundingMint,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA: completedPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 300 });
+      const treatingProviderEntry = await registerVerifier(
+        program, treatingProviderKeypair.publicKey, { accreditedProvider: {} }, 'HPCSA-MP-0026026'
+      );
+      await startTreatment(program, {
+        treatmentPassPDA: completedPassPDA,
+        patientKeypair: completedPatientKeypair,
+        providerKeypair: treatingProviderKeypair,
+      });
+      await submitMilestoneCompletion(program, {
+        treatmentPassPDA: completedPassPDA,
+        patientKeypair: completedPatientKeypair,
+        milestoneId: 1,
+      });
+      await verifyMilestone(program, {
+        treatmentPassPDA: completedPassPDA,
+        verifierKeypair: treatingProviderKeypair,
+        verifierEntry: treatingProviderEntry,
+        patientTokenAccount: await fundedTokenAccount(
+          connection, provider.wallet.payer, fundingMint, completedPatientKeypair.publicKey, 0
+        ),
+        milestoneId: 1,
+      });
+
+      registryPDA = await verifierRegistry(program);
+    });
+
+    it('should only let governance or the elder council mark a pass fraudulent', async () => {
+      await expectRejected(
+        () => markFraudulent(treatmentPassPDA, strangerKeypair, 'Forged treatment quote'),
+        'Only governance or the elder council may mark a pass fraudulent'
+      );
+      await expectRejected(
+        () => markFraudulent(treatmentPassPDA, provider.wallet.payer, 'a'.repeat(257)),
+        'Fraud reason exceeds maximum length'
+      );
+    });
+
+    it('should cancel a pass that governance marks fraudulent, once', async () => {
+      await markFraudulent(treatmentPassPDA, provider.wallet.payer, 'Forged treatment quote');
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.flaggedFraudulent).to.be.true;
+      expect(treatmentPass.status).to.have.property('treatmentCancelled');
+
+      await expectRejected(
+        () => markFraudulent(treatmentPassPDA, provider.wallet.payer, 'Forged treatment quote'),
+        'Treatment pass is already marked fraudulent'
+      );
+    });
+
+    it('should refund sponsors of a pass marked fraudulent in full', async () => {
+      const balanceBefore = Number((await getAccount(connection, sponsorTokenAccount)).amount);
+      await claimSponsorRefund(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount });
+
+      expect(Number((await getAccount(connection, sponsorTokenAccount)).amount)).to.equal(balanceBefore + 400);
+      expect(Number((await getAccount(connection, await passEscrowAddress(program, treatmentPassPDA))).amount)).to.equal(0);
+    });
+
+    it('should not let a completed pass be marked fraudulent', async () => {
+      await expectRejected(
+        () => markFraudulent(completedPassPDA, provider.wallet.payer, 'Reported after completion'),
+        'Completed treatment passes cannot be marked fraudulent'
+      );
+    });
+  });
+
+  describe('Elder Council Quorum', () => {
+    const approvalPassId = 2028;
+    const disapprovalPassId = 2029;
+    let reviewedPatientKeypair;
+    let elderKeypairs;
+    let outsiderKeypair;
+    let approvalPassPDA;
+    let disapprovalPassPDA;
+    let elderCouncilPDA;
+
+    async function manageCouncil(instruction, args, authority = provider.wallet.payer) {
+      return program.methods[instruction](...args)
+        .accounts({
+          elderCouncil: elderCouncilPDA,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    async function referToCouncil(treatmentPassPDA) {
+      return program.methods
+        .requestElderCouncilReview('Family asked for the elders\' guidance')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: reviewedPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([reviewedPatientKeypair])
+        .rpc();
+    }
+
+    async function treatmentPassAt(treatmentPassPDA) {
+      return (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      reviewedPatientKeypair = Keypair.generate();
+      elderKeypairs = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      outsiderKeypair = Keypair.generate();
+      await airdrop(connection, reviewedPatientKeypair.publicKey, 2);
+      await airdrop(connection, outsiderKeypair.publicKey, 1);
+      for (const elderKeypair of elderKeypairs) {
+        await airdrop(connection, elderKeypair.publicKey, 1);
+      }
+
+      // Start from a council of exactly this suite's four elders
+      elderCouncilPDA = await elderCouncil(program);
+      const council = (await program.account.elderCouncilAccount.fetch(elderCouncilPDA)).council;
+      for (const member of council.members) {
+        await manageCouncil('removeElderCouncilMember', [member]);
+      }
+      await manageCouncil('setElderCouncilThresholds', [2, 6667]);
+      for (const elderKeypair of elderKeypairs) {
+        await manageCouncil('addElderCouncilMember', [elderKeypair.publicKey]);
+      }
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      approvalPassPDA = await createTreatmentPass(program, {
+        patient: reviewedPatientKeypair.publicKey,
+        creator: reviewedPatientKeypair,
+        passId: approvalPassId,
+        amounts: [1000],
+        fundingMint,
+      });
+      disapprovalPassPDA = await createTreatmentPass(program, {
+        patient: reviewedPatientKeypair.publicKey,
+        creator: reviewedPatientKeypair,
+        passId: disapprovalPassId,
+        amounts: [1000],
+        fundingMint,
+      });
+      await referToCouncil(approvalPassPDA);
+      await referToCouncil(disapprovalPassPDA);
+    });
+
+    it('should only let governance manage the council within the allowed thresholds', async () => {
+      await expectRejected(
+        () => manageCouncil('addElderCouncilMember', [outsiderKeypair.publicKey], outsiderKeypair),
+        'Signer is not the elder council governance authority'
+      );
+      await expectRejected(
+        () => manageCouncil('addElderCouncilMember', [elderKeypairs[0].publicKey]),
+        'Elder is already a council member'
+      );
+      await expectRejected(
+        () => manageCouncil('setElderCouncilThresholds', [1, 6667]),
+        'Elder council quorum is outside the allowed range'
+      );
+      await expectRejected(
+        () => manageCouncil('setElderCouncilThresholds', [2, 5000]),
+        'Disapproval supermajority is outside the allowed range'
+      );
+    });
+
+    it('should reject a review from a wallet outside the council', async () => {
+      await expectRejected(
+        () => elderCouncilReview(program, { treatmentPassPDA: approvalPassPDA, elderKeypair: outsiderKeypair, decision: { approved: {} } }),
+        'Signer is not an elder council member'
+      );
+    });
+
+    it('should only approve a pass once a quorum of elders agrees', async () => {
+      await elderCouncilReview(program, { treatmentPassPDA: approvalPassPDA, elderKeypair: elderKeypairs[0], decision: { approved: {} } });
+
+      let treatmentPass = await treatmentPassAt(approvalPassPDA);
+      expect(treatmentPass.status).to.have.property('elderCouncilReview');
+      expect(treatmentPass.elderCouncilApproval).to.be.false;
+
+      // Approvals with and without guidance count towards the same outcome
+      await elderCouncilReview(program, {
+        treatmentPassPDA: approvalPassPDA,
+        elderKeypair: elderKeypairs[1],
+        decision: { approvedWithGuidance: {} },
+      });
+
+      treatmentPass = await treatmentPassAt(approvalPassPDA);
+      expect(treatmentPass.status).to.have.property('fundingRequired');
+      expect(treatmentPass.elderCouncilApproval).to.be.true;
+      expect(treatmentPass.approvingElder.toString()).to.equal(elderKeypairs[1].publicKey.toString());
+      expect(treatmentPass.elderReviewRound).to.equal(1);
+    });
+
+    it('should need a supermajority of current members to disapprove, ignoring removed elders', async () => {
+      await elderCouncilReview(program, {
+        treatmentPassPDA: disapprovalPassPDA,
+        elderKeypair: elderKeypairs[0],
+        decision: { disapproved: {} },
+      });
+      await manageCouncil('removeElderCouncilMember', [elderKeypairs[0].publicKey]);
+
+      // The removed elder's vote no longer counts, and two of three falls short of two thirds
+      for (const elderKeypair of elderKeypairs.slice(1, 3)) {
+        await elderCouncilReview(program, { treatmentPassPDA: disapprovalPassPDA, elderKeypair, decision: { disapproved: {} } });
+        expect((await treatmentPassAt(disapprovalPassPDA)).status).to.have.property('elderCouncilReview');
+      }
+
+      await elderCouncilReview(program, {
+        treatmentPassPDA: disapprovalPassPDA,
+        elderKeypair: elderKeypairs[3],
+        decision: { disapproved: {} },
+      });
+      expect((await treatmentPassAt(disapprovalPassPDA)).status).to.have.property('treatmentCancelled');
+    });
+  });
+
+  describe('Elder and Community Review', () => {
+    const passId = 2030;
+    const rejectedPassId = 2031;
+    const healingCircle = {
+      practiceType: 'Ubuntu healing circle',
+      traditionalHealer: null,
+      integrationPlan: 'Weekly circle with family during rehabilitation',
+    };
+    let reviewedPatientKeypair;
+    let elderKeypairs;
+    let fundingMint;
+    let treatmentPassPDA;
+
+    async function referToCouncil() {
+      return program.methods
+        .requestElderCouncilReview('Family asked for the elders\' guidance')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: reviewedPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([reviewedPatientKeypair])
+        .rpc();
+    }
+
+    async function reviewByQuorum(decision) {
+      for (const elderKeypair of elderKeypairs) {
+        await elderCouncilReview(program, { treatmentPassPDA, elderKeypair, decision });
+      }
+    }
+
+    async function updateDiasporaConnection(connectionStrength) {
+      return program.methods
+        .updateDiasporaConnection('Gugulethu', 'London', connectionStrength, [provider.wallet.publicKey], true)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: reviewedPatientKeypair.publicKey,
+          patientRepresentative: null,
+        })
+        .signers([reviewedPatientKeypair])
+        .rpc();
+    }
+
+    async function treatmentPass() {
+      return (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+    }
+
+    before(async () => {
+      reviewedPatientKeypair = Keypair.generate();
+      elderKeypairs = [Keypair.generate(), Keypair.generate()];
+      await airdrop(connection, reviewedPatientKeypair.publicKey, 2);
+
+      // Two new elders make up the quorum on their own
+      const elderCouncilPDA = await elderCouncil(program);
+      await program.methods
+        .setElderCouncilThresholds(2, 6667)
+        .accounts({ elderCouncil: elderCouncilPDA, authority: provider.wallet.publicKey })
+        .rpc();
+      for (const elderKeypair of elderKeypairs) {
+        await airdrop(connection, elderKeypair.publicKey, 1);
+        await program.methods
+          .addElderCouncilMember(elderKeypair.publicKey)
+          .accounts({ elderCouncil: elderCouncilPDA, authority: provider.wallet.publicKey })
+          .rpc();
+      }
+
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      const now = await chainTime(connection);
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: reviewedPatientKeypair.publicKey,
+        creator: reviewedPatientKeypair,
+        passId,
+        amounts: [],
+        fundingMint,
+        milestones: [{ ...milestone(1, 1000, now + 60 * DAY_SECONDS), verificationRequired: { integratedVerification: {} } }],
+        traditionalHealing: healingCircle,
+      });
+    });
+
+    it('should store the traditional healing component on a versioned pass', async () => {
+      const pass = await treatmentPass();
+      expect(pass.version).to.equal(3);
+      expect(pass.traditionalHealingComponent).to.deep.equal(healingCircle);
+      expect(pass.elderCouncilApproval).to.be.false;
+      expect(pass.approvingElder).to.be.null;
+    });
+
+    it('should reject traditional healing details that exceed their maximum length', async () => {
+      try {
+        await createTreatmentPass(program, {
+          patient: reviewedPatientKeypair.publicKey,
+          creator: reviewedPatientKeypair,
+          passId: rejectedPassId,
+          amounts: [1000],
+          fundingMint,
+          traditionalHealing: { ...healingCircle, practiceType: 'a'.repeat(65) },
+        });
+        expect.fail('Should have rejected an over-long healing practice');
+      } catch (error) {
+        expect(error.message).to.include('Traditional healing details exceed maximum length');
+      }
+    });
+
+    it('should move a pass from elder review to community review and back', async () => {
+      await referToCouncil();
+      expect((await treatmentPass()).status).to.have.property('elderCouncilReview');
+
+      await reviewByQuorum({ deferToCommunity: {} });
+      expect((await treatmentPass()).status).to.have.property('communityReview');
+
+      await referToCouncil();
+      await reviewByQuorum({ approved: {} });
+
+      const pass = await treatmentPass();
+      expect(pass.status).to.have.property('fundingRequired');
+      expect(pass.elderCouncilApproval).to.be.true;
+      expect(pass.elderReviewRound).to.equal(2);
+      expect(pass.milestones[0].elderCouncilReview).to.not.be.null;
+    });
+
+    it('should record a validated diaspora connection', async () => {
+      try {
+        await updateDiasporaConnection(101);
+        expect.fail('Should have rejected a connection strength above 100');
+      } catch (error) {
+        expect(error.message).to.include('Diaspora connection strength must be between 0 and 100');
+      }
+
+      await updateDiasporaConnection(80);
+      const diasporaConnection = (await treatmentPass()).diasporaConnection;
+      expect(diasporaConnection.originCommunity).to.equal('Gugulethu');
+      expect(diasporaConnection.connectionStrength).to.equal(80);
+      expect(diasporaConnection.remittanceCapability).to.be.true;
+      expect(diasporaConnection.networkContacts.map(String)).to.deep.equal([provider.wallet.publicKey.toString()]);
+    });
+  });
+
+  describe('Diaspora and Healing Record Authorization', () => {
+    const passId = 2032;
+    let guardedPatientKeypair;
+    let familyMemberKeypair;
+    let sponsorKeypair;
+    let strangerKeypair;
+    let treatmentPassPDA;
+
+    async function updateDiasporaConnection(authorityKeypair, patientRepresentative = null) {
+      return program.methods
+        .updateDiasporaConnection('Mthatha', 'Toronto', 70, [], false)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: authorityKeypair.publicKey,
+          patientRepresentative,
+        })
+        .signers([authorityKeypair])
+        .rpc();
+    }
+
+    async function addHealingKnowledge(signerKeypair, sponsorWallet, healingKnowledge) {
+      return program.methods
+        .addTraditionalHealingKnowledge(sponsorWallet, healingKnowledge, null)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, sponsorWallet),
+          sponsor: signerKeypair.publicKey,
+        })
+        .signers([signerKeypair])
+        .rpc();
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      guardedPatientKeypair = Keypair.generate();
+      familyMemberKeypair = Keypair.generate();
+      sponsorKeypair = Keypair.generate();
+      strangerKeypair = Keypair.generate();
+      await airdrop(connection, guardedPatientKeypair.publicKey, 2);
+      await airdrop(connection, familyMemberKeypair.publicKey, 1);
+      await airdrop(connection, sponsorKeypair.publicKey, 1);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      const sponsorTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, sponsorKeypair.publicKey, 100
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: guardedPatientKeypair.publicKey,
+        creator: guardedPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+      await sponsorTreatment(program, { treatmentPassPDA, sponsorKeypair, sponsorTokenAccount, amount: 100 });
+    });
+
+    it('should not let a stranger rewrite the patient\'s diaspora record', async () => {
+      await expectRejected(
+        () => updateDiasporaConnection(strangerKeypair),
+        'Signer is neither the patient nor an appointed representative'
+      );
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.diasporaConnection).to.be.null;
+    });
+
+    it('should validate representative appointments', async () => {
+      await expectRejected(
+        () => appointRepresentative(program, {
+          patientKeypair: guardedPatientKeypair,
+          representativeKeypair: guardedPatientKeypair,
+          role: { familyMember: {} },
+          scopes: [{ manageTreatment: {} }],
+        }),
+        'Patients cannot appoint themselves as their own representative'
+      );
+      await expectRejected(
+        () => appointRepresentative(program, {
+          patientKeypair: guardedPatientKeypair,
+          representativeKeypair: familyMemberKeypair,
+          role: { familyMember: {} },
+          scopes: [],
+        }),
+        'Representative must be granted at least one scope'
+      );
+    });
+
+    it('should let an appointed family member update the diaspora record', async () => {
+      const familyMemberPDA = await appointRepresentative(program, {
+        patientKeypair: guardedPatientKeypair,
+        representativeKeypair: familyMemberKeypair,
+        role: { familyMember: {} },
+        scopes: [{ manageTreatment: {} }],
+      });
+
+      await updateDiasporaConnection(familyMemberKeypair, familyMemberPDA);
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.diasporaConnection.currentLocation).to.equal('Toronto');
+    });
+
+    it('should only let the sponsor add healing knowledge to their own record', async () => {
+      await expectRejected(
+        () => addHealingKnowledge(strangerKeypair, sponsorKeypair.publicKey, 'Rooibos for the family'),
+        'Signer is not the sponsor of this contribution'
+      );
+      await expectRejected(
+        () => addHealingKnowledge(strangerKeypair, strangerKeypair.publicKey, 'Rooibos for the family'),
+        'The program expected this account to be already initialized'
+      );
+      await expectRejected(
+        () => addHealingKnowledge(sponsorKeypair, sponsorKeypair.publicKey, ''),
+        'Traditional healing knowledge must not be empty'
+      );
+
+      await addHealingKnowledge(sponsorKeypair, sponsorKeypair.publicKey, 'Rooibos for the family');
+      const contribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, treatmentPassPDA, sponsorKeypair.publicKey)
+      )).contribution;
+      expect(contribution.traditionalHealingKnowledge).to.equal('Rooibos for the family');
+    });
+  });
+
+  describe('Diaspora Remittance Pledges', () => {
+    const passId = 2033;
+    let remittingPatientKeypair;
+    let diasporaContactKeypair;
+    let strangerKeypair;
+    let treatmentPassPDA;
+    let escrowPDA;
+    let contactTokenAccount;
+    let strangerTokenAccount;
+    let remittancePledgePDA;
+
+    async function createPledge(contributorKeypair, sourceTokenAccount, amountPerPeriod, periodSeconds, periods) {
+      const [pledgePDA] = await PublicKey.findProgramAddress(
+        [Buffer.from('remittance_pledge'), treatmentPassPDA.toBuffer(), contributorKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .createRemittancePledge(new anchor.BN(amountPerPeriod), new anchor.BN(periodSeconds), periods)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          remittancePledge: pledgePDA,
+          sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, contributorKeypair.publicKey),
+          contributor: contributorKeypair.publicKey,
+          sourceTokenAccount,
+          escrowAccount: escrowPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([contributorKeypair])
+        .rpc();
+      return pledgePDA;
+    }
+
+    async function executePledge({ escrowAccount = escrowPDA, contributorKeypair = null } = {}) {
+      return program.methods
+        .executeRemittancePledge()
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          remittancePledge: remittancePledgePDA,
+          sponsorContribution: await sponsorContributionAddress(program, treatmentPassPDA, diasporaContactKeypair.publicKey),
+          sourceTokenAccount: contactTokenAccount,
+          escrowAccount,
+          contributor: contributorKeypair && contributorKeypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers(contributorKeypair ? [contributorKeypair] : [])
+        .rpc();
+    }
+
+    async function pledge() {
+      return (await program.account.remittancePledgeAccount.fetch(remittancePledgePDA)).pledge;
+    }
+
+    async function expectRejected(action, expectedError) {
+      try {
+        await action();
+        expect.fail(`Should have failed with: ${expectedError}`);
+      } catch (error) {
+        expect(error.message).to.include(expectedError);
+      }
+    }
+
+    before(async () => {
+      remittingPatientKeypair = Keypair.generate();
+      diasporaContactKeypair = Keypair.generate();
+      strangerKeypair = Keypair.generate();
+      await airdrop(connection, remittingPatientKeypair.publicKey, 2);
+      await airdrop(connection, diasporaContactKeypair.publicKey, 1);
+      await airdrop(connection, strangerKeypair.publicKey, 1);
+
+      const fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+      contactTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, diasporaContactKeypair.publicKey, 500
+      );
+      strangerTokenAccount = await fundedTokenAccount(
+        connection, provider.wallet.payer, fundingMint, strangerKeypair.publicKey, 500
+      );
+      treatmentPassPDA = await createTreatmentPass(program, {
+        patient: remittingPatientKeypair.publicKey,
+        creator: remittingPatientKeypair,
+        passId,
+        amounts: [1000],
+        fundingMint,
+      });
+      escrowPDA = await passEscrowAddress(program, treatmentPassPDA);
+
+      await program.methods
+        .updateDiasporaConnection('Bulawayo', 'Johannesburg', 90, [diasporaContactKeypair.publicKey], true)
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: remittingPatientKeypair.publicKey,
+          patientRepresentative: null,
+        })
+        .signers([remittingPatientKeypair])
+        .rpc();
+    });
+
+    it('should only accept pledges from listed contacts within the allowed schedule', async () => {
+      await expectRejected(
+        () => createPledge(strangerKeypair, strangerTokenAccount, 100, 30 * DAY_SECONDS, 3),
+        "Contributor is not a contact on the pass's diaspora network"
+      );
+      await expectRejected(
+        () => createPledge(diasporaContactKeypair, contactTokenAccount, 100, DAY_SECONDS, 3),
+        'Remittance period must be between one week and 90 days'
+      );
+      await expectRejected(
+        () => createPledge(diasporaContactKeypair, contactTokenAccount, 100, 30 * DAY_SECONDS, 37),
+        'Pledge must cover between 1 and 36 periods'
+      );
+    });
+
+    it('should delegate the pledged total to the pledge', async () => {
+      remittancePledgePDA = await createPledge(diasporaContactKeypair, contactTokenAccount, 100, 30 * DAY_SECONDS, 3);
+
+      const sourceAccount = await getAccount(connection, contactTokenAccount);
+      expect(sourceAccount.delegate.toString()).to.equal(remittancePledgePDA.toString());
+      expect(Number(sourceAccount.delegatedAmount)).to.equal(300);
+      expect((await pledge()).status).to.have.property('active');
+    });
+
+    it('should only remit into the pass\'s own escrow', async () => {
+      await expectRejected(
+        () => executePledge({ escrowAccount: strangerTokenAccount }),
+        'Escrow account is not controlled by this treatment pass'
+      );
+    });
+
+    it('should remit the first period straight away and then wait a full period', async () => {
+      await executePledge();
+
+      expect(Number((await getAccount(connection, escrowPDA)).amount)).to.equal(100);
+      expect(Number((await getAccount(connection, contactTokenAccount)).amount)).to.equal(400);
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.currentFunding.toNumber()).to.equal(100);
+      const contribution = (await program.account.sponsorContributionAccount.fetch(
+        await sponsorContributionAddress(program, treatmentPassPDA, diasporaContactKeypair.publicKey)
+      )).contribution;
+      expect(contribution.amountContributed.toNumber()).to.equal(100);
+      const remittancePledge = await pledge();
+      expect(remittancePledge.totalRemitted.toNumber()).to.equal(100);
+      expect(remittancePledge.remainingPeriods).to.equal(2);
+
+      await expectRejected(() => executePledge(), 'Next remittance is not yet due');
+    });
+
+    it('should cancel the pledge and revoke its delegation once the pass is cancelled', async () => {
+      await program.methods
+        .cancelTreatment('Treatment found closer to home')
+        .accounts({
+          treatmentPassAccount: treatmentPassPDA,
+          authority: remittingPatientKeypair.publicKey,
+          authorityEntry: null,
+          patientRepresentative: null,
+        })
+        .signers([remittingPatientKeypair])
+        .rpc();
+
+      await executePledge({ contributorKeypair: diasporaContactKeypair });
+
+      expect((await pledge()).status).to.have.property('cancelled');
+      expect((await getAccount(connection, contactTokenAccount)).delegate).to.be.null;
+      expect(Number((await getAccount(connection, escrowPDA)).amount)).to.equal(100);
+      await expectRejected(() => executePledge(), 'Remittance pledge is no longer active');
+    });
+  });
+
+  describe('Patient Representative Authorization', () => {
+    let representedPatientKeypair;
+    let caseworkerKeypair;
+    let caregiverKeypair;
+    let fundingMint;
+
+    async function appoint(representativeKeypair, role, scopes, expiresAt = null) {
+      return appointRepresentative(program, {
+        patientKeypair: representedPatientKeypair,
+        representativeKeypair,
+        role,
+        scopes,
+        expiresAt,
+      });
+    }
+
+    before(async () => {
+      representedPatientKeypair = Keypair.generate();
+      caseworkerKeypair = Keypair.generate();
+      caregiverKeypair = Keypair.generate();
+      await airdrop(connection, representedPatientKeypair.publicKey, 2);
+      await airdrop(connection, caseworkerKeypair.publicKey, 2);
+      await airdrop(connection, caregiverKeypair.publicKey, 2);
+      fundingMint = await createMint(connection, provider.wallet.payer, provider.wallet.publicKey, null, 0);
+    });
+
+    it('should let a representative holding ManageTreatment create a pass for the patient', async () => {
+      const caseworkerPDA = await appoint(caseworkerKeypair, { caseworker: {} }, [{ manageTreatment: {} }]);
+
+      const treatmentPassPDA = await createTreatmentPass(program, {
+        patient: representedPatientKeypair.publicKey,
+        creator: caseworkerKeypair,
+        passId: 2004,
+        amounts: [500],
+        fundingMint,
+        patientRepresentative: caseworkerPDA,
+      });
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.patient.toString()).to.equal(representedPatientKeypair.publicKey.toString());
+    });
+
+    it('should reject a representative acting outside their granted scopes', async () => {
+      const caregiverPDA = await appoint(caregiverKeypair, { caregiver: {} }, [{ logRecovery: {} }]);
+
+      try {
+        await createTreatmentPass(program, {
+          patient: representedPatientKeypair.publicKey,
+          creator: caregiverKeypair,
+          passId: 2005,
+          amounts: [500],
+          fundingMint,
+          patientRepresentative: caregiverPDA,
+        });
+        expect.fail('Should have rejected a representative without ManageTreatment');
+      } catch (error) {
+        expect(error.message).to.include('Signer is neither the patient nor an appointed representative');
+      }
+    });
+
+    it('should stop honouring an appointment once it expires', async () => {
+      const now = await chainTime(connection);
+      try {
+        await appoint(Keypair.generate(), { familyMember: {} }, [{ manageTreatment: {} }], now - 60);
+        expect.fail('Should have rejected an appointment that has already expired');
+      } catch (error) {
+        expect(error.message).to.include('Representative appointment must expire in the future');
+      }
+
+      const caregiverPDA = await representativeAddress(
+        program, representedPatientKeypair.publicKey, caregiverKeypair.publicKey
+      );
+      await program.methods
+        .updatePatientRepresentative([{ manageTreatment: {} }], new anchor.BN(now + 5))
+        .accounts({
+          representativeAccount: caregiverPDA,
+          patient: representedPatientKeypair.publicKey,
+        })
+        .signers([representedPatientKeypair])
+        .rpc();
+      await waitForChainTime(connection, now + 5);
+
+      try {
+        await createTreatmentPass(program, {
+          patient: representedPatientKeypair.publicKey,
+          creator: caregiverKeypair,
+          passId: 2005,
+          amounts: [500],
+          fundingMint,
+          patientRepresentative: caregiverPDA,
+        });
+        expect.fail('Should have rejected an expired representative');
+      } catch (error) {
+        expect(error.message).to.include('Signer is neither the patient nor an appointed representative');
+      }
+    });
+
+    it('should stop honouring a representative once the patient revokes them', async () => {
+      const caseworkerPDA = await representativeAddress(
+        program, representedPatientKeypair.publicKey, caseworkerKeypair.publicKey
+      );
+      const treatmentPassPDA = await treatmentPassAddress(program, representedPatientKeypair.publicKey, 2004);
+
+      await program.methods
+        .revokePatientRepresentative()
+        .accounts({
+          representativeAccount: caseworkerPDA,
+          patient: representedPatientKeypair.publicKey,
+        })
+        .signers([representedPatientKeypair])
+        .rpc();
+
+      try {
+        await program.methods
+          .cancelTreatment('Cancelled by former caseworker')
+          .accounts({
+            treatmentPassAccount: treatmentPassPDA,
+            authority: caseworkerKeypair.publicKey,
+            authorityEntry: null,
+            patientRepresentative: null,
+          })
+          .signers([caseworkerKeypair])
+          .rpc();
+        expect.fail('Should have rejected a revoked representative');
+      } catch (error) {
+        expect(error.message).to.include("Signer is not allowed to change this treatment's status");
+      }
+
+      const treatmentPass = (await program.account.treatmentPassAccount.fetch(treatmentPassPDA)).treatmentPass;
+      expect(treatmentPass.status).to.have.property('fundingRequired');
+    });
+  });
+
   describe('Error Handling and Edge Cases', () => {
     it('should prevent unauthorized elder council actions', async () => {
       const [ubuntuHealthPDA] = await PublicKey.findProgramAddress(